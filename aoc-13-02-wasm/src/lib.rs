@@ -1,5 +1,5 @@
+use intcode::{input_fn, Computer, IntcodeError, State};
 use wasm_bindgen::prelude::*;
-use std::collections::HashMap;
 use std::fmt;
 
 #[wasm_bindgen]
@@ -49,7 +49,7 @@ pub struct Screen {
     width: usize,
     height: usize,
     data: Vec<Tile>,
-    intcode_computer: Computer,
+    arcade: Arcade,
 }
 
 #[wasm_bindgen]
@@ -59,12 +59,14 @@ impl Screen {
             width: SCREEN_WIDTH,
             height: SCREEN_HEIGHT,
             data: vec![Tile::Empty; SCREEN_WIDTH * SCREEN_HEIGHT],
-            intcode_computer: Computer::new(),
+            arcade: Arcade::new(),
         }
     }
 
-    pub fn run(&mut self) -> bool {
-        self.intcode_computer.run(&mut self.data)
+    pub fn run(&mut self) -> Result<bool, JsValue> {
+        self.arcade
+            .run(&mut self.data)
+            .map_err(|error| JsValue::from_str(&error.to_string()))
     }
 
     pub fn render(&self) -> String {
@@ -84,17 +86,23 @@ impl Screen {
     }
 
     pub fn score(&self) -> i64 {
-        self.intcode_computer.score
+        self.arcade.score
     }
 
     pub fn set_joystick(&mut self, joystick: Joystick) {
-        self.intcode_computer.set_joystick(joystick);
+        self.arcade.set_joystick(joystick);
+    }
+}
+
+impl Default for Screen {
+    fn default() -> Screen {
+        Screen::new()
     }
 }
 
 impl fmt::Display for Screen {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for line in self.data.as_slice().chunks(self.width as usize) {
+        for line in self.data.as_slice().chunks(self.width) {
             for &tile in line {
                 let symbol = match tile {
                     Tile::Empty => " ",
@@ -105,7 +113,7 @@ impl fmt::Display for Screen {
                 };
                 write!(f, "{}", symbol)?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
 
         Ok(())
@@ -113,35 +121,28 @@ impl fmt::Display for Screen {
 }
 
 
-struct Computer {
-    program: HashMap<usize, i64>,
-    current_position: usize,
-    relative_base: usize,
+struct Arcade {
+    computer: Computer,
     output_x: Option<i64>,
     output_y: Option<i64>,
     score: i64,
-    initial_render_complete: bool,
     joystick: Joystick,
 }
 
-impl Computer {
-    fn new() -> Computer {
+impl Arcade {
+    fn new() -> Arcade {
         let program_input = include_str!("../input");
-        let program: HashMap<usize, i64> = program_input
+        let program: Vec<i64> = program_input
             .trim()
             .split(",")
             .map(|n| n.parse().expect("input should have been a number"))
-            .enumerate()
             .collect();
 
-        Computer {
-            program,
-            current_position: 0,
-            relative_base: 0,
+        Arcade {
+            computer: Computer::new(program),
             output_x: None,
             output_y: None,
             score: 0,
-            initial_render_complete: false,
             joystick: Joystick::Neutral,
         }
     }
@@ -150,225 +151,56 @@ impl Computer {
         self.joystick = joystick;
     }
 
-    fn run(&mut self, data: &mut [Tile]) -> bool {
-        let mut current_inst = self.current_instruction();
-
-        while current_inst.opcode != 99 {
-            match current_inst.opcode {
-                1 => {
-                    let input1 = self.get_value(0);
-                    let input2 = self.get_value(1);
-                    let answer = input1 + input2;
-                    self.set_value(2, answer);
-                    self.current_position += 4;
-                }
-                2 => {
-                    let input1 = self.get_value(0);
-                    let input2 = self.get_value(1);
-                    let answer = input1 * input2;
-                    self.set_value(2, answer);
-                    self.current_position += 4;
-                }
-                3 => {
-                    self.set_value(0, self.joystick.as_intcode_value());
-                    self.current_position += 2;
+    // Runs one tick of the game: the program reads the joystick once, then draws until it wants
+    // to read the joystick again. The very first tick also draws the whole starting screen.
+    fn run(&mut self, data: &mut [Tile]) -> Result<bool, IntcodeError> {
+        let joystick = self.joystick;
+        let mut joystick_read = false;
+        let mut output = vec![];
+
+        let state = self.computer.run_until_input(&mut (
+            input_fn(|| {
+                if joystick_read {
+                    None
+                } else {
+                    joystick_read = true;
+                    Some(joystick.as_intcode_value())
                 }
-                4 => {
-                    let value = self.get_value(0);
-                    self.current_position += 2;
+            }),
+            &mut output,
+        ))?;
 
-                    match (self.output_x, self.output_y) {
-                        (None, None) => {
-                            self.output_x = Some(value);
-                        }
-                        (Some(_), None) => {
-                            self.output_y = Some(value);
-                        }
-                        (Some(-1), Some(0)) => {
-                            self.score = value;
-
-                            self.output_x = None;
-                            self.output_y = None;
-                        }
-                        (Some(x), Some(y)) => {
-                            let tile_value: Tile = value.into();
-
-                            let index = y as usize * SCREEN_WIDTH + x as usize;
-                            data[index] = tile_value;
-
-                            self.output_x = None;
-                            self.output_y = None;
-
-                            if self.initial_render_complete {
-                                return false;
-                            } else {
-                                if index == data.len() - 1 {
-                                    self.initial_render_complete = true;
-                                }
-                            }
-                        }
-                        _ => unreachable!(),
-                    }
-                }
-                5 => {
-                    // jump-if-true
-                    let test_value = self.get_value(0);
-                    if test_value != 0 {
-                        let jump_location = self.get_value(1);
-                        self.current_position = jump_location as usize;
-                    } else {
-                        self.current_position += 3;
-                    }
-                }
-                6 => {
-                    // jump-if-false
-                    let test_value = self.get_value(0);
-                    if test_value == 0 {
-                        let jump_location = self.get_value(1);
-                        self.current_position = jump_location as usize;
-                    } else {
-                        self.current_position += 3;
-                    }
-                }
-                7 => {
-                    // less-than
-                    let input1 = self.get_value(0);
-                    let input2 = self.get_value(1);
-                    let answer = if input1 < input2 { 1 } else { 0 };
-                    self.set_value(2, answer);
-                    self.current_position += 4;
-                }
-                8 => {
-                    // equals
-                    let input1 = self.get_value(0);
-                    let input2 = self.get_value(1);
-                    let answer = if input1 == input2 { 1 } else { 0 };
-                    self.set_value(2, answer);
-                    self.current_position += 4;
-                }
-                9 => {
-                    // relative base adjustment
-                    let input1 = self.get_value(0);
-                    let new_rel_base = self.relative_base as i64 + input1;
-                    self.relative_base = new_rel_base as usize;
-                    self.current_position += 2;
-                }
-                other => panic!("Unknown opcode: {}", other),
-            }
-            current_inst = self.current_instruction();
+        for value in output {
+            self.handle_output(value, data);
         }
 
-        return true;
+        Ok(state == State::Halted)
     }
-}
 
-impl Computer {
-    fn current_instruction(&self) -> Instruction {
-        instruction(self.read_at(self.current_position))
-    }
-
-    fn get_value(&self, parameter_index: usize) -> i64 {
-        get_value(&self.program, self.current_position, &self.current_instruction(), parameter_index, self.relative_base)
-    }
-
-    fn set_value(&mut self, parameter_index: usize, value: i64) {
-        let instruction = self.current_instruction();
-        set_value(&mut self.program, self.current_position, &instruction, parameter_index, self.relative_base, value);
-    }
-
-    fn read_at(&self, index: usize) -> i64 {
-        self.program.get(&index).copied().unwrap_or(0)
-    }
-}
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Mode {
-    Position,
-    Immediate,
-    Relative,
-}
-
-#[derive(Debug, PartialEq)]
-struct Instruction {
-    opcode: i64,
-    modes: Vec<Mode>,
-}
-
-impl Instruction {
-    fn mode(&self, parameter: usize) -> Mode {
-        self.modes.get(parameter).copied().unwrap_or(Mode::Position)
-    }
-}
-
-fn instruction(mut full_opcode: i64) -> Instruction {
-    let opcode = full_opcode % 100;
-    full_opcode /= 100;
-
-    let mut modes = vec![];
-
-    while full_opcode > 0 {
-        let mode = match full_opcode % 10 {
-            0 => Mode::Position,
-            1 => Mode::Immediate,
-            2 => Mode::Relative,
-            other => panic!("Unexpected parameter mode: {}", other),
-        };
-        modes.push(mode);
-        full_opcode /= 10;
-    }
-
-    Instruction { opcode, modes }
-}
-
-fn get_value(
-    program: &HashMap<usize, i64>,
-    instruction_pointer: usize,
-    inst: &Instruction,
-    parameter_index: usize,
-    relative_base: usize,
-) -> i64 {
-    let parameter_location = instruction_pointer + parameter_index + 1;
+    fn handle_output(&mut self, value: i64, data: &mut [Tile]) {
+        match (self.output_x, self.output_y) {
+            (None, None) => {
+                self.output_x = Some(value);
+            }
+            (Some(_), None) => {
+                self.output_y = Some(value);
+            }
+            (Some(-1), Some(0)) => {
+                self.score = value;
 
-    match inst.mode(parameter_index) {
-        Mode::Position => {
-            let position = program.get(&parameter_location).copied().unwrap_or(0) as usize;
-            program.get(&position).copied().unwrap_or(0)
-        },
-        Mode::Immediate => program.get(&parameter_location).copied().unwrap_or(0),
-        Mode::Relative => {
-            let offset = program.get(&parameter_location).copied().unwrap_or(0);
-            let memory_location = offset + relative_base as i64;
-            if memory_location < 0 {
-                panic!("Cannot access memory at {}", memory_location);
+                self.output_x = None;
+                self.output_y = None;
             }
-            program.get(&(memory_location as usize)).copied().unwrap_or(0)
-        },
-    }
-}
+            (Some(x), Some(y)) => {
+                let tile_value: Tile = value.into();
 
-fn set_value(
-    program: &mut HashMap<usize, i64>,
-    instruction_pointer: usize,
-    inst: &Instruction,
-    parameter_index: usize,
-    relative_base: usize,
-    value: i64,
-) {
-    let parameter_location = instruction_pointer + parameter_index + 1;
+                let index = y as usize * SCREEN_WIDTH + x as usize;
+                data[index] = tile_value;
 
-    match inst.mode(parameter_index) {
-        Mode::Position => {
-            let position = program.get(&parameter_location).copied().unwrap_or(0) as usize;
-            program.insert(position, value);
-        },
-        Mode::Immediate => unreachable!("Can't set values in immediate mode"),
-        Mode::Relative => {
-            let offset = program.get(&parameter_location).copied().unwrap_or(0);
-            let memory_location = offset + relative_base as i64;
-            if memory_location < 0 {
-                panic!("Cannot access memory at {}", memory_location);
+                self.output_x = None;
+                self.output_y = None;
             }
-            program.insert(memory_location as usize, value);
-        },
+            _ => unreachable!(),
+        }
     }
 }
@@ -1,10 +1,10 @@
 fn main() {
     let mut screen = aoc_13_02::Screen::new();
-    let mut game_over = screen.run();
+    let mut game_over = screen.run().expect("Intcode program should have run");
 
     while !game_over {
         println!("{}", screen);
-        game_over = screen.run();
+        game_over = screen.run().expect("Intcode program should have run");
     }
 
     println!("score: {}", screen.score());
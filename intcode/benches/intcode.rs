@@ -0,0 +1,99 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use intcode::{parse_program, Computer, Io};
+use std::hint::black_box;
+
+// Made by `intcode compile`, and checked against the interpreter by the tests
+mod day_09 {
+    include!("../tests/compiled/day_09.rs");
+}
+mod day_13 {
+    include!("../tests/compiled/day_13.rs");
+}
+
+// Day 9 part 2: the BOOST program in sensor boost mode
+fn boost(c: &mut Criterion) {
+    let program = parse_program(include_str!("../../aoc-09-02/input")).unwrap();
+
+    c.bench_function("day 9 BOOST", |b| {
+        b.iter(|| {
+            let mut output = vec![];
+            Computer::new(black_box(program.clone()))
+                .run(&mut (vec![2].into_iter(), &mut output))
+                .unwrap();
+            output
+        })
+    });
+
+    c.bench_function("day 9 BOOST compiled", |b| {
+        b.iter(|| {
+            let mut output = vec![];
+            day_09::run(
+                &mut Computer::new(black_box(program.clone())),
+                &mut (vec![2].into_iter(), &mut output),
+            )
+            .unwrap();
+            output
+        })
+    });
+}
+
+// Day 13 part 2 without the screen: keeps the paddle under the ball until every block is gone.
+struct Breakout {
+    output: Vec<i64>,
+    ball_x: i64,
+    paddle_x: i64,
+    score: i64,
+}
+
+impl Io for Breakout {
+    fn input(&mut self) -> Option<i64> {
+        Some((self.ball_x - self.paddle_x).signum())
+    }
+
+    fn output(&mut self, value: i64) {
+        self.output.push(value);
+
+        if let [x, y, tile] = self.output[..] {
+            match (x, y, tile) {
+                (-1, 0, score) => self.score = score,
+                (x, _, 3) => self.paddle_x = x,
+                (x, _, 4) => self.ball_x = x,
+                _ => {}
+            }
+            self.output.clear();
+        }
+    }
+}
+
+fn breakout(c: &mut Criterion) {
+    let mut program = parse_program(include_str!("../../aoc-13-02/input")).unwrap();
+    program[0] = 2;
+
+    let new_game = || Breakout {
+        output: vec![],
+        ball_x: 0,
+        paddle_x: 0,
+        score: 0,
+    };
+
+    c.bench_function("day 13 breakout", |b| {
+        b.iter(|| {
+            let mut game = new_game();
+            Computer::new(black_box(program.clone()))
+                .run(&mut game)
+                .unwrap();
+            game.score
+        })
+    });
+
+    c.bench_function("day 13 breakout compiled", |b| {
+        b.iter(|| {
+            let mut game = new_game();
+            day_13::run(&mut Computer::new(black_box(program.clone())), &mut game).unwrap();
+            game.score
+        })
+    });
+}
+
+criterion_group!(benches, boost, breakout);
+criterion_main!(benches);
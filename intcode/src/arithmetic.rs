@@ -0,0 +1,176 @@
+use num_bigint::{BigInt, Sign};
+use std::convert::TryFrom;
+
+// What the `Computer` does when an add or multiply doesn't fit in an i64.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Arithmetic {
+    // Stop with `IntcodeError::Overflow`
+    #[default]
+    Checked,
+    // Keep the low 64 bits, like a release build of plain i64 math would
+    Wrapping,
+    // Keep the whole value. It can still be compared, tested by a jump, or output to an `Io` that
+    // takes big values, but not used as an address.
+    BigInt,
+}
+
+// The low 64 bits of `value`, as two's complement.
+pub(crate) fn wrap(value: &BigInt) -> i64 {
+    let (sign, digits) = value.to_u64_digits();
+    let low = digits.first().copied().unwrap_or(0);
+
+    match sign {
+        Sign::Minus => low.wrapping_neg() as i64,
+        _ => low as i64,
+    }
+}
+
+pub(crate) fn fits(value: &BigInt) -> bool {
+    i64::try_from(value).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BigOutput, Computer, IntcodeError, State};
+
+    fn run_with(arithmetic: Arithmetic, program: Vec<i64>) -> Result<Vec<BigInt>, IntcodeError> {
+        let mut output = BigOutput::default();
+        Computer::with_arithmetic(program, arithmetic)
+            .run(&mut (vec![].into_iter(), &mut output))?;
+        Ok(output.0)
+    }
+
+    fn big(value: &str) -> BigInt {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn day_9_large_numbers_work_with_every_arithmetic() {
+        for &arithmetic in &[
+            Arithmetic::Checked,
+            Arithmetic::Wrapping,
+            Arithmetic::BigInt,
+        ] {
+            let program = vec![1102, 34915192, 34915192, 7, 4, 7, 99, 0];
+            assert_eq!(
+                run_with(arithmetic, program),
+                Ok(vec![BigInt::from(1219070632396864_i64)])
+            );
+
+            let program = vec![104, 1125899906842624, 99];
+            assert_eq!(
+                run_with(arithmetic, program),
+                Ok(vec![BigInt::from(1125899906842624_i64)])
+            );
+        }
+    }
+
+    #[test]
+    fn overflow_depends_on_the_arithmetic() {
+        // Squares the day 9 answer, which doesn't fit in an i64
+        let program = vec![2, 7, 7, 7, 4, 7, 99, 1219070632396864];
+
+        assert_eq!(
+            run_with(Arithmetic::Checked, program.clone()),
+            Err(IntcodeError::Overflow {
+                position: 0,
+                opcode: 2,
+            })
+        );
+        assert_eq!(
+            run_with(Arithmetic::Wrapping, program.clone()),
+            Ok(vec![BigInt::from(
+                1219070632396864_i64.wrapping_mul(1219070632396864)
+            )])
+        );
+        assert_eq!(
+            run_with(Arithmetic::BigInt, program),
+            Ok(vec![big("1486133206772489918753597034496")])
+        );
+    }
+
+    #[test]
+    fn big_values_can_be_compared_and_tested() {
+        let program = vec![
+            // [30] = i64::MAX * 4, then [31] = i64::MAX * 3 - 1 with a negative add
+            1002,
+            30,
+            4,
+            30,
+            1001,
+            30,
+            -9223372036854775808,
+            31,
+            // [32] = [31] < [30], [33] = [30] == [30]
+            7,
+            31,
+            30,
+            32,
+            8,
+            30,
+            30,
+            33,
+            // Skips the halt, since [31] isn't 0
+            1005,
+            31,
+            20,
+            99,
+            4,
+            31,
+            4,
+            32,
+            4,
+            33,
+            99,
+            0,
+            0,
+            0,
+            9223372036854775807,
+            0,
+            0,
+            0,
+        ];
+
+        assert_eq!(
+            run_with(Arithmetic::BigInt, program),
+            Ok(vec![
+                big("27670116110564327420"),
+                BigInt::from(1),
+                BigInt::from(1)
+            ])
+        );
+    }
+
+    #[test]
+    fn big_values_cant_be_used_as_addresses() {
+        let program = vec![1002, 7, 2, 7, 9, 7, 99, i64::MAX];
+        let mut computer = Computer::with_arithmetic(program.clone(), Arithmetic::BigInt);
+        let mut io = (vec![].into_iter(), vec![]);
+
+        assert_eq!(computer.step(&mut io), Ok(State::Running));
+        assert_eq!(computer.read_big(7), big("18446744073709551614"));
+        assert_eq!(computer.read_at(7), -2);
+        assert_eq!(
+            computer.run(&mut io),
+            Err(IntcodeError::TooBig {
+                position: 4,
+                opcode: 9,
+                parameter: 0,
+            })
+        );
+
+        // Nor output to something that only takes i64s
+        let mut program = program;
+        program[4] = 4;
+        let mut computer = Computer::with_arithmetic(program, Arithmetic::BigInt);
+        assert_eq!(
+            computer.run(&mut io),
+            Err(IntcodeError::TooBig {
+                position: 4,
+                opcode: 4,
+                parameter: 0,
+            })
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use crate::disassemble::mnemonic;
+use crate::Mode;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+// Assembles the language that `disassemble` prints, plus labels:
+//
+//     ; Counts down from the input to 1
+//             in [counter]
+//     loop:   out [counter]
+//             add [counter], #-1, [counter]
+//             jt [counter], #loop
+//             hlt
+//     counter: .data -1
+//
+// Operands are `[address]` for position mode, `#value` for immediate mode and `rb+offset` for
+// relative mode. Anywhere a number can go, a label can too, optionally with `+n` or `-n` after
+// it. A line that starts with a number followed by a colon, like the disassembler prints, checks
+// that the line really does end up at that address.
+pub fn assemble(source: &str) -> Result<Vec<i64>, AssembleError> {
+    let mut statements = vec![];
+    let mut labels = HashMap::new();
+    let mut address = 0;
+
+    for (index, text) in source.lines().enumerate() {
+        let line = index + 1;
+        let error = |message: String| AssembleError { line, message };
+
+        let mut rest = text.split(';').next().unwrap_or("").trim();
+
+        while let Some(colon) = rest.find(':') {
+            let label = rest[..colon].trim();
+
+            if let Ok(expected) = label.parse::<usize>() {
+                if expected != address {
+                    return Err(error(format!(
+                        "expected to be at address {}, but this is address {}",
+                        expected, address
+                    )));
+                }
+            } else if is_label(label) {
+                if labels.insert(label.to_string(), address).is_some() {
+                    return Err(error(format!(
+                        "label `{}` is defined more than once",
+                        label
+                    )));
+                }
+            } else {
+                return Err(error(format!("`{}` isn't a valid label", label)));
+            }
+
+            rest = rest[colon + 1..].trim();
+        }
+
+        if rest.is_empty() {
+            continue;
+        }
+
+        let statement = parse_statement(rest).map_err(error)?;
+        address += statement.size();
+        statements.push((line, statement));
+    }
+
+    let mut program = Vec::with_capacity(address);
+
+    for (line, statement) in statements {
+        let resolve = |value: &Value| {
+            value
+                .resolve(&labels)
+                .map_err(|message| AssembleError { line, message })
+        };
+
+        match statement {
+            Statement::Data(values) => {
+                for value in &values {
+                    program.push(resolve(value)?);
+                }
+            }
+            Statement::Instruction { opcode, operands } => {
+                let mut full_opcode = opcode;
+                let mut place = 100;
+
+                for (mode, _) in &operands {
+                    full_opcode += place * mode_digit(*mode);
+                    place *= 10;
+                }
+                program.push(full_opcode);
+
+                for (_, value) in &operands {
+                    program.push(resolve(value)?);
+                }
+            }
+        }
+    }
+
+    Ok(program)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AssembleError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for AssembleError {}
+
+enum Statement {
+    Data(Vec<Value>),
+    Instruction {
+        opcode: i64,
+        operands: Vec<(Mode, Value)>,
+    },
+}
+
+impl Statement {
+    fn size(&self) -> usize {
+        match self {
+            Statement::Data(values) => values.len(),
+            Statement::Instruction { operands, .. } => operands.len() + 1,
+        }
+    }
+}
+
+enum Value {
+    Number(i64),
+    Label(String, i64),
+}
+
+impl Value {
+    fn resolve(&self, labels: &HashMap<String, usize>) -> Result<i64, String> {
+        match self {
+            Value::Number(number) => Ok(*number),
+            Value::Label(label, offset) => {
+                let address = labels
+                    .get(label)
+                    .ok_or_else(|| format!("label `{}` isn't defined", label))?;
+                i64::checked_add(*address as i64, *offset).ok_or_else(|| {
+                    format!("`{}{:+}` is too big for an Intcode value", label, offset)
+                })
+            }
+        }
+    }
+}
+
+fn parse_statement(text: &str) -> Result<Statement, String> {
+    let (name, arguments) = match text.find(char::is_whitespace) {
+        Some(space) => (&text[..space], text[space..].trim()),
+        None => (text, ""),
+    };
+    let arguments: Vec<&str> = if arguments.is_empty() {
+        vec![]
+    } else {
+        arguments.split(',').map(str::trim).collect()
+    };
+
+    if name == ".data" {
+        if arguments.is_empty() {
+            return Err(String::from("`.data` needs at least one value"));
+        }
+        let values = arguments
+            .iter()
+            .map(|argument| parse_value(argument))
+            .collect::<Result<_, _>>()?;
+        return Ok(Statement::Data(values));
+    }
+
+    let opcode = (1..=9)
+        .chain(Some(99))
+        .find(|&opcode| mnemonic(opcode).map(|(known, _, _)| known) == Some(name))
+        .ok_or_else(|| format!("unknown instruction `{}`", name))?;
+    let (_, parameter_count, written_parameter) =
+        mnemonic(opcode).expect("opcode came from the mnemonic table");
+
+    if arguments.len() != parameter_count {
+        return Err(format!(
+            "`{}` takes {} operands, but got {}",
+            name,
+            parameter_count,
+            arguments.len()
+        ));
+    }
+
+    let operands = arguments
+        .iter()
+        .map(|argument| parse_operand(argument))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(i) = written_parameter {
+        if operands[i].0 == Mode::Immediate {
+            return Err(format!(
+                "`{}` writes to operand {}, which can't be in immediate mode",
+                name,
+                i + 1
+            ));
+        }
+    }
+
+    Ok(Statement::Instruction { opcode, operands })
+}
+
+fn parse_operand(text: &str) -> Result<(Mode, Value), String> {
+    if text.starts_with('[') && text.ends_with(']') {
+        Ok((Mode::Position, parse_value(&text[1..text.len() - 1])?))
+    } else if let Some(value) = text.strip_prefix('#') {
+        Ok((Mode::Immediate, parse_value(value)?))
+    } else if let Some(offset) = text.strip_prefix("rb") {
+        let offset = offset.trim();
+        let value = if offset.is_empty() {
+            Value::Number(0)
+        } else if let Some(positive) = offset.strip_prefix('+') {
+            parse_value(positive)?
+        } else if offset.starts_with('-') {
+            match parse_value(offset)? {
+                Value::Number(number) => Value::Number(number),
+                Value::Label(..) => return Err(format!("can't subtract a label in `{}`", text)),
+            }
+        } else {
+            return Err(format!("`{}` isn't a valid operand", text));
+        };
+        Ok((Mode::Relative, value))
+    } else {
+        Err(format!(
+            "`{}` isn't a valid operand; use `[address]`, `#value` or `rb+offset`",
+            text
+        ))
+    }
+}
+
+fn parse_value(text: &str) -> Result<Value, String> {
+    let text = text.trim();
+
+    if let Ok(number) = text.parse() {
+        return Ok(Value::Number(number));
+    }
+
+    let (label, offset) = match text.find(['+', '-']) {
+        Some(sign) => {
+            let offset = text[sign..].replace(' ', "");
+            let offset = offset
+                .trim_start_matches('+')
+                .parse()
+                .map_err(|_| format!("`{}` isn't a valid offset", &text[sign..]))?;
+            (text[..sign].trim(), offset)
+        }
+        None => (text, 0),
+    };
+
+    if is_label(label) {
+        Ok(Value::Label(label.to_string(), offset))
+    } else {
+        Err(format!("`{}` isn't a number or a label", text))
+    }
+}
+
+fn is_label(text: &str) -> bool {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+fn mode_digit(mode: Mode) -> i64 {
+    match mode {
+        Mode::Position => 0,
+        Mode::Immediate => 1,
+        Mode::Relative => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disassemble;
+
+    #[test]
+    fn assemble_program_with_labels() {
+        let source = "
+            ; Counts down from the input to 1
+                    in [counter]
+            loop:   out [counter]
+                    add [counter], #-1, [counter]
+                    jt [counter], #loop
+                    hlt
+            counter: .data -1
+        ";
+
+        assert_eq!(
+            assemble(source),
+            Ok(vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, -1])
+        );
+    }
+
+    #[test]
+    fn assemble_every_mode_and_label_offsets() {
+        let source = "
+            start: arb #end
+                   add rb-1, rb, rb+2
+                   mul [start+1], #end-2, [7]
+            end:   .data 5, start, end+1
+        ";
+
+        assert_eq!(
+            assemble(source),
+            Ok(vec![109, 10, 22201, -1, 0, 2, 1002, 1, 8, 7, 5, 0, 11])
+        );
+    }
+
+    #[test]
+    fn assemble_what_the_disassembler_prints() {
+        let program = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99, 42, -3,
+        ];
+        let listing: Vec<String> = disassemble(&program)
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        assert_eq!(assemble(&listing.join("\n")), Ok(program));
+    }
+
+    #[test]
+    fn errors_say_which_line_is_wrong() {
+        let error = |source: &str| assemble(source).unwrap_err().to_string();
+
+        assert_eq!(error("hlt\nfoo #1"), "line 2: unknown instruction `foo`");
+        assert_eq!(
+            error("add #1, #2"),
+            "line 1: `add` takes 3 operands, but got 2"
+        );
+        assert_eq!(
+            error("\n\nin #4"),
+            "line 3: `in` writes to operand 1, which can't be in immediate mode"
+        );
+        assert_eq!(
+            error("out 4"),
+            "line 1: `4` isn't a valid operand; use `[address]`, `#value` or `rb+offset`"
+        );
+        assert_eq!(
+            error("hlt\njt #1, #nowhere"),
+            "line 2: label `nowhere` isn't defined"
+        );
+        assert_eq!(
+            error("a: hlt\na: hlt"),
+            "line 2: label `a` is defined more than once"
+        );
+        assert_eq!(
+            error("0: hlt\n2: hlt"),
+            "line 2: expected to be at address 2, but this is address 1"
+        );
+        assert_eq!(error(".data"), "line 1: `.data` needs at least one value");
+        assert_eq!(
+            error("hlt\nend: .data end+9223372036854775807"),
+            "line 2: `end+9223372036854775807` is too big for an Intcode value"
+        );
+    }
+}
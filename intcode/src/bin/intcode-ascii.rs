@@ -0,0 +1,32 @@
+use intcode::{parse_program, AsciiIo, Computer, State};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::process;
+
+const USAGE: &str = "usage: intcode-ascii <program file> [<script file>]";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (path, script) = match args.as_slice() {
+        [path] => (path, None),
+        [path, script] => (path, Some(fs::read_to_string(script)?)),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+
+    let program = parse_program(&fs::read_to_string(path)?)?;
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut io = AsciiIo::new(stdin.lock(), stdout.lock()).script(script.as_deref().unwrap_or(""));
+
+    if Computer::new(program).run_until_input(&mut io)? == State::NeedsInput {
+        eprintln!("the input ran out before the program halted");
+        process::exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use intcode::config::NetworkConfig;
+use intcode::Outcome;
+use std::env;
+use std::error::Error;
+use std::path::Path;
+use std::process;
+
+const USAGE: &str = "usage: intcode-network <network config file>";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let path = match args.as_slice() {
+        [path] => Path::new(path),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    };
+
+    let config = NetworkConfig::load(path)?;
+    let mut network = config.build(path.parent().unwrap_or_else(|| Path::new(".")))?;
+    let outcome = network.run();
+
+    for (address, node) in config.nodes.iter().enumerate() {
+        match network.last_output(address) {
+            Some(value) => println!("{}: {}", node.name, value),
+            None => println!("{}: no output", node.name),
+        }
+    }
+    let output: Vec<String> = network.output.iter().map(i64::to_string).collect();
+    println!("output: {}", output.join(","));
+
+    if outcome? == Outcome::Idle {
+        println!("the network went idle");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,536 @@
+use crate::disassemble::{decode_at, Line, Operand};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write;
+
+// A run of instructions that always run one after another: nothing jumps into the middle, and
+// only the last one can jump or halt.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Block {
+    pub instructions: Vec<Line>,
+    // The blocks this one can go on to next
+    pub successors: Vec<usize>,
+    // Whether it ends with a jump to an address read from memory, like a return
+    pub computed_jump: bool,
+    pub reachable: bool,
+}
+
+impl Block {
+    pub fn start(&self) -> usize {
+        self.instructions[0].address()
+    }
+
+    pub fn end(&self) -> usize {
+        let last = &self.instructions[self.instructions.len() - 1];
+        last.address() + last.size()
+    }
+}
+
+// An instruction at `position` that writes to `address`, which is part of the instruction at
+// `instruction`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CodeWrite {
+    pub position: usize,
+    pub address: usize,
+    pub instruction: usize,
+}
+
+// The blocks of a program, found by following it from address 0. Jumps with immediate targets go
+// where they say. A computed jump could go anywhere, so it's assumed to go to any instruction whose
+// address reachable code stores as an immediate value, like a return address being pushed. The
+// instruction after a jump or a halt is decoded too, even if nothing seems to get there, and ends
+// up in an unreachable block if it really is unreachable.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ControlFlowGraph {
+    pub blocks: BTreeMap<usize, Block>,
+    pub code_writes: Vec<CodeWrite>,
+}
+
+impl ControlFlowGraph {
+    pub fn new(program: &[i64]) -> ControlFlowGraph {
+        let instructions = find_instructions(program);
+        let code_writes = find_code_writes(&instructions);
+
+        let mut starts = BTreeSet::new();
+        starts.insert(0);
+        for line in instructions.values() {
+            if let Some(target) = static_target(line) {
+                starts.insert(target);
+            }
+            if jump_target(line).is_some() || !falls_through(line) {
+                starts.insert(line.address() + line.size());
+            }
+            starts.extend(address_taken(line));
+        }
+        // The interpreter runs instructions the program has written over, so whatever comes
+        // after them has to make sense on its own
+        for write in &code_writes {
+            starts.insert(write.instruction + instructions[&write.instruction].size());
+        }
+
+        let mut blocks = BTreeMap::new();
+        for &start in starts
+            .iter()
+            .filter(|start| instructions.contains_key(start))
+        {
+            let mut block = Block {
+                instructions: vec![],
+                successors: vec![],
+                computed_jump: false,
+                reachable: false,
+            };
+
+            let mut address = start;
+            loop {
+                let line = &instructions[&address];
+                block.instructions.push(line.clone());
+                address += line.size();
+
+                if !falls_through(line)
+                    || !instructions.contains_key(&address)
+                    || starts.contains(&address)
+                {
+                    break;
+                }
+            }
+
+            let last = &block.instructions[block.instructions.len() - 1];
+            match (static_target(last), jump_target(last)) {
+                (Some(target), _) => block.successors.push(target),
+                (None, Some(_)) => block.computed_jump = true,
+                (None, None) => {}
+            }
+            if falls_through(last) {
+                block.successors.push(address);
+            }
+            block
+                .successors
+                .retain(|successor| instructions.contains_key(successor));
+            block.successors.dedup();
+
+            blocks.insert(start, block);
+        }
+
+        let mut graph = ControlFlowGraph {
+            blocks,
+            code_writes,
+        };
+        graph.mark_reachable();
+        // Only writes that can actually happen are worth knowing about
+        let blocks = &graph.blocks;
+        graph.code_writes.retain(|write| {
+            blocks
+                .range(..=write.position)
+                .next_back()
+                .is_some_and(|(_, block)| block.reachable)
+        });
+        graph
+    }
+
+    fn mark_reachable(&mut self) {
+        let mut reached = BTreeSet::new();
+        let mut queue = vec![0];
+        let mut taken = BTreeSet::new();
+        let mut computed_jump = false;
+
+        while !queue.is_empty() {
+            while let Some(start) = queue.pop() {
+                let block = match self.blocks.get(&start) {
+                    Some(block) if reached.insert(start) => block,
+                    _ => continue,
+                };
+                queue.extend(&block.successors);
+                computed_jump |= block.computed_jump;
+                taken.extend(block.instructions.iter().flat_map(address_taken));
+            }
+
+            if computed_jump {
+                let blocks = &self.blocks;
+                queue.extend(
+                    taken
+                        .iter()
+                        .filter(|start| blocks.contains_key(start) && !reached.contains(start)),
+                );
+            }
+        }
+
+        for (start, block) in &mut self.blocks {
+            block.reachable = reached.contains(start);
+        }
+    }
+
+    // The block `address` is part of, if any.
+    pub fn block_at(&self, address: usize) -> Option<&Block> {
+        self.blocks
+            .range(..=address)
+            .next_back()
+            .map(|(_, block)| block)
+            .filter(|block| address < block.end())
+    }
+
+    pub fn unreachable(&self) -> impl Iterator<Item = &Block> {
+        self.blocks.values().filter(|block| !block.reachable)
+    }
+
+    // Everywhere a jump with an immediate target can go.
+    pub fn jump_targets(&self) -> BTreeSet<usize> {
+        self.blocks
+            .values()
+            .flat_map(|block| block.instructions.iter().filter_map(static_target))
+            .filter(|target| self.blocks.contains_key(target))
+            .collect()
+    }
+
+    // A name for each jump target, for the disassembler to use instead of its address.
+    pub fn labels(&self) -> BTreeMap<usize, String> {
+        self.jump_targets()
+            .into_iter()
+            .map(|target| (target, format!("label_{}", target)))
+            .collect()
+    }
+
+    // The whole program, as the instructions in the blocks, and data everywhere else.
+    pub fn lines(&self, program: &[i64]) -> Vec<Line> {
+        let mut lines = vec![];
+        let mut address = 0;
+
+        while address < program.len() {
+            let line = match self.block_at(address) {
+                Some(block) => block
+                    .instructions
+                    .iter()
+                    .find(|line| line.address() == address)
+                    .cloned(),
+                None => None,
+            }
+            .unwrap_or(Line::Data {
+                address,
+                value: program[address],
+            });
+
+            address += line.size();
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    // The graph in Graphviz's DOT language. Unreachable blocks are grey and dashed, and writes
+    // into code are red edges from the block that writes to the block that gets written over.
+    pub fn to_dot(&self) -> String {
+        let mut dot =
+            String::from("digraph intcode {\n    node [shape=box, fontname=\"monospace\"];\n");
+
+        for (start, block) in &self.blocks {
+            let label: String = block
+                .instructions
+                .iter()
+                .map(|line| format!("{}\\l", line.to_string().trim_start()))
+                .collect();
+            let style = if block.reachable {
+                ""
+            } else {
+                ", style=dashed, color=grey"
+            };
+            writeln!(dot, "    b{} [label=\"{}\"{}];", start, label, style).unwrap();
+        }
+
+        for (start, block) in &self.blocks {
+            for successor in &block.successors {
+                writeln!(dot, "    b{} -> b{};", start, successor).unwrap();
+            }
+            if block.computed_jump {
+                writeln!(dot, "    b{} -> computed [style=dashed];", start).unwrap();
+            }
+        }
+        if self.blocks.values().any(|block| block.computed_jump) {
+            dot.push_str("    computed [label=\"computed jump\", shape=ellipse];\n");
+        }
+
+        let code_writes: BTreeSet<(usize, usize, usize)> = self
+            .code_writes
+            .iter()
+            .filter_map(|write| {
+                let from = self.block_at(write.position)?;
+                let to = self.block_at(write.address)?;
+                Some((from.start(), to.start(), write.address))
+            })
+            .collect();
+        for (from, to, address) in code_writes {
+            writeln!(
+                dot,
+                "    b{} -> b{} [color=red, label=\"writes [{}]\"];",
+                from, to, address
+            )
+            .unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// Every instruction that can be found from address 0. The instruction after a jump or a halt is
+// a guess, since it's usually where a call returns to; the guesses are only followed once nothing
+// else is left, so data that happens to look like an instruction can't claim the values of a
+// real one.
+fn find_instructions(program: &[i64]) -> BTreeMap<usize, Line> {
+    let mut instructions = BTreeMap::new();
+    let mut claimed = vec![false; program.len()];
+    let mut reachable = VecDeque::from(vec![0]);
+    let mut guesses = VecDeque::new();
+
+    while let Some(address) = reachable.pop_front().or_else(|| guesses.pop_front()) {
+        if address >= program.len() || instructions.contains_key(&address) {
+            continue;
+        }
+        let line = match decode_at(program, address) {
+            Some(line) => line,
+            None => continue,
+        };
+
+        let next = address + line.size();
+        if claimed[address..next].iter().any(|&claimed| claimed) {
+            continue;
+        }
+        claimed[address..next]
+            .iter_mut()
+            .for_each(|claimed| *claimed = true);
+
+        if let Some(target) = static_target(&line) {
+            reachable.push_back(target);
+        }
+        if falls_through(&line) {
+            reachable.push_back(next);
+        } else {
+            guesses.push_back(next);
+        }
+
+        instructions.insert(address, line);
+    }
+
+    instructions
+}
+
+fn find_code_writes(instructions: &BTreeMap<usize, Line>) -> Vec<CodeWrite> {
+    instructions
+        .values()
+        .filter_map(|line| match written_operand(line) {
+            Some(Operand::Position(address)) => Some((line.address(), address as usize)),
+            _ => None,
+        })
+        .filter_map(|(position, address)| {
+            let (&instruction, written) = instructions.range(..=address).next_back()?;
+            if address < instruction + written.size() {
+                Some(CodeWrite {
+                    position,
+                    address,
+                    instruction,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn operands(line: &Line) -> (&str, &[Operand]) {
+    match line {
+        Line::Instruction {
+            mnemonic, operands, ..
+        } => (mnemonic, operands),
+        Line::Data { .. } => (".data", &[]),
+    }
+}
+
+// Whether the instruction can go on to the one after it.
+pub(crate) fn falls_through(line: &Line) -> bool {
+    match operands(line) {
+        ("hlt", _) | (".data", _) => false,
+        ("jt", [Operand::Immediate(test), _]) => *test == 0,
+        ("jf", [Operand::Immediate(test), _]) => *test != 0,
+        _ => true,
+    }
+}
+
+// Where the instruction jumps to, if it can jump.
+pub(crate) fn jump_target(line: &Line) -> Option<Operand> {
+    match operands(line) {
+        ("jt", [Operand::Immediate(0), _]) => None,
+        ("jf", [Operand::Immediate(test), _]) if *test != 0 => None,
+        ("jt", [_, target]) | ("jf", [_, target]) => Some(*target),
+        _ => None,
+    }
+}
+
+fn static_target(line: &Line) -> Option<usize> {
+    match jump_target(line) {
+        Some(Operand::Immediate(target)) if target >= 0 => Some(target as usize),
+        _ => None,
+    }
+}
+
+// The operand the instruction writes to.
+pub(crate) fn written_operand(line: &Line) -> Option<Operand> {
+    match operands(line) {
+        ("add", operands) | ("mul", operands) | ("lt", operands) | ("eq", operands) => {
+            Some(operands[2])
+        }
+        ("in", operands) => Some(operands[0]),
+        _ => None,
+    }
+}
+
+// A constant the instruction stores that could be an address a computed jump goes to later,
+// like the return address `add #after, #0, rb+0` pushes before a call.
+fn address_taken(line: &Line) -> Option<usize> {
+    let value = match operands(line) {
+        ("add", [Operand::Immediate(a), Operand::Immediate(b), _]) => a.checked_add(*b)?,
+        ("mul", [Operand::Immediate(a), Operand::Immediate(b), _]) => a.checked_mul(*b)?,
+        _ => return None,
+    };
+    if value < 0 {
+        return None;
+    }
+    Some(value as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assemble;
+
+    fn starts<'a>(blocks: impl Iterator<Item = &'a Block>) -> Vec<usize> {
+        blocks.map(Block::start).collect()
+    }
+
+    #[test]
+    fn split_into_blocks_at_jumps_and_their_targets() {
+        let program = assemble(
+            "       in [counter]
+             loop:  out [counter]
+                    add [counter], #-1, [counter]
+                    jt [counter], #loop
+                    hlt
+             counter: .data 0",
+        )
+        .unwrap();
+        let graph = ControlFlowGraph::new(&program);
+
+        assert_eq!(starts(graph.blocks.values()), vec![0, 2, 11]);
+        assert_eq!(graph.blocks[&0].successors, vec![2]);
+        assert_eq!(graph.blocks[&2].successors, vec![2, 11]);
+        assert_eq!(graph.blocks[&2].instructions.len(), 3);
+        assert!(graph.blocks.values().all(|block| block.reachable));
+        assert_eq!(
+            graph.jump_targets().into_iter().collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert!(graph.code_writes.is_empty());
+    }
+
+    #[test]
+    fn find_unreachable_code() {
+        let program = assemble(
+            "       jt #1, #end
+                    out #1
+             end:   hlt",
+        )
+        .unwrap();
+        let graph = ControlFlowGraph::new(&program);
+
+        assert_eq!(starts(graph.blocks.values()), vec![0, 3, 5]);
+        assert_eq!(starts(graph.unreachable()), vec![3]);
+    }
+
+    #[test]
+    fn instructions_dont_overlap() {
+        // The guess after the jump at 0 is the `add` at 3, which overlaps the `out` at 5 the jump
+        // goes to; the real one wins.
+        let program = vec![1105, 1, 5, 1, 0, 104, 7, 99];
+        let graph = ControlFlowGraph::new(&program);
+
+        assert_eq!(starts(graph.blocks.values()), vec![0, 5]);
+        assert_eq!(graph.blocks[&5].instructions.len(), 2);
+    }
+
+    #[test]
+    fn computed_jumps_go_to_return_addresses() {
+        // Calls `double` with the return address in rb+0, the way the day 9 and 13 programs do
+        let program = assemble(
+            "       add #after, #0, rb+0
+                    jt #1, #double
+             after: hlt
+                    out #404
+             double: jf #0, rb+0",
+        )
+        .unwrap();
+        let graph = ControlFlowGraph::new(&program);
+
+        assert_eq!(starts(graph.blocks.values()), vec![0, 7, 8, 10]);
+        assert!(graph.blocks[&10].computed_jump);
+        assert_eq!(starts(graph.unreachable()), vec![8]);
+    }
+
+    #[test]
+    fn find_writes_into_code() {
+        let mut program = assemble(
+            "       add #1, [target], [target]
+                    out [10]
+                    hlt
+             target: .data 0",
+        )
+        .unwrap();
+        // Point the write at the `out`'s operand instead
+        program[3] = 5;
+        let graph = ControlFlowGraph::new(&program);
+
+        assert_eq!(
+            graph.code_writes,
+            vec![CodeWrite {
+                position: 0,
+                address: 5,
+                instruction: 4,
+            }]
+        );
+        assert_eq!(starts(graph.blocks.values()), vec![0, 6]);
+        assert!(graph
+            .to_dot()
+            .contains("b0 -> b0 [color=red, label=\"writes [5]\"];"));
+    }
+
+    #[test]
+    fn labeled_listings_assemble_back_into_the_program() {
+        let program = vec![3, 12, 1005, 12, 7, 104, 0, 4, 12, 1105, 1, 0, 0, 42];
+        let graph = ControlFlowGraph::new(&program);
+        let labels = graph.labels();
+
+        let mut listing = String::new();
+        for line in graph.lines(&program) {
+            if let Some(label) = labels.get(&line.address()) {
+                listing.push_str(&format!("{}:\n", label));
+            }
+            listing.push_str(&format!("{}\n", line.with_labels(&labels)));
+        }
+
+        assert!(listing.contains("    2: jt [12], #label_7\n"));
+        assert_eq!(assemble(&listing).unwrap(), program);
+    }
+
+    #[test]
+    fn dot_output() {
+        let program = vec![1105, 1, 4, 99, 104, 1, 99];
+        let dot = ControlFlowGraph::new(&program).to_dot();
+
+        assert_eq!(
+            dot,
+            "digraph intcode {
+    node [shape=box, fontname=\"monospace\"];
+    b0 [label=\"0: jt #1, #4\\l\"];
+    b3 [label=\"3: hlt\\l\", style=dashed, color=grey];
+    b4 [label=\"4: out #1\\l6: hlt\\l\"];
+    b0 -> b4;
+}
+"
+        );
+    }
+}
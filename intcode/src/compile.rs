@@ -0,0 +1,376 @@
+use crate::cfg::{falls_through, jump_target, ControlFlowGraph};
+use crate::disassemble::{Line, Operand};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+// Translates `program` into the source of a Rust module with `run` and `run_until_input`
+// functions that work like the `Computer` methods, for a crate that depends on this one to
+// `include!`. Every instruction that can be found by following the program from address 0 becomes
+// straight-line Rust code, with a `match` on the instruction pointer for wherever jumps land.
+// Anything else, including instructions the program has overwritten by the time they run, is
+// left to the interpreter.
+pub fn compile(program: &[i64]) -> String {
+    let graph = ControlFlowGraph::new(program);
+    let instructions: Vec<&Line> = graph
+        .blocks
+        .values()
+        .flat_map(|block| &block.instructions)
+        .collect();
+    let indexes: BTreeMap<usize, usize> = instructions
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (line.address(), i))
+        .collect();
+
+    let mut source = String::new();
+    let out = &mut source;
+
+    writeln!(
+        out,
+        "// Compiled from a {}-value Intcode program by `intcode compile`. Don't edit it by hand;",
+        program.len()
+    )
+    .unwrap();
+    out.push_str(HEADER);
+
+    out.push_str("const PROGRAM: &[i64] = &[\n");
+    write_list(out, program.iter().map(i64::to_string));
+    out.push_str("];\n\n");
+
+    out.push_str("// Where each compiled instruction starts and how many values it takes up\n");
+    out.push_str("const INSTRUCTIONS: &[(usize, usize)] = &[\n");
+    write_list(
+        out,
+        instructions
+            .iter()
+            .map(|line| format!("({}, {})", line.address(), line.size())),
+    );
+    out.push_str("];\n");
+
+    out.push_str(RUN_START);
+    let body_start = out.len();
+    for (&start, block) in &graph.blocks {
+        writeln!(out, "                {} => {{", start).unwrap();
+
+        for line in &block.instructions {
+            writeln!(
+                out,
+                "                    // {}",
+                line.to_string().trim_start()
+            )
+            .unwrap();
+            if line.address() != start {
+                writeln!(
+                    out,
+                    "                    computer.current_position = {};",
+                    line.address()
+                )
+                .unwrap();
+            }
+            writeln!(
+                out,
+                "                    if !code.valid[{}] {{\n                        break 'compiled;\n                    }}",
+                indexes[&line.address()]
+            )
+            .unwrap();
+            write_instruction(out, line);
+        }
+
+        if block.instructions.last().is_some_and(falls_through) {
+            writeln!(
+                out,
+                "                    computer.current_position = {};\n                    continue 'dispatch;",
+                block.end()
+            )
+            .unwrap();
+        }
+
+        out.push_str("                }\n");
+    }
+
+    // Only bring in the helpers something uses, so there's nothing dead
+    let uses_relative = out[body_start..].contains("relative(");
+    let uses_address = uses_relative || out[body_start..].contains("address(");
+    out.push_str(RUN_END);
+    if uses_relative {
+        out.push_str(RELATIVE);
+    }
+    if uses_address {
+        out.push_str(ADDRESS);
+    }
+
+    source
+}
+
+const HEADER: &str = "// compile the program again instead.
+//
+// Each instruction that was found in the program is Rust code here. Any that weren't, or that the
+// program has written over since, are run by the interpreter. So is any add or multiply that
+// overflows, and everything once memory has a value too big for an i64, so the computer's
+// arithmetic decides what happens. Nothing is recorded in the computer's history. A computer that
+// isn't `Computer::is_standard`, with opcodes of its own, an earlier spec level or limits, is run
+// by the interpreter the whole way.
+
+use intcode::{Computer, IntcodeError, Io, State};
+
+";
+
+const RUN_START: &str = "
+pub fn run(computer: &mut Computer, io: &mut impl Io) -> Result<(), IntcodeError> {
+    if run_until_input(computer, io)? == State::NeedsInput {
+        return Err(IntcodeError::MissingInput {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+        });
+    }
+    Ok(())
+}
+
+pub fn run_until_input(computer: &mut Computer, io: &mut impl Io) -> Result<State, IntcodeError> {
+    if !computer.is_standard() {
+        return computer.run_until_input(io);
+    }
+    let mut code = Code::new(computer);
+
+    'dispatch: loop {
+        'compiled: {
+            if computer.has_big_values() {
+                break 'compiled;
+            }
+            match computer.current_position {
+";
+
+const RUN_END: &str = "                _ => break 'compiled,
+            }
+        }
+
+        let state = computer.step(io)?;
+        if let Some(address) = computer.last_write() {
+            code.written(computer, address);
+        }
+        match state {
+            State::Running | State::Output(_) => {}
+            other => return Ok(other),
+        }
+    }
+}
+
+// Which compiled instructions are still what's in memory
+struct Code {
+    owners: Vec<Option<usize>>,
+    valid: Vec<bool>,
+}
+
+impl Code {
+    fn new(computer: &Computer) -> Code {
+        let mut owners = vec![None; PROGRAM.len()];
+        for (i, &(start, length)) in INSTRUCTIONS.iter().enumerate() {
+            for owner in &mut owners[start..start + length] {
+                *owner = Some(i);
+            }
+        }
+
+        let valid = INSTRUCTIONS
+            .iter()
+            .map(|&instruction| unchanged(computer, instruction))
+            .collect();
+
+        Code { owners, valid }
+    }
+
+    fn written(&mut self, computer: &Computer, address: usize) {
+        if let Some(&Some(i)) = self.owners.get(address) {
+            self.valid[i] = unchanged(computer, INSTRUCTIONS[i]);
+        }
+    }
+}
+
+fn unchanged(computer: &Computer, (start, length): (usize, usize)) -> bool {
+    (start..start + length).all(|address| computer.read_at(address) == PROGRAM[address])
+}
+
+fn write(computer: &mut Computer, code: &mut Code, address: usize, value: i64) {
+    computer.write_at(address, value);
+    code.written(computer, address);
+}
+";
+
+const RELATIVE: &str = "
+fn relative(computer: &Computer, parameter: usize, offset: i64) -> Result<usize, IntcodeError> {
+    match i64::checked_add(computer.relative_base as i64, offset) {
+        Some(value) => address(computer, parameter, value),
+        None => Err(IntcodeError::AddressOverflow {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+            parameter,
+        }),
+    }
+}
+";
+
+const ADDRESS: &str = "
+fn address(computer: &Computer, parameter: usize, address: i64) -> Result<usize, IntcodeError> {
+    if address < 0 {
+        return Err(IntcodeError::NegativeAddress {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+            parameter,
+            address,
+        });
+    }
+    Ok(address as usize)
+}
+";
+
+fn write_instruction(out: &mut String, line: &Line) {
+    let (mnemonic, operands) = match line {
+        Line::Instruction {
+            mnemonic, operands, ..
+        } => (*mnemonic, operands),
+        Line::Data { .. } => return,
+    };
+    let indent = "                    ";
+    let read = |parameter: usize| match operands[parameter] {
+        Operand::Position(address) => format!("computer.read_at({})", address),
+        Operand::Immediate(value) => value.to_string(),
+        Operand::Relative(offset) => format!(
+            "computer.read_at(relative(computer, {}, {})?)",
+            parameter, offset
+        ),
+    };
+    let destination = |parameter: usize| match operands[parameter] {
+        Operand::Relative(offset) => format!("relative(computer, {}, {})?", parameter, offset),
+        // The disassembler never decodes a write in immediate mode
+        Operand::Position(address) | Operand::Immediate(address) => address.to_string(),
+    };
+    let target = || match operands[1] {
+        Operand::Immediate(target) if target >= 0 => target.to_string(),
+        _ => format!("address(computer, 1, {})?", read(1)),
+    };
+
+    let mut emit = |code: String| {
+        for code_line in code.lines() {
+            writeln!(out, "{}{}", indent, code_line).unwrap();
+        }
+    };
+
+    match mnemonic {
+        "add" | "mul" | "lt" | "eq" => {
+            emit(format!("let a = {};", read(0)));
+            emit(format!("let b = {};", read(1)));
+            emit(format!("let destination = {};", destination(2)));
+            let value = match mnemonic {
+                // The interpreter deals with overflow however the computer's arithmetic says to
+                "add" | "mul" => {
+                    emit(format!(
+                        "let value = match i64::checked_{}(a, b) {{\n    Some(value) => value,\n    None => break 'compiled,\n}};",
+                        mnemonic
+                    ));
+                    "value"
+                }
+                "lt" => "i64::from(a < b)",
+                _ => "i64::from(a == b)",
+            };
+            emit(format!(
+                "write(computer, &mut code, destination, {});",
+                value
+            ));
+        }
+        "in" => {
+            emit(String::from(
+                "let value = match io.input() {\n    Some(value) => value,\n    None => return Ok(State::NeedsInput),\n};",
+            ));
+            emit(format!("let destination = {};", destination(0)));
+            emit(String::from(
+                "write(computer, &mut code, destination, value);",
+            ));
+        }
+        "out" => emit(format!("io.output({});", read(0))),
+        "jt" | "jf" => {
+            if jump_target(line).is_none() {
+                return;
+            }
+            let jump = format!(
+                "computer.current_position = {};\ncontinue 'dispatch;",
+                target()
+            );
+            if falls_through(line) {
+                let comparison = if mnemonic == "jt" { "!=" } else { "==" };
+                emit(format!("if {} {} 0 {{", read(0), comparison));
+                for jump_line in jump.lines() {
+                    emit(format!("    {}", jump_line));
+                }
+                emit(String::from("}"));
+            } else {
+                emit(jump);
+            }
+        }
+        // Like overflow in an add, the interpreter reports it
+        "arb" => emit(format!(
+            "computer.relative_base = match i64::checked_add(computer.relative_base as i64, {}) {{\n    Some(base) => base as usize,\n    None => break 'compiled,\n}};",
+            read(0)
+        )),
+        _ => emit(String::from("return Ok(State::Halted);")),
+    }
+}
+
+// Writes values separated by commas, wrapped the way rustfmt would.
+fn write_list(out: &mut String, values: impl Iterator<Item = String>) {
+    let mut line = String::from("   ");
+
+    for value in values {
+        if line.len() + value.len() + 2 > 100 {
+            out.push_str(&line);
+            out.push('\n');
+            line = String::from("   ");
+        }
+        line.push(' ');
+        line.push_str(&value);
+        line.push(',');
+    }
+
+    if line.trim() != "" {
+        out.push_str(&line);
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_straight_line_code() {
+        let source = compile(&[1101, 2, 3, 7, 204, -1, 99]);
+
+        assert!(source.contains(
+            "                0 => {
+                    // 0: add #2, #3, [7]
+                    if !code.valid[0] {
+                        break 'compiled;
+                    }
+                    let a = 2;
+                    let b = 3;
+                    let destination = 7;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 4: out rb-1
+                    computer.current_position = 4;
+                    if !code.valid[1] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(relative(computer, 0, -1)?));
+                    // 6: hlt
+                    computer.current_position = 6;
+                    if !code.valid[2] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                _ => break 'compiled,"
+        ));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::network::{Link, Network, Topology};
+use crate::{parse_program, Computer};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+// A network described in a TOML or JSON file, like:
+//
+//     topology = "ring"
+//
+//     [[node]]
+//     name = "a"
+//     program = "input"
+//     input = [9, 0]
+//
+//     [[node]]
+//     name = "b"
+//     program = [3, 9, 1002, 9, 2, 9, 4, 9, 99, 0]
+//     input = [8]
+//
+// A node's program is either a file, relative to the config file, or the program itself. Leave
+// out `topology` to wire the nodes up by hand instead, by listing the names of the nodes each one
+// sends its output `to`; "output" means the network's output.
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkConfig {
+    pub topology: Option<Topology>,
+    #[serde(rename = "node", alias = "nodes")]
+    pub nodes: Vec<NodeConfig>,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NodeConfig {
+    pub name: String,
+    pub program: ProgramSource,
+    #[serde(default)]
+    pub input: Vec<i64>,
+    #[serde(default)]
+    pub to: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ProgramSource {
+    File(String),
+    Code(Vec<i64>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConfigError {
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ConfigError {}
+
+fn error(message: String) -> ConfigError {
+    ConfigError { message }
+}
+
+impl NetworkConfig {
+    pub fn from_toml(text: &str) -> Result<NetworkConfig, ConfigError> {
+        toml::from_str(text).map_err(|e| error(format!("invalid network config: {}", e)))
+    }
+
+    pub fn from_json(text: &str) -> Result<NetworkConfig, ConfigError> {
+        serde_json::from_str(text).map_err(|e| error(format!("invalid network config: {}", e)))
+    }
+
+    // Reads a `.json` file as JSON and anything else as TOML.
+    pub fn load(path: &Path) -> Result<NetworkConfig, ConfigError> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| error(format!("couldn't read {}: {}", path.display(), e)))?;
+
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            NetworkConfig::from_json(&text)
+        } else {
+            NetworkConfig::from_toml(&text)
+        }
+    }
+
+    // Sets up the network, with each node at the address of its place in `nodes`. Program files
+    // are found relative to `directory`.
+    pub fn build(&self, directory: &Path) -> Result<Network, ConfigError> {
+        let mut addresses = HashMap::new();
+        for (address, node) in self.nodes.iter().enumerate() {
+            if addresses.insert(node.name.as_str(), address).is_some() {
+                return Err(error(format!(
+                    "there's more than one node named `{}`",
+                    node.name
+                )));
+            }
+        }
+
+        let topology = self.topology.unwrap_or(Topology::Links);
+        let mut network = Network::new(topology);
+
+        for node in &self.nodes {
+            let program = match &node.program {
+                ProgramSource::Code(program) => program.clone(),
+                ProgramSource::File(file) => {
+                    let path = directory.join(file);
+                    let text = fs::read_to_string(&path)
+                        .map_err(|e| error(format!("couldn't read {}: {}", path.display(), e)))?;
+                    parse_program(&text).map_err(|e| {
+                        error(format!(
+                            "{} isn't an Intcode program: {}",
+                            path.display(),
+                            e
+                        ))
+                    })?
+                }
+            };
+            network.add(Computer::new(program), node.input.iter().copied());
+        }
+
+        for (from, node) in self.nodes.iter().enumerate() {
+            if !node.to.is_empty() && topology != Topology::Links {
+                return Err(error(format!(
+                    "node `{}` says where its output goes, but the topology already does",
+                    node.name
+                )));
+            }
+
+            for to in &node.to {
+                let link = match addresses.get(to.as_str()) {
+                    Some(&address) => Link::Machine(address),
+                    None if to == "output" => Link::Output,
+                    None => {
+                        return Err(error(format!(
+                            "node `{}` sends to `{}`, but there's no node with that name",
+                            node.name, to
+                        )))
+                    }
+                };
+                network.connect(from, link);
+            }
+        }
+
+        Ok(network)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Outcome;
+
+    const AMPLIFIER: &str = "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,\
+                             28,1005,28,6,99,0,0,5";
+
+    #[test]
+    fn run_day_7_amplifiers_from_toml() {
+        let directory = std::env::temp_dir().join("intcode-config-test");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("amplifier"), AMPLIFIER).unwrap();
+
+        let mut config = String::from("topology = \"ring\"\n");
+        for (i, phase_setting) in [9, 8, 7, 6, 5].iter().enumerate() {
+            config.push_str(&format!(
+                "[[node]]\nname = \"{}\"\nprogram = \"amplifier\"\ninput = [{}{}]\n",
+                i,
+                phase_setting,
+                if i == 0 { ", 0" } else { "" }
+            ));
+        }
+
+        let config = NetworkConfig::from_toml(&config).unwrap();
+        let mut network = config.build(&directory).unwrap();
+
+        assert_eq!(network.run(), Ok(Outcome::Halted));
+        assert_eq!(network.last_output(4), Some(139629729));
+    }
+
+    #[test]
+    fn wire_nodes_up_by_hand_in_json() {
+        let config = NetworkConfig::from_json(
+            r#"{
+                "nodes": [
+                    { "name": "five", "program": [104, 5, 99], "to": ["double", "output"] },
+                    { "name": "double", "program": [3, 9, 1002, 9, 2, 9, 4, 9, 99, 0], "to": ["output"] }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let mut network = config.build(Path::new(".")).unwrap();
+
+        assert_eq!(network.run(), Ok(Outcome::Halted));
+        assert_eq!(network.output, vec![5, 10]);
+    }
+
+    #[test]
+    fn config_errors() {
+        let build = |toml: &str| {
+            NetworkConfig::from_toml(toml)
+                .and_then(|config| config.build(Path::new(".")))
+                .err()
+                .map(|e| e.to_string())
+        };
+
+        assert_eq!(
+            build("[[node]]\nname = \"a\"\nprogram = [99]\nto = [\"b\"]"),
+            Some(String::from(
+                "node `a` sends to `b`, but there's no node with that name"
+            ))
+        );
+        assert_eq!(
+            build("[[node]]\nname = \"a\"\nprogram = [99]\n[[node]]\nname = \"a\"\nprogram = [99]"),
+            Some(String::from("there's more than one node named `a`"))
+        );
+        assert_eq!(
+            build("topology = \"chain\"\n[[node]]\nname = \"a\"\nprogram = [99]\nto = [\"a\"]"),
+            Some(String::from(
+                "node `a` says where its output goes, but the topology already does"
+            ))
+        );
+        assert!(
+            build("topology = \"star\"\n[[node]]\nname = \"a\"\nprogram = [99]")
+                .unwrap()
+                .starts_with("invalid network config")
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use crate::disassemble::line_from;
+use crate::{Computer, State};
+use std::collections::{BTreeSet, VecDeque};
+
+const HELP: &str = "\
+step [count]          run one instruction, or `count` of them (s)
+back [count]          undo one instruction, or `count` of them (bs)
+continue              run until a breakpoint, a watchpoint, input is needed or the program halts (c)
+break [address]       stop before running the instruction at `address`, or list breakpoints (b)
+watch [address]       stop after anything writes to `address`, or list watchpoints (w)
+who <address>         show which instruction last wrote to `address`
+delete <address>      remove the breakpoint and watchpoint at `address` (d)
+input <values...>     queue values for the program to read (i)
+registers             show the instruction pointer and relative base (r)
+memory <start> [len]  show `len` values of memory starting at `start` (x)
+list [count]          disassemble the next `count` instructions (l)
+quit                  stop debugging (q)";
+
+// How many instructions `back` can undo, unless `set_history_depth` says otherwise
+const HISTORY_DEPTH: usize = 1_000_000;
+
+// Runs a `Computer` one command at a time, like a REPL. Each command returns what it has to say,
+// including anything the program output along the way.
+pub struct Debugger {
+    pub computer: Computer,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+    input: VecDeque<i64>,
+}
+
+impl Debugger {
+    pub fn new(program: Vec<i64>) -> Debugger {
+        let mut computer = Computer::new(program);
+        computer.record_history_up_to(HISTORY_DEPTH);
+
+        Debugger {
+            computer,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            input: VecDeque::new(),
+        }
+    }
+
+    pub fn set_history_depth(&mut self, depth: usize) {
+        self.computer.record_history_up_to(depth);
+    }
+
+    pub fn execute(&mut self, command: &str) -> Result<String, String> {
+        let mut words = command.split_whitespace();
+        let name = words.next().unwrap_or("");
+        let arguments = words.map(parse_number).collect::<Result<Vec<_>, _>>()?;
+        let mut report = vec![];
+
+        match (name, arguments.as_slice()) {
+            ("step", []) | ("s", []) => self.step_times(1, &mut report),
+            ("step", [count]) | ("s", [count]) => self.step_times(to_usize(*count)?, &mut report),
+            ("back", []) | ("bs", []) => self.step_back_times(1, &mut report),
+            ("back", [count]) | ("bs", [count]) => {
+                self.step_back_times(to_usize(*count)?, &mut report)
+            }
+            ("continue", []) | ("c", []) => self.continue_running(&mut report),
+            ("break", []) | ("b", []) => report.push(list("breakpoints", &self.breakpoints)),
+            ("break", [address]) | ("b", [address]) => {
+                let address = to_usize(*address)?;
+                self.breakpoints.insert(address);
+                report.push(format!("breakpoint at {}", address));
+            }
+            ("watch", []) | ("w", []) => report.push(list("watchpoints", &self.watchpoints)),
+            ("watch", [address]) | ("w", [address]) => {
+                let address = to_usize(*address)?;
+                self.watchpoints.insert(address);
+                report.push(format!("watching {}", address));
+            }
+            ("who", [address]) => {
+                let address = to_usize(*address)?;
+                report.push(match self.computer.last_writer(address) {
+                    Some(position) => format!(
+                        "{} was last written by the instruction at {}",
+                        address, position
+                    ),
+                    None => format!("nothing has written to {}", address),
+                });
+            }
+            ("delete", [address]) | ("d", [address]) => {
+                let address = to_usize(*address)?;
+                self.breakpoints.remove(&address);
+                self.watchpoints.remove(&address);
+                report.push(format!("nothing stops at {} any more", address));
+            }
+            ("input", values) | ("i", values) if !values.is_empty() => {
+                self.input.extend(values);
+                report.push(format!("{} values waiting to be read", self.input.len()));
+            }
+            ("registers", []) | ("r", []) => report.push(format!(
+                "ip = {}, rb = {}",
+                self.computer.current_position, self.computer.relative_base
+            )),
+            ("memory", [start]) | ("x", [start]) => self.dump(to_usize(*start)?, 8, &mut report),
+            ("memory", [start, length]) | ("x", [start, length]) => {
+                self.dump(to_usize(*start)?, to_usize(*length)?, &mut report)
+            }
+            ("list", []) | ("l", []) => self.list_instructions(5, &mut report),
+            ("list", [count]) | ("l", [count]) => {
+                self.list_instructions(to_usize(*count)?, &mut report)
+            }
+            ("help", []) | ("h", []) => report.push(String::from(HELP)),
+            _ => return Err(format!("unknown command `{}`; try `help`", command.trim())),
+        }
+
+        Ok(report.join("\n"))
+    }
+
+    // The instruction the computer will run next, as the disassembler shows it.
+    pub fn current_line(&self) -> String {
+        self.lines_from(self.computer.current_position, 1).remove(0)
+    }
+
+    fn step_times(&mut self, count: usize, report: &mut Vec<String>) {
+        for _ in 0..count {
+            if let Some(reason) = self.step(report) {
+                report.push(reason);
+                break;
+            }
+        }
+        report.push(self.current_line());
+    }
+
+    fn step_back_times(&mut self, count: usize, report: &mut Vec<String>) {
+        for _ in 0..count {
+            match self.computer.step_back() {
+                Some(change) => {
+                    if let Some(input) = change.input {
+                        self.input.push_front(input);
+                    }
+                }
+                None => {
+                    report.push(String::from("at the start of the history"));
+                    break;
+                }
+            }
+        }
+        report.push(self.current_line());
+    }
+
+    fn continue_running(&mut self, report: &mut Vec<String>) {
+        loop {
+            if let Some(reason) = self.step(report) {
+                report.push(reason);
+                break;
+            }
+            let position = self.computer.current_position;
+            if self.breakpoints.contains(&position) {
+                report.push(format!("breakpoint at {}", position));
+                break;
+            }
+        }
+        report.push(self.current_line());
+    }
+
+    // Runs one instruction, and says why the debugger should stop if it should.
+    fn step(&mut self, report: &mut Vec<String>) -> Option<String> {
+        let position = self.computer.current_position;
+        let mut output = vec![];
+        let result = self.computer.step(&mut (&mut self.input, &mut output));
+
+        for value in output {
+            report.push(format!("output: {}", value));
+        }
+
+        match result {
+            Ok(State::NeedsInput) => Some(String::from("waiting for input")),
+            Ok(State::Halted) => Some(String::from("halted")),
+            Err(e) => Some(format!("error: {}", e)),
+            Ok(State::Running) | Ok(State::Output(_)) => {
+                let address = self.computer.last_write()?;
+                if self.watchpoints.contains(&address) {
+                    Some(format!(
+                        "watchpoint: {} = {}, written by the instruction at {}",
+                        address,
+                        self.computer.read_at(address),
+                        position
+                    ))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn dump(&self, start: usize, length: usize, report: &mut Vec<String>) {
+        let end = start.saturating_add(length);
+        for row_start in (start..end).step_by(8) {
+            let row_end = row_start.saturating_add(8).min(end);
+            let values: Vec<String> = (row_start..row_end)
+                .map(|address| self.computer.read_at(address).to_string())
+                .collect();
+            report.push(format!("{:>5}: {}", row_start, values.join(" ")));
+        }
+    }
+
+    fn list_instructions(&self, count: usize, report: &mut Vec<String>) {
+        report.extend(self.lines_from(self.computer.current_position, count));
+    }
+
+    fn lines_from(&self, mut address: usize, count: usize) -> Vec<String> {
+        let mut lines = vec![];
+        // An instruction is no longer than the opcode that takes the most parameters
+        let opcodes = self.computer.opcodes();
+        let longest = (0..100)
+            .filter_map(|opcode| opcodes.get(opcode))
+            .map(|opcode| opcode.parameters + 1)
+            .max()
+            .unwrap_or(1);
+
+        for _ in 0..count.max(1) {
+            let values: Vec<i64> = (address..address.saturating_add(longest))
+                .map(|a| self.computer.read_at(a))
+                .collect();
+            let line = line_from(&values, address, opcodes);
+            lines.push(line.to_string());
+            address = match address.checked_add(line.size()) {
+                Some(next) => next,
+                None => break,
+            };
+        }
+
+        lines
+    }
+}
+
+fn parse_number(word: &str) -> Result<i64, String> {
+    word.parse()
+        .map_err(|_| format!("`{}` isn't a number", word))
+}
+
+fn to_usize(number: i64) -> Result<usize, String> {
+    if number < 0 {
+        Err(format!("{} can't be negative", number))
+    } else {
+        Ok(number as usize)
+    }
+}
+
+fn list(name: &str, addresses: &BTreeSet<usize>) -> String {
+    if addresses.is_empty() {
+        return format!("no {}", name);
+    }
+    let addresses: Vec<String> = addresses.iter().map(usize::to_string).collect();
+    format!("{}: {}", name, addresses.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts down from the input to 1
+    fn countdown() -> Debugger {
+        Debugger::new(vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, -1])
+    }
+
+    #[test]
+    fn step_through_a_program() {
+        let mut debugger = countdown();
+
+        assert_eq!(debugger.current_line(), "    0: in [12]");
+        assert_eq!(
+            debugger.execute("step"),
+            Ok(String::from("waiting for input\n    0: in [12]"))
+        );
+        assert_eq!(
+            debugger.execute("input 2"),
+            Ok(String::from("1 values waiting to be read"))
+        );
+        assert_eq!(
+            debugger.execute("s 2"),
+            Ok(String::from("output: 2\n    4: add [12], #-1, [12]"))
+        );
+        assert_eq!(debugger.execute("r"), Ok(String::from("ip = 4, rb = 0")));
+        assert_eq!(
+            debugger.execute("c"),
+            Ok(String::from("output: 1\nhalted\n   11: hlt"))
+        );
+    }
+
+    #[test]
+    fn stop_at_breakpoints_and_watchpoints() {
+        let mut debugger = countdown();
+        debugger.execute("input 3").unwrap();
+
+        assert_eq!(
+            debugger.execute("break 8"),
+            Ok(String::from("breakpoint at 8"))
+        );
+        assert_eq!(
+            debugger.execute("continue"),
+            Ok(String::from(
+                "output: 3\nbreakpoint at 8\n    8: jt [12], #2"
+            ))
+        );
+
+        debugger.execute("delete 8").unwrap();
+        debugger.execute("watch 12").unwrap();
+        assert_eq!(debugger.execute("w"), Ok(String::from("watchpoints: 12")));
+        assert_eq!(
+            debugger.execute("continue"),
+            Ok(String::from(
+                "output: 2\nwatchpoint: 12 = 1, written by the instruction at 4\n    8: jt [12], #2"
+            ))
+        );
+        assert_eq!(debugger.execute("b"), Ok(String::from("no breakpoints")));
+    }
+
+    #[test]
+    fn step_backwards() {
+        let mut debugger = countdown();
+        debugger.execute("input 2").unwrap();
+        debugger.execute("continue").unwrap();
+
+        assert_eq!(
+            debugger.execute("who 12"),
+            Ok(String::from("12 was last written by the instruction at 4"))
+        );
+        assert_eq!(
+            debugger.execute("back 2"),
+            Ok(String::from("    4: add [12], #-1, [12]"))
+        );
+        assert_eq!(debugger.execute("x 12 1"), Ok(String::from("   12: 1")));
+        assert_eq!(
+            debugger.execute("bs 10"),
+            Ok(String::from("at the start of the history\n    0: in [12]"))
+        );
+        assert_eq!(
+            debugger.execute("who 12"),
+            Ok(String::from("nothing has written to 12"))
+        );
+
+        // The input the program read on the way gets read again
+        assert_eq!(
+            debugger.execute("s 2"),
+            Ok(String::from("output: 2\n    4: add [12], #-1, [12]"))
+        );
+    }
+
+    #[test]
+    fn step_back_only_as_far_as_the_history_goes() {
+        let mut debugger = countdown();
+        debugger.set_history_depth(3);
+        debugger.execute("input 2").unwrap();
+        debugger.execute("continue").unwrap();
+
+        assert_eq!(
+            debugger.execute("bs 10"),
+            Ok(String::from(
+                "at the start of the history
+    2: out [12]"
+            ))
+        );
+        assert_eq!(debugger.execute("x 12 1"), Ok(String::from("   12: 1")));
+    }
+
+    #[test]
+    fn inspect_memory() {
+        let mut debugger = countdown();
+
+        assert_eq!(
+            debugger.execute("memory 0 10"),
+            Ok(String::from(
+                "    0: 3 12 4 12 1001 12 -1 12\n    8: 1005 12"
+            ))
+        );
+        assert_eq!(
+            debugger.execute("x 11"),
+            Ok(String::from("   11: 99 -1 0 0 0 0 0 0"))
+        );
+        assert_eq!(
+            debugger.execute("list 3"),
+            Ok(String::from(
+                "    0: in [12]\n    2: out [12]\n    4: add [12], #-1, [12]"
+            ))
+        );
+    }
+
+    #[test]
+    fn inspect_far_away_memory() {
+        // Jumps somewhere enormous, where nothing has been written
+        let mut debugger = Debugger::new(vec![1105, 1, i64::MAX]);
+        debugger.execute("step").unwrap();
+
+        let far = i64::MAX as usize;
+        assert_eq!(debugger.current_line(), format!("{:>5}: .data 0", far));
+        assert_eq!(
+            debugger.execute("memory 9223372036854775805 5"),
+            Ok(String::from("9223372036854775805: 0 0 0 0 0"))
+        );
+
+        debugger.computer.current_position = usize::MAX - 1;
+        debugger.computer.write_at(usize::MAX - 1, 99);
+        assert_eq!(
+            debugger.execute("list 3"),
+            Ok(format!("{}: hlt\n{}: .data 0", usize::MAX - 1, usize::MAX))
+        );
+    }
+
+    #[test]
+    fn bad_commands_are_errors() {
+        let mut debugger = countdown();
+
+        assert_eq!(
+            debugger.execute("jump 4"),
+            Err(String::from("unknown command `jump 4`; try `help`"))
+        );
+        assert_eq!(
+            debugger.execute("break here"),
+            Err(String::from("`here` isn't a number"))
+        );
+        assert_eq!(
+            debugger.execute("watch -1"),
+            Err(String::from("-1 can't be negative"))
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{instruction, opcodes, Mode, Opcodes};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operand {
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+}
+
+impl Operand {
+    fn new(mode: Mode, value: i64) -> Operand {
+        match mode {
+            Mode::Position => Operand::Position(value),
+            Mode::Immediate => Operand::Immediate(value),
+            Mode::Relative => Operand::Relative(value),
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Operand::Position(address) => write!(f, "[{}]", address),
+            Operand::Immediate(value) => write!(f, "#{}", value),
+            Operand::Relative(offset) if offset < 0 => write!(f, "rb{}", offset),
+            Operand::Relative(offset) => write!(f, "rb+{}", offset),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Line {
+    Instruction {
+        address: usize,
+        mnemonic: &'static str,
+        operands: Vec<Operand>,
+    },
+    Data {
+        address: usize,
+        value: i64,
+    },
+}
+
+impl Line {
+    pub fn address(&self) -> usize {
+        match *self {
+            Line::Instruction { address, .. } | Line::Data { address, .. } => address,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            Line::Instruction { operands, .. } => operands.len() + 1,
+            Line::Data { .. } => 1,
+        }
+    }
+
+    // Like `to_string`, but a jump to an address in `labels` goes to the label instead.
+    pub fn with_labels(&self, labels: &BTreeMap<usize, String>) -> String {
+        let mut text = self.to_string();
+
+        if let Line::Instruction {
+            mnemonic: "jt" | "jf",
+            operands,
+            ..
+        } = self
+        {
+            if let [_, Operand::Immediate(target)] = operands[..] {
+                if let Some(label) = labels.get(&(target as usize)).filter(|_| target >= 0) {
+                    text.truncate(text.len() - target.to_string().len());
+                    text.push_str(label);
+                }
+            }
+        }
+
+        text
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>5}: ", self.address())?;
+
+        match self {
+            Line::Instruction {
+                mnemonic, operands, ..
+            } => {
+                write!(f, "{}", mnemonic)?;
+                for (i, operand) in operands.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}", separator, operand)?;
+                }
+                Ok(())
+            }
+            Line::Data { value, .. } => write!(f, ".data {}", value),
+        }
+    }
+}
+
+// The mnemonic for each standard opcode, how many parameters it takes, and which of those it
+// writes to.
+pub fn mnemonic(opcode: i64) -> Option<(&'static str, usize, Option<usize>)> {
+    let opcode = opcodes::standard().get(opcode)?;
+    Some((opcode.mnemonic, opcode.parameters, opcode.written))
+}
+
+// Decodes the program from the start, one instruction after another. Anything that can't be an
+// instruction the `Computer` would run without an error, like an unknown opcode, a write in
+// immediate mode or a negative address, becomes a `.data` word instead.
+pub fn disassemble(program: &[i64]) -> Vec<Line> {
+    disassemble_with(program, opcodes::standard())
+}
+
+// Like `disassemble`, for a program that runs with `opcodes` instead of the standard ones.
+pub fn disassemble_with(program: &[i64], opcodes: &Opcodes) -> Vec<Line> {
+    let mut lines = vec![];
+    let mut address = 0;
+
+    while address < program.len() {
+        let line = line_from(program.get(address..).unwrap_or(&[]), address, opcodes);
+        address += line.size();
+        lines.push(line);
+    }
+
+    lines
+}
+
+// The line the instruction, or data, at `address` disassembles to.
+pub fn line_at(program: &[i64], address: usize) -> Line {
+    line_from(
+        program.get(address..).unwrap_or(&[]),
+        address,
+        opcodes::standard(),
+    )
+}
+
+// Like `line_at`, for `values` that start at `address` instead of a whole program, so only the
+// few values an instruction can take up need to be read, and decoded with `opcodes`.
+pub fn line_from(values: &[i64], address: usize, opcodes: &Opcodes) -> Line {
+    decode(values, address, opcodes).unwrap_or(Line::Data {
+        address,
+        value: values.first().copied().unwrap_or(0),
+    })
+}
+
+pub(crate) fn decode_at(program: &[i64], address: usize) -> Option<Line> {
+    decode(program.get(address..)?, address, opcodes::standard())
+}
+
+fn decode(values: &[i64], address: usize, opcodes: &Opcodes) -> Option<Line> {
+    let inst = instruction(*values.first()?).ok()?;
+    let opcode = opcodes.get(inst.opcode)?;
+    let (mnemonic, parameter_count, written_parameter) =
+        (opcode.mnemonic, opcode.parameters, opcode.written);
+
+    if inst.mode_count() > parameter_count {
+        return None;
+    }
+    if written_parameter.map(|i| inst.mode(i)) == Some(Mode::Immediate) {
+        return None;
+    }
+
+    let parameters = values.get(1..1 + parameter_count)?;
+    let negative_position = parameters
+        .iter()
+        .enumerate()
+        .any(|(i, &value)| inst.mode(i) == Mode::Position && value < 0);
+    if negative_position {
+        return None;
+    }
+
+    let operands = parameters
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| Operand::new(inst.mode(i), value))
+        .collect();
+
+    Some(Line::Instruction {
+        address,
+        mnemonic,
+        operands,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Opcode, State};
+
+    fn listing(program: &[i64]) -> Vec<String> {
+        disassemble(program)
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn disassemble_every_opcode() {
+        let program = vec![
+            1, 9, 10, 11, 1002, 4, 3, 4, 3, 0, 104, 7, 1105, 1, 0, 106, 0, 0, 1107, 1, 2, 3, 8, 1,
+            2, 3, 109, -2, 99,
+        ];
+
+        assert_eq!(
+            listing(&program),
+            vec![
+                "    0: add [9], [10], [11]",
+                "    4: mul [4], #3, [4]",
+                "    8: in [0]",
+                "   10: out #7",
+                "   12: jt #1, #0",
+                "   15: jf #0, [0]",
+                "   18: lt #1, #2, [3]",
+                "   22: eq [1], [2], [3]",
+                "   26: arb #-2",
+                "   28: hlt",
+            ]
+        );
+    }
+
+    #[test]
+    fn disassemble_relative_operands() {
+        let program = vec![204, -1, 21101, 3, 4, 5];
+
+        assert_eq!(
+            listing(&program),
+            vec!["    0: out rb-1", "    2: add #3, #4, rb+5"]
+        );
+    }
+
+    #[test]
+    fn values_that_cant_be_instructions_are_data() {
+        // Unknown opcode, an immediate mode write, adds that would read from a negative address,
+        // a bad mode, a negative value, and an instruction that runs off the end of the program
+        let program = vec![42, 11101, 1, 1, 301, -4, 1, 0];
+
+        assert_eq!(
+            listing(&program),
+            vec![
+                "    0: .data 42",
+                "    1: .data 11101",
+                "    2: .data 1",
+                "    3: .data 1",
+                "    4: .data 301",
+                "    5: .data -4",
+                "    6: .data 1",
+                "    7: .data 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn disassemble_added_opcodes() {
+        let mut opcodes = Opcodes::standard();
+        opcodes.insert(20, Opcode::new("swap", 2, Some(1), |_| Ok(State::Running)));
+        let program = vec![120, 5, 6, 20, 99];
+
+        let added: Vec<String> = disassemble_with(&program, &opcodes)
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(
+            added,
+            vec!["    0: swap #5, [6]", "    3: .data 20", "    4: hlt"]
+        );
+        assert_eq!(listing(&program)[0], "    0: .data 120");
+    }
+}
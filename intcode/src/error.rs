@@ -0,0 +1,214 @@
+use crate::spec::{SpecLevel, Violation};
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+// Everything that can go wrong while running a program. Each one says where the instruction
+// pointer was and what the raw value there was, so the program can be fixed.
+#[derive(Debug, PartialEq, Clone)]
+pub enum IntcodeError {
+    UnknownOpcode {
+        position: usize,
+        opcode: i64,
+    },
+    UnexpectedMode {
+        position: usize,
+        opcode: i64,
+        parameter: usize,
+        mode: i64,
+    },
+    NegativeAddress {
+        position: usize,
+        opcode: i64,
+        parameter: usize,
+        address: i64,
+    },
+    WriteInImmediateMode {
+        position: usize,
+        opcode: i64,
+        parameter: usize,
+    },
+    // A relative mode parameter plus the relative base, or a relative base adjustment, doesn't fit
+    // in an i64
+    AddressOverflow {
+        position: usize,
+        opcode: i64,
+        parameter: usize,
+    },
+    MissingInput {
+        position: usize,
+        opcode: i64,
+    },
+    // The run went past one of the computer's `Limits`
+    StepLimit {
+        position: usize,
+        opcode: i64,
+        steps: u64,
+    },
+    MemoryLimit {
+        position: usize,
+        opcode: i64,
+        parameter: usize,
+        address: i64,
+        limit: usize,
+    },
+    Timeout {
+        position: usize,
+        opcode: i64,
+        timeout: Duration,
+    },
+    // An add or multiply didn't fit in an i64, with `Arithmetic::Checked`
+    Overflow {
+        position: usize,
+        opcode: i64,
+    },
+    // A value only `Arithmetic::BigInt` can hold was needed as an i64, like for an address
+    TooBig {
+        position: usize,
+        opcode: i64,
+        parameter: usize,
+    },
+    // The program did something the puzzle for the computer's `SpecLevel` didn't allow
+    SpecViolation {
+        position: usize,
+        opcode: i64,
+        parameter: usize,
+        level: SpecLevel,
+        violation: Violation,
+    },
+}
+
+impl IntcodeError {
+    pub fn position(&self) -> usize {
+        match *self {
+            IntcodeError::UnknownOpcode { position, .. }
+            | IntcodeError::UnexpectedMode { position, .. }
+            | IntcodeError::NegativeAddress { position, .. }
+            | IntcodeError::WriteInImmediateMode { position, .. }
+            | IntcodeError::AddressOverflow { position, .. }
+            | IntcodeError::MissingInput { position, .. }
+            | IntcodeError::StepLimit { position, .. }
+            | IntcodeError::MemoryLimit { position, .. }
+            | IntcodeError::Timeout { position, .. }
+            | IntcodeError::Overflow { position, .. }
+            | IntcodeError::TooBig { position, .. }
+            | IntcodeError::SpecViolation { position, .. } => position,
+        }
+    }
+
+    pub fn opcode(&self) -> i64 {
+        match *self {
+            IntcodeError::UnknownOpcode { opcode, .. }
+            | IntcodeError::UnexpectedMode { opcode, .. }
+            | IntcodeError::NegativeAddress { opcode, .. }
+            | IntcodeError::WriteInImmediateMode { opcode, .. }
+            | IntcodeError::AddressOverflow { opcode, .. }
+            | IntcodeError::MissingInput { opcode, .. }
+            | IntcodeError::StepLimit { opcode, .. }
+            | IntcodeError::MemoryLimit { opcode, .. }
+            | IntcodeError::Timeout { opcode, .. }
+            | IntcodeError::Overflow { opcode, .. }
+            | IntcodeError::TooBig { opcode, .. }
+            | IntcodeError::SpecViolation { opcode, .. } => opcode,
+        }
+    }
+
+    pub fn parameter(&self) -> Option<usize> {
+        match *self {
+            IntcodeError::UnexpectedMode { parameter, .. }
+            | IntcodeError::NegativeAddress { parameter, .. }
+            | IntcodeError::WriteInImmediateMode { parameter, .. }
+            | IntcodeError::AddressOverflow { parameter, .. }
+            | IntcodeError::MemoryLimit { parameter, .. }
+            | IntcodeError::TooBig { parameter, .. }
+            | IntcodeError::SpecViolation { parameter, .. } => Some(parameter),
+            IntcodeError::UnknownOpcode { .. }
+            | IntcodeError::MissingInput { .. }
+            | IntcodeError::StepLimit { .. }
+            | IntcodeError::Timeout { .. }
+            | IntcodeError::Overflow { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntcodeError::UnknownOpcode { opcode, .. } => write!(f, "Unknown opcode: {}", opcode)?,
+            IntcodeError::UnexpectedMode {
+                mode, parameter, ..
+            } => write!(
+                f,
+                "Unexpected parameter mode {} for parameter {}",
+                mode, parameter
+            )?,
+            IntcodeError::NegativeAddress {
+                address, parameter, ..
+            } => write!(
+                f,
+                "Cannot access memory at {} for parameter {}",
+                address, parameter
+            )?,
+            IntcodeError::WriteInImmediateMode { parameter, .. } => write!(
+                f,
+                "Can't set values in immediate mode for parameter {}",
+                parameter
+            )?,
+            IntcodeError::AddressOverflow { parameter, .. } => write!(
+                f,
+                "The address for parameter {} doesn't fit in 64 bits",
+                parameter
+            )?,
+            IntcodeError::MissingInput { .. } => write!(f, "Should have had input for opcode 3")?,
+            IntcodeError::StepLimit { steps, .. } => {
+                write!(f, "Still running after {} instructions", steps)?
+            }
+            IntcodeError::MemoryLimit {
+                address,
+                parameter,
+                limit,
+                ..
+            } => write!(
+                f,
+                "Memory at {} for parameter {} is past the limit of {}",
+                address, parameter, limit
+            )?,
+            IntcodeError::Timeout { timeout, .. } => {
+                write!(f, "Still running after {:?}", timeout)?
+            }
+            IntcodeError::Overflow { .. } => write!(f, "The result doesn't fit in 64 bits")?,
+            IntcodeError::TooBig { parameter, .. } => write!(
+                f,
+                "The value for parameter {} is too big to use here",
+                parameter
+            )?,
+            IntcodeError::SpecViolation {
+                parameter,
+                level,
+                violation,
+                ..
+            } => write!(
+                f,
+                "Parameter {} breaks the {:?} spec: {}",
+                parameter, level, violation
+            )?,
+        }
+
+        write!(
+            f,
+            " (instruction {} at position {})",
+            self.opcode(),
+            self.position()
+        )
+    }
+}
+
+impl Error for IntcodeError {}
+
+// What `instruction` found wrong with a value; the `Computer` turns this into an
+// `IntcodeError::UnexpectedMode` once it knows where the value came from.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct UnexpectedMode {
+    pub parameter: usize,
+    pub mode: i64,
+}
@@ -0,0 +1,33 @@
+use crate::Io;
+use num_bigint::BigInt;
+
+// How to undo one instruction: where the computer was before it ran, and what was in the memory
+// it wrote over. Any input the instruction read is kept too, so it can be given back.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Change {
+    pub position: usize,
+    pub relative_base: usize,
+    pub write: Option<(usize, i64)>,
+    pub input: Option<i64>,
+}
+
+// Passes everything through to `io`, remembering the last value it read.
+pub(crate) struct RecordInput<'a, T> {
+    pub io: &'a mut T,
+    pub input: Option<i64>,
+}
+
+impl<T: Io> Io for RecordInput<'_, T> {
+    fn input(&mut self) -> Option<i64> {
+        self.input = self.io.input();
+        self.input
+    }
+
+    fn output(&mut self, value: i64) {
+        self.io.output(value)
+    }
+
+    fn output_big(&mut self, value: &BigInt) -> bool {
+        self.io.output_big(value)
+    }
+}
@@ -0,0 +1,420 @@
+use num_bigint::BigInt;
+use std::collections::VecDeque;
+use std::io::{BufRead, Stdin, Stdout, Write};
+use std::mem;
+use std::sync::mpsc::{Receiver, Sender};
+
+// Where opcode 3 gets its values from. `None` means there's no input available.
+pub trait Input {
+    fn read(&mut self) -> Option<i64>;
+}
+
+// Where opcode 4 sends its values to.
+pub trait Output {
+    fn write(&mut self, value: i64);
+
+    // For a value too big for an i64, which only `Arithmetic::BigInt` makes. Returns `false` if
+    // there's nowhere for it to go.
+    fn write_big(&mut self, _value: &BigInt) -> bool {
+        false
+    }
+}
+
+// What the `Computer` talks to. Anything that needs to see both its input and its output, like
+// the hull painting robot, implements this directly; otherwise, pair up an `Input` and an
+// `Output` in a tuple.
+pub trait Io {
+    fn input(&mut self) -> Option<i64>;
+    fn output(&mut self, value: i64);
+
+    // Like `Output::write_big`.
+    fn output_big(&mut self, _value: &BigInt) -> bool {
+        false
+    }
+}
+
+impl<I: Input, O: Output> Io for (I, O) {
+    fn input(&mut self) -> Option<i64> {
+        self.0.read()
+    }
+
+    fn output(&mut self, value: i64) {
+        self.1.write(value)
+    }
+
+    fn output_big(&mut self, value: &BigInt) -> bool {
+        self.1.write_big(value)
+    }
+}
+
+impl<T: Input + ?Sized> Input for &mut T {
+    fn read(&mut self) -> Option<i64> {
+        (**self).read()
+    }
+}
+
+impl<T: Output + ?Sized> Output for &mut T {
+    fn write(&mut self, value: i64) {
+        (**self).write(value)
+    }
+
+    fn write_big(&mut self, value: &BigInt) -> bool {
+        (**self).write_big(value)
+    }
+}
+
+impl Input for std::vec::IntoIter<i64> {
+    fn read(&mut self) -> Option<i64> {
+        self.next()
+    }
+}
+
+impl Input for VecDeque<i64> {
+    fn read(&mut self) -> Option<i64> {
+        self.pop_front()
+    }
+}
+
+impl Output for Vec<i64> {
+    fn write(&mut self, value: i64) {
+        self.push(value)
+    }
+}
+
+// Collects output that may not fit in an i64.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct BigOutput(pub Vec<BigInt>);
+
+impl Output for BigOutput {
+    fn write(&mut self, value: i64) {
+        self.0.push(BigInt::from(value))
+    }
+
+    fn write_big(&mut self, value: &BigInt) -> bool {
+        self.0.push(value.clone());
+        true
+    }
+}
+
+impl Input for Receiver<i64> {
+    fn read(&mut self) -> Option<i64> {
+        self.recv().ok()
+    }
+}
+
+impl Output for Sender<i64> {
+    fn write(&mut self, value: i64) {
+        // Once the other end has gone nobody can see the value, so like a `Receiver` with no
+        // sender reading as no input, it's dropped rather than stopping the computer
+        let _ = self.send(value);
+    }
+}
+
+impl Input for Stdin {
+    fn read(&mut self) -> Option<i64> {
+        let mut line = String::new();
+        self.lock().read_line(&mut line).ok()?;
+        line.trim().parse().ok()
+    }
+}
+
+impl Output for Stdout {
+    fn write(&mut self, value: i64) {
+        writeln!(self, "{}", value).expect("Should have been able to write to stdout")
+    }
+
+    fn write_big(&mut self, value: &BigInt) -> bool {
+        writeln!(self, "{}", value).expect("Should have been able to write to stdout");
+        true
+    }
+}
+
+pub struct InputFn<F>(F);
+
+pub fn input_fn<F: FnMut() -> Option<i64>>(f: F) -> InputFn<F> {
+    InputFn(f)
+}
+
+impl<F: FnMut() -> Option<i64>> Input for InputFn<F> {
+    fn read(&mut self) -> Option<i64> {
+        (self.0)()
+    }
+}
+
+pub struct OutputFn<F>(F);
+
+pub fn output_fn<F: FnMut(i64)>(f: F) -> OutputFn<F> {
+    OutputFn(f)
+}
+
+impl<F: FnMut(i64)> Output for OutputFn<F> {
+    fn write(&mut self, value: i64) {
+        (self.0)(value)
+    }
+}
+
+// For programs that talk in ASCII: output under 128 is a character, anything else is a number
+// (usually the answer) and gets a line of its own, and input is read a line at a time and sent
+// one character at a time, ending with a newline (10).
+pub struct AsciiIo<R, W> {
+    input: R,
+    output: W,
+    // Lines to send before reading any, written out as they're sent like they'd been typed
+    script: VecDeque<String>,
+    line: VecDeque<i64>,
+    // Whether the output is partway through a line
+    midline: bool,
+}
+
+impl<R: BufRead, W: Write> AsciiIo<R, W> {
+    pub fn new(input: R, output: W) -> AsciiIo<R, W> {
+        AsciiIo {
+            input,
+            output,
+            script: VecDeque::new(),
+            line: VecDeque::new(),
+            midline: false,
+        }
+    }
+
+    // Sends each line of `script` first, then goes on to `input` once they've run out.
+    pub fn script(mut self, script: &str) -> AsciiIo<R, W> {
+        self.script.extend(script.lines().map(String::from));
+        self
+    }
+
+    pub fn into_output(self) -> W {
+        self.output
+    }
+
+    fn next_line(&mut self) -> Option<String> {
+        self.output
+            .flush()
+            .expect("Should have been able to write the output");
+
+        if let Some(line) = self.script.pop_front() {
+            writeln!(self.output, "{}", line).expect("Should have been able to write the output");
+            return Some(line);
+        }
+
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end_matches(['\r', '\n']).to_string()),
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> Io for AsciiIo<R, W> {
+    fn input(&mut self) -> Option<i64> {
+        if self.line.is_empty() {
+            let line = self.next_line()?;
+            self.line.extend(line.bytes().map(i64::from));
+            self.line.push_back(10);
+        }
+        self.line.pop_front()
+    }
+
+    fn output(&mut self, value: i64) {
+        let result = if (0..128).contains(&value) {
+            self.midline = value != 10;
+            self.output.write_all(&[value as u8])
+        } else if mem::take(&mut self.midline) {
+            writeln!(self.output, "\n{}", value)
+        } else {
+            writeln!(self.output, "{}", value)
+        };
+        result.expect("Should have been able to write the output")
+    }
+
+    fn output_big(&mut self, value: &BigInt) -> bool {
+        let newline = if mem::take(&mut self.midline) {
+            "\n"
+        } else {
+            ""
+        };
+        writeln!(self.output, "{}{}", newline, value)
+            .expect("Should have been able to write the output");
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computer, State};
+    use std::sync::mpsc::channel;
+    use std::thread;
+
+    fn run_intcode(program: Vec<i64>, input: Receiver<i64>, output: Sender<i64>) {
+        Computer::new(program).run(&mut (input, output)).unwrap();
+    }
+
+    #[test]
+    fn run_intcode_with_vec() {
+        let program = vec![3, 13, 3, 14, 4, 13, 1, 13, 14, 15, 4, 15, 99, 0, 0, 0];
+        let mut output = vec![];
+
+        Computer::new(program)
+            .run(&mut (vec![3, 4].into_iter(), &mut output))
+            .unwrap();
+
+        assert_eq!(output, vec![3, 7]);
+    }
+
+    #[test]
+    fn run_intcode_with_closures() {
+        let program = vec![3, 13, 3, 14, 4, 13, 1, 13, 14, 15, 4, 15, 99, 0, 0, 0];
+        let mut next_input = 10;
+        let mut output = vec![];
+
+        Computer::new(program)
+            .run(&mut (
+                input_fn(|| {
+                    next_input += 1;
+                    Some(next_input)
+                }),
+                output_fn(|value| output.push(value * 2)),
+            ))
+            .unwrap();
+
+        assert_eq!(output, vec![22, 46]);
+    }
+
+    #[test]
+    fn run_intcode_with_channels() {
+        let (send_input, receive_input) = channel();
+        let (send_output, receive_output) = channel();
+
+        thread::spawn(move || {
+            let program = vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, -1];
+            run_intcode(program, receive_input, send_output);
+        });
+
+        send_input.send(3).unwrap();
+
+        assert_eq!(receive_output.recv().unwrap(), 3);
+        assert_eq!(receive_output.recv().unwrap(), 2);
+        assert_eq!(receive_output.recv().unwrap(), 1);
+        assert!(receive_output.recv().is_err());
+    }
+
+    #[test]
+    fn output_to_a_closed_channel_is_dropped() {
+        let (send_output, receive_output) = channel();
+        drop(receive_output);
+
+        let program = vec![104, 1, 104, 2, 99];
+        let mut computer = Computer::new(program);
+        computer
+            .run(&mut (vec![].into_iter(), send_output))
+            .unwrap();
+
+        assert!(computer.is_halted());
+    }
+
+    #[test]
+    fn run_intcode_that_expects_multiple_inputs_and_sends_multiple_outputs() {
+        let (send_input, receive_input) = channel();
+        let (send_output, receive_output) = channel();
+
+        thread::spawn(move || {
+            let program = vec![
+                3, 18, 3, 19, 1002, 19, 2, 19, 4, 19, 1001, 18, -1, 18, 1005, 18, 2, 99, -1, -2,
+            ];
+            run_intcode(program, receive_input, send_output);
+        });
+
+        // Run loop 3 times
+        send_input.send(3).unwrap();
+
+        send_input.send(5).unwrap();
+        assert_eq!(receive_output.recv().unwrap(), 10);
+
+        send_input.send(25).unwrap();
+        assert_eq!(receive_output.recv().unwrap(), 50);
+
+        send_input.send(1).unwrap();
+        assert_eq!(receive_output.recv().unwrap(), 2);
+
+        assert!(receive_output.recv().is_err());
+    }
+
+    #[test]
+    fn run_looped_channel_intcode_computers() {
+        let (send_input_original, receive_input1) = channel();
+        let (send_output1, receive_input2) = channel();
+        let (send_output2, receive_output_spy) = channel();
+
+        let program1 = vec![
+            3, 20, 4, 20, 3, 21, 1002, 21, 2, 21, 4, 21, 1001, 20, -1, 20, 1005, 20, 4, 99, -1, -2,
+        ];
+        let program2 = program1.clone();
+
+        thread::spawn(move || {
+            run_intcode(program1, receive_input1, send_output1);
+        });
+
+        thread::spawn(move || {
+            run_intcode(program2, receive_input2, send_output2);
+        });
+
+        // Run loop 3 times, which also sends 3 around to be doubled
+        send_input_original.send(3).unwrap();
+
+        let mut final_value = -1;
+
+        while let Ok(received_value) = receive_output_spy.recv() {
+            let _ = send_input_original.send(received_value);
+            final_value = received_value;
+        }
+
+        assert_eq!(final_value, 192);
+    }
+
+    // Echoes a line back, then outputs 1000
+    const ECHO: [i64; 14] = [
+        3, 100, 4, 100, 1008, 100, 10, 101, 1006, 101, 0, 104, 1000, 99,
+    ];
+
+    fn run_ascii(io: &mut AsciiIo<&[u8], Vec<u8>>) -> State {
+        Computer::new(ECHO.to_vec()).run_until_input(io).unwrap()
+    }
+
+    #[test]
+    fn ascii_lines_go_in_a_character_at_a_time() {
+        let mut io = AsciiIo::new(&b"hi there\r\nnot read\n"[..], vec![]);
+
+        assert_eq!(run_ascii(&mut io), State::Halted);
+        assert_eq!(
+            String::from_utf8(io.into_output()).unwrap(),
+            "hi there\n1000\n"
+        );
+
+        let mut io = AsciiIo::new(&b""[..], vec![]);
+        assert_eq!(run_ascii(&mut io), State::NeedsInput);
+    }
+
+    #[test]
+    fn ascii_scripts_come_before_input_and_are_echoed() {
+        let mut io = AsciiIo::new(&b"typed\n"[..], vec![]).script("scripted\nunused\n");
+
+        assert_eq!(run_ascii(&mut io), State::Halted);
+        assert_eq!(
+            String::from_utf8(io.into_output()).unwrap(),
+            "scripted\nscripted\n1000\n"
+        );
+    }
+
+    #[test]
+    fn values_past_ascii_get_lines_of_their_own() {
+        let program = vec![104, 65, 104, 128, 104, -1, 104, 10, 99];
+        let mut io = AsciiIo::new(&b""[..], vec![]);
+
+        Computer::new(program).run(&mut io).unwrap();
+        assert_eq!(
+            String::from_utf8(io.into_output()).unwrap(),
+            "A\n128\n-1\n\n"
+        );
+    }
+}
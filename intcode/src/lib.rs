@@ -0,0 +1,1574 @@
+use crate::arithmetic::{fits, wrap};
+use crate::memory::DENSE_LIMIT;
+use crate::opcodes::Run;
+use num_bigint::BigInt;
+use std::collections::BTreeMap;
+use std::num::ParseIntError;
+use std::sync::Arc;
+
+mod arithmetic;
+pub mod assemble;
+pub mod cfg;
+pub mod compile;
+pub mod config;
+pub mod debugger;
+pub mod disassemble;
+mod error;
+mod history;
+pub mod io;
+mod limits;
+mod memory;
+pub mod network;
+pub mod opcodes;
+pub mod search;
+pub mod snapshot;
+mod spec;
+pub mod symbolic;
+pub mod trace;
+
+pub use crate::arithmetic::Arithmetic;
+pub use crate::assemble::{assemble, AssembleError};
+pub use crate::cfg::ControlFlowGraph;
+pub use crate::compile::compile;
+pub use crate::debugger::Debugger;
+pub use crate::disassemble::disassemble;
+pub use crate::error::{IntcodeError, UnexpectedMode};
+pub use crate::history::Change;
+use crate::history::RecordInput;
+pub use crate::io::{input_fn, output_fn, AsciiIo, BigOutput, Input, Io, Output};
+use crate::limits::Budget;
+pub use crate::limits::Limits;
+pub use crate::memory::Memory;
+pub use crate::network::{Link, Network, NetworkError, Outcome, Topology};
+pub use crate::opcodes::{Opcode, Opcodes, Operation};
+pub use crate::search::{Search, SearchError};
+pub use crate::snapshot::Snapshot;
+pub use crate::spec::{SpecLevel, Violation};
+pub use crate::symbolic::Symbolic;
+pub use crate::trace::{Event, JsonTracer, Profile, Tracer};
+pub use num_bigint;
+
+pub fn parse_program(input: &str) -> Result<Vec<i64>, ParseIntError> {
+    input.trim().split(',').map(|n| n.trim().parse()).collect()
+}
+
+#[derive(Clone)]
+pub struct Computer {
+    memory: Memory,
+    pub current_position: usize,
+    pub relative_base: usize,
+    // The address the last instruction wrote to and what was there before
+    last_write: Option<(usize, i64)>,
+    // Every instruction run since `record_history` was called, so they can be undone
+    history: Option<Vec<Change>>,
+    // How many of those to keep; older ones are dropped a batch at a time
+    history_depth: usize,
+    // What the value at each address decodes to, filled in as instructions run and cleared when
+    // something writes over them
+    decoded: Vec<Option<Instruction>>,
+    limits: Limits,
+    spec_level: SpecLevel,
+    // Before day 9, memory stops at the end of the program
+    memory_end: Option<usize>,
+    arithmetic: Arithmetic,
+    // With `Arithmetic::BigInt`, every value too big for an i64, by address; the memory there has
+    // its low 64 bits. These aren't part of snapshots or the history.
+    big_values: BTreeMap<usize, BigInt>,
+    opcodes: Arc<Opcodes>,
+}
+
+impl Computer {
+    pub fn new(program: Vec<i64>) -> Computer {
+        Computer::with_arithmetic(program, Arithmetic::default())
+    }
+
+    pub fn with_arithmetic(program: Vec<i64>, arithmetic: Arithmetic) -> Computer {
+        Computer {
+            memory: Memory::new(program),
+            current_position: 0,
+            relative_base: 0,
+            last_write: None,
+            history: None,
+            history_depth: usize::MAX,
+            decoded: vec![],
+            limits: Limits::default(),
+            spec_level: SpecLevel::default(),
+            memory_end: None,
+            arithmetic,
+            big_values: BTreeMap::new(),
+            opcodes: Arc::clone(opcodes::standard()),
+        }
+    }
+
+    // Runs `program` the way the puzzle for `level` describes, so anything that came later is an
+    // error.
+    pub fn with_spec_level(program: Vec<i64>, level: SpecLevel) -> Computer {
+        let program_length = program.len();
+        let mut computer = Computer::new(program);
+
+        computer.spec_level = level;
+        if level != SpecLevel::Day9 {
+            computer.set_opcodes(level.opcodes());
+        }
+        if !level.unbounded_memory() {
+            computer.memory_end = Some(program_length);
+        }
+        computer
+    }
+
+    // The computer's memory and registers; fill in the rest of the snapshot before saving it.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            memory: self.memory.clone(),
+            current_position: self.current_position,
+            relative_base: self.relative_base,
+            input: vec![],
+            output: vec![],
+            extra: BTreeMap::new(),
+        }
+    }
+
+    // Only the memory and registers are in a snapshot, so everything else is back to what
+    // `Computer::new` has: checked arithmetic, the day 9 spec level, no limits, the standard
+    // opcodes, and no values too big for an i64. Set the limits and opcodes again if they matter.
+    pub fn restore(snapshot: &Snapshot) -> Computer {
+        Computer {
+            memory: snapshot.memory.clone(),
+            current_position: snapshot.current_position,
+            relative_base: snapshot.relative_base,
+            last_write: None,
+            history: None,
+            history_depth: usize::MAX,
+            decoded: vec![],
+            limits: Limits::default(),
+            spec_level: SpecLevel::default(),
+            memory_end: None,
+            arithmetic: Arithmetic::default(),
+            big_values: BTreeMap::new(),
+            opcodes: Arc::clone(opcodes::standard()),
+        }
+    }
+
+    pub fn current_instruction(&self) -> Result<Instruction, IntcodeError> {
+        let full_opcode = self.read_at(self.current_position);
+
+        instruction(full_opcode).map_err(|UnexpectedMode { parameter, mode }| {
+            IntcodeError::UnexpectedMode {
+                position: self.current_position,
+                opcode: full_opcode,
+                parameter,
+                mode,
+            }
+        })
+    }
+
+    fn decode_current_instruction(&mut self) -> Result<Instruction, IntcodeError> {
+        let position = self.current_position;
+
+        if let Some(&Some(inst)) = self.decoded.get(position) {
+            return Ok(inst);
+        }
+
+        let inst = self.current_instruction()?;
+        if self.spec_level != SpecLevel::Day9 {
+            self.check_modes(&inst)?;
+        }
+        if position < DENSE_LIMIT {
+            if position >= self.decoded.len() {
+                self.decoded.resize(position + 1, None);
+            }
+            self.decoded[position] = Some(inst);
+        }
+        Ok(inst)
+    }
+
+    fn check_modes(&self, inst: &Instruction) -> Result<(), IntcodeError> {
+        for parameter in 0..inst.mode_count() {
+            let mode = inst.mode(parameter);
+            if !self.spec_level.allows(mode) {
+                return Err(self.spec_violation(parameter, Violation::Mode { mode }));
+            }
+        }
+        Ok(())
+    }
+
+    fn forget_decoded(&mut self, address: usize) {
+        if let Some(decoded) = self.decoded.get_mut(address) {
+            *decoded = None;
+        }
+    }
+
+    fn get_value(&self, inst: &Instruction, parameter_index: usize) -> Result<i64, IntcodeError> {
+        self.check_memory_limit(inst, parameter_index)?;
+        let value = get_value(
+            &self.memory,
+            self.current_position,
+            inst,
+            parameter_index,
+            self.relative_base,
+        )?;
+        self.check_value(parameter_index, value)?;
+        Ok(value)
+    }
+
+    fn set_value(
+        &mut self,
+        inst: &Instruction,
+        parameter_index: usize,
+        value: i64,
+    ) -> Result<(), IntcodeError> {
+        self.check_memory_limit(inst, parameter_index)?;
+        self.check_value(parameter_index, value)?;
+        let (address, previous) = set_value(
+            &mut self.memory,
+            self.current_position,
+            inst,
+            parameter_index,
+            self.relative_base,
+            value,
+        )?;
+        self.last_write = Some((address, previous));
+        self.forget_decoded(address);
+        self.forget_big_value(address);
+        Ok(())
+    }
+
+    // Writes a result that doesn't fit in an i64 to the third parameter, the way the arithmetic
+    // says to, and moves on to the next instruction.
+    fn set_big_value(&mut self, inst: &Instruction, value: BigInt) -> Result<State, IntcodeError> {
+        if self.arithmetic == Arithmetic::Checked {
+            return Err(IntcodeError::Overflow {
+                position: self.current_position,
+                opcode: self.read_at(self.current_position),
+            });
+        }
+
+        self.set_value(inst, 2, wrap(&value))?;
+        if self.arithmetic == Arithmetic::BigInt && !fits(&value) {
+            if let Some((address, _)) = self.last_write {
+                self.big_values.insert(address, value);
+            }
+        }
+        self.current_position += 4;
+        Ok(State::Running)
+    }
+
+    fn forget_big_value(&mut self, address: usize) {
+        if !self.big_values.is_empty() {
+            self.big_values.remove(&address);
+        }
+    }
+
+    fn check_memory_limit(
+        &self,
+        inst: &Instruction,
+        parameter_index: usize,
+    ) -> Result<(), IntcodeError> {
+        if self.limits.max_memory.is_none() && self.memory_end.is_none() {
+            return Ok(());
+        }
+
+        let parameter = self.read_at(self.current_position + parameter_index + 1);
+        let address = match inst.mode(parameter_index) {
+            Mode::Position => parameter,
+            Mode::Immediate => return Ok(()),
+            Mode::Relative => relative_address(
+                &self.memory,
+                self.current_position,
+                parameter_index,
+                self.relative_base,
+            )?,
+        };
+
+        // Negative addresses are an error of their own
+        let past =
+            |limit: Option<usize>| limit.filter(|&limit| address >= 0 && address as usize >= limit);
+        if let Some(end) = past(self.memory_end) {
+            return Err(
+                self.spec_violation(parameter_index, Violation::PastTheEnd { address, end })
+            );
+        }
+        if let Some(limit) = past(self.limits.max_memory) {
+            return Err(IntcodeError::MemoryLimit {
+                position: self.current_position,
+                opcode: self.read_at(self.current_position),
+                parameter: parameter_index,
+                address,
+                limit,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_value(&self, parameter_index: usize, value: i64) -> Result<(), IntcodeError> {
+        if self.spec_level.allows_value(value) {
+            return Ok(());
+        }
+        Err(self.spec_violation(parameter_index, Violation::ValueOutOfRange { value }))
+    }
+
+    fn spec_violation(&self, parameter_index: usize, violation: Violation) -> IntcodeError {
+        IntcodeError::SpecViolation {
+            position: self.current_position,
+            opcode: self.read_at(self.current_position),
+            parameter: parameter_index,
+            level: self.spec_level,
+            violation,
+        }
+    }
+
+    fn jump(&mut self, inst: &Instruction, parameter_index: usize) -> Result<(), IntcodeError> {
+        let jump_location = self.get_value(inst, parameter_index)?;
+        if jump_location < 0 {
+            return Err(IntcodeError::NegativeAddress {
+                position: self.current_position,
+                opcode: self.read_at(self.current_position),
+                parameter: parameter_index,
+                address: jump_location,
+            });
+        }
+        self.current_position = jump_location as usize;
+        Ok(())
+    }
+
+    pub fn read_at(&self, index: usize) -> i64 {
+        self.memory.get(index)
+    }
+
+    pub fn write_at(&mut self, index: usize, value: i64) {
+        self.memory.set(index, value);
+        self.forget_decoded(index);
+        self.forget_big_value(index);
+    }
+
+    // Like `read_at`, but with the whole value if it's too big for an i64.
+    pub fn read_big(&self, index: usize) -> BigInt {
+        match self.big_values.get(&index) {
+            Some(value) => value.clone(),
+            None => BigInt::from(self.read_at(index)),
+        }
+    }
+
+    // Whether anything in memory is too big for an i64, which only `Arithmetic::BigInt` allows.
+    pub fn has_big_values(&self) -> bool {
+        !self.big_values.is_empty()
+    }
+
+    // The address the last call to `step` wrote to, if it wrote anything.
+    pub fn last_write(&self) -> Option<usize> {
+        self.last_write.map(|(address, _)| address)
+    }
+
+    // Starts keeping a history of every instruction `step` runs, so `step_back` can undo them.
+    // Writes made with `write_at` aren't part of the history.
+    pub fn record_history(&mut self) {
+        if self.history.is_none() {
+            self.history = Some(vec![]);
+        }
+    }
+
+    // Like `record_history`, but only the last `depth` instructions can be undone, so a long run
+    // doesn't keep a change for every instruction.
+    pub fn record_history_up_to(&mut self, depth: usize) {
+        self.record_history();
+        self.history_depth = depth;
+    }
+
+    pub fn history(&self) -> &[Change] {
+        let history = self.history.as_deref().unwrap_or(&[]);
+        &history[history.len().saturating_sub(self.history_depth)..]
+    }
+
+    // Undoes the last instruction in the history, and returns what it undid. Give `input` back
+    // to the program if there is some; it'll want it again.
+    pub fn step_back(&mut self) -> Option<Change> {
+        let history = self.history.as_mut()?;
+        if history.len() > self.history_depth {
+            history.drain(..history.len() - self.history_depth);
+        }
+        let change = history.pop()?;
+
+        if let Some((address, previous)) = change.write {
+            self.memory.set(address, previous);
+            self.forget_decoded(address);
+            self.forget_big_value(address);
+        }
+        self.current_position = change.position;
+        self.relative_base = change.relative_base;
+        self.last_write = None;
+
+        Some(change)
+    }
+
+    // Where the instruction that last wrote to `address` is, according to the history.
+    pub fn last_writer(&self, address: usize) -> Option<usize> {
+        self.history()
+            .iter()
+            .rev()
+            .find(|change| change.write.map(|(written, _)| written) == Some(address))
+            .map(|change| change.position)
+    }
+
+    // Limits every run from now on; see `Limits`.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    // Runs with these opcodes from now on, instead of the standard ones.
+    pub fn set_opcodes(&mut self, opcodes: Opcodes) {
+        self.opcodes = Arc::new(opcodes);
+    }
+
+    pub fn opcodes(&self) -> &Opcodes {
+        &self.opcodes
+    }
+
+    // Whether the computer runs programs just the way day 9 describes: the standard opcodes, the
+    // day 9 spec level and no limits. That's all compiled code knows how to do.
+    pub fn is_standard(&self) -> bool {
+        self.spec_level == SpecLevel::Day9
+            && self.limits == Limits::default()
+            && self.opcodes.are_standard()
+    }
+
+    pub fn is_halted(&self) -> bool {
+        match self.current_instruction() {
+            Ok(inst) => inst.opcode == 99,
+            Err(_) => false,
+        }
+    }
+
+    pub fn run(&mut self, io: &mut impl Io) -> Result<(), IntcodeError> {
+        if self.run_until_input(io)? == State::NeedsInput {
+            return Err(IntcodeError::MissingInput {
+                position: self.current_position,
+                opcode: self.read_at(self.current_position),
+            });
+        }
+        Ok(())
+    }
+
+    // Runs until the program halts or asks for input that `io` doesn't have yet. Feed it more
+    // input and call this again to pick up where it left off.
+    pub fn run_until_input(&mut self, io: &mut impl Io) -> Result<State, IntcodeError> {
+        let mut budget = Budget::new(self.limits);
+        loop {
+            budget.spend(self)?;
+            match self.step(io)? {
+                State::Running | State::Output(_) => {}
+                other => return Ok(other),
+            }
+        }
+    }
+
+    // Like `run_until_input`, but stops with `Running` after `steps` instructions, so something
+    // else can have a turn.
+    pub fn run_for(&mut self, io: &mut impl Io, steps: u64) -> Result<State, IntcodeError> {
+        let mut budget = Budget::new(self.limits);
+        for _ in 0..steps {
+            budget.spend(self)?;
+            match self.step(io)? {
+                State::Running | State::Output(_) => {}
+                other => return Ok(other),
+            }
+        }
+        Ok(State::Running)
+    }
+
+    // Like `run_until_input`, but also stops after each value the program outputs.
+    pub fn run_until_output(&mut self, io: &mut impl Io) -> Result<State, IntcodeError> {
+        let mut budget = Budget::new(self.limits);
+        loop {
+            budget.spend(self)?;
+            match self.step(io)? {
+                State::Running => {}
+                other => return Ok(other),
+            }
+        }
+    }
+
+    // Like `run`, but tells `tracer` about every instruction along the way.
+    pub fn run_traced(
+        &mut self,
+        io: &mut impl Io,
+        tracer: &mut impl Tracer,
+    ) -> Result<(), IntcodeError> {
+        let mut budget = Budget::new(self.limits);
+        loop {
+            budget.spend(self)?;
+            match self.step_traced(io, tracer)? {
+                State::Running | State::Output(_) => {}
+                State::Halted => return Ok(()),
+                State::NeedsInput => {
+                    return Err(IntcodeError::MissingInput {
+                        position: self.current_position,
+                        opcode: self.read_at(self.current_position),
+                    })
+                }
+            }
+        }
+    }
+
+    // Like `step`, but tells `tracer` about the instruction if it ran.
+    pub fn step_traced(
+        &mut self,
+        io: &mut impl Io,
+        tracer: &mut impl Tracer,
+    ) -> Result<State, IntcodeError> {
+        let position = self.current_position;
+        let relative_base = self.relative_base;
+        let inst = self.current_instruction()?;
+        let values = self.values_read_by(&inst)?;
+        let (mnemonic, parameters) = self
+            .opcodes
+            .get(inst.opcode)
+            .map_or(("?", 0), |opcode| (opcode.mnemonic, opcode.parameters));
+
+        let state = self.step(io)?;
+
+        if state != State::NeedsInput {
+            tracer.trace(&Event {
+                position,
+                relative_base,
+                instruction: inst,
+                mnemonic,
+                parameters,
+                values,
+                write: self
+                    .last_write()
+                    .map(|address| (address, self.read_at(address))),
+                state,
+            });
+        }
+        Ok(state)
+    }
+
+    fn values_read_by(&self, inst: &Instruction) -> Result<Vec<i64>, IntcodeError> {
+        let (parameter_count, written_parameter) = match self.opcodes.get(inst.opcode) {
+            Some(opcode) => (opcode.parameters, opcode.written),
+            None => return Ok(vec![]),
+        };
+
+        (0..parameter_count)
+            .filter(|&i| Some(i) != written_parameter)
+            .map(|i| self.get_value(inst, i))
+            .collect()
+    }
+
+    // Executes the instruction at `current_position`. If that instruction needs input and `io`
+    // doesn't have any, or if it fails, nothing changes, so stepping again carries on.
+    pub fn step(&mut self, io: &mut impl Io) -> Result<State, IntcodeError> {
+        if self.history.is_none() {
+            return self.execute(io);
+        }
+
+        let position = self.current_position;
+        let relative_base = self.relative_base;
+        let mut io = RecordInput { io, input: None };
+        let state = self.execute(&mut io)?;
+
+        if let (State::Running, Some(history)) | (State::Output(_), Some(history)) =
+            (state, &mut self.history)
+        {
+            history.push(Change {
+                position,
+                relative_base,
+                write: self.last_write,
+                input: io.input,
+            });
+            if history.len() >= self.history_depth.saturating_mul(2).max(1) {
+                history.drain(..history.len() - self.history_depth);
+            }
+        }
+        Ok(state)
+    }
+
+    fn execute(&mut self, io: &mut impl Io) -> Result<State, IntcodeError> {
+        self.last_write = None;
+        let inst = self.decode_current_instruction()?;
+
+        if !self.opcodes.is_standard(inst.opcode) {
+            return self.execute_custom(inst, io);
+        }
+
+        if !self.big_values.is_empty() {
+            if let Some(state) = self.execute_big(&inst, io)? {
+                return Ok(state);
+            }
+        }
+
+        match inst.opcode {
+            1 => {
+                let input1 = self.get_value(&inst, 0)?;
+                let input2 = self.get_value(&inst, 1)?;
+                let answer = match input1.checked_add(input2) {
+                    Some(answer) => answer,
+                    None => return self.set_big_value(&inst, BigInt::from(input1) + input2),
+                };
+                self.set_value(&inst, 2, answer)?;
+                self.current_position += 4;
+            }
+            2 => {
+                let input1 = self.get_value(&inst, 0)?;
+                let input2 = self.get_value(&inst, 1)?;
+                let answer = match input1.checked_mul(input2) {
+                    Some(answer) => answer,
+                    None => return self.set_big_value(&inst, BigInt::from(input1) * input2),
+                };
+                self.set_value(&inst, 2, answer)?;
+                self.current_position += 4;
+            }
+            3 => {
+                let value = match io.input() {
+                    Some(value) => value,
+                    None => return Ok(State::NeedsInput),
+                };
+                self.set_value(&inst, 0, value)?;
+                self.current_position += 2;
+            }
+            4 => {
+                let printing_value = self.get_value(&inst, 0)?;
+                io.output(printing_value);
+                self.current_position += 2;
+                return Ok(State::Output(printing_value));
+            }
+            5 => {
+                // jump-if-true
+                let test_value = self.get_value(&inst, 0)?;
+                if test_value != 0 {
+                    self.jump(&inst, 1)?;
+                } else {
+                    self.current_position += 3;
+                }
+            }
+            6 => {
+                // jump-if-false
+                let test_value = self.get_value(&inst, 0)?;
+                if test_value == 0 {
+                    self.jump(&inst, 1)?;
+                } else {
+                    self.current_position += 3;
+                }
+            }
+            7 => {
+                // less-than
+                let input1 = self.get_value(&inst, 0)?;
+                let input2 = self.get_value(&inst, 1)?;
+                let answer = if input1 < input2 { 1 } else { 0 };
+                self.set_value(&inst, 2, answer)?;
+                self.current_position += 4;
+            }
+            8 => {
+                // equals
+                let input1 = self.get_value(&inst, 0)?;
+                let input2 = self.get_value(&inst, 1)?;
+                let answer = if input1 == input2 { 1 } else { 0 };
+                self.set_value(&inst, 2, answer)?;
+                self.current_position += 4;
+            }
+            9 => {
+                // relative base adjustment
+                let input1 = self.get_value(&inst, 0)?;
+                let new_rel_base = match (self.relative_base as i64).checked_add(input1) {
+                    Some(new_rel_base) => new_rel_base,
+                    None => {
+                        return Err(IntcodeError::AddressOverflow {
+                            position: self.current_position,
+                            opcode: self.read_at(self.current_position),
+                            parameter: 0,
+                        })
+                    }
+                };
+                self.relative_base = new_rel_base as usize;
+                self.current_position += 2;
+            }
+            99 => return Ok(State::Halted),
+            _ => {
+                return Err(IntcodeError::UnknownOpcode {
+                    position: self.current_position,
+                    opcode: self.read_at(self.current_position),
+                })
+            }
+        }
+
+        Ok(State::Running)
+    }
+
+    #[inline(never)]
+    fn execute_custom(
+        &mut self,
+        inst: Instruction,
+        io: &mut impl Io,
+    ) -> Result<State, IntcodeError> {
+        match self.opcodes.get(inst.opcode) {
+            Some(&Opcode {
+                run: Run::Custom(handler),
+                parameters,
+                ..
+            }) => opcodes::run_custom(self, inst, handler, parameters, io),
+            _ => Err(IntcodeError::UnknownOpcode {
+                position: self.current_position,
+                opcode: self.read_at(self.current_position),
+            }),
+        }
+    }
+
+    // Runs the instruction if it reads a value too big for an i64. Returns `None` if it doesn't,
+    // so `execute` can run it as usual.
+    fn execute_big(
+        &mut self,
+        inst: &Instruction,
+        io: &mut impl Io,
+    ) -> Result<Option<State>, IntcodeError> {
+        let position = self.current_position;
+        let (parameter_count, written_parameter) = match self.opcodes.get(inst.opcode) {
+            Some(opcode) => (opcode.parameters, opcode.written),
+            None => return Ok(None),
+        };
+
+        // The instruction itself has to fit
+        let end = position + parameter_count;
+        if let Some((&address, _)) = self.big_values.range(position..=end).next() {
+            if address == position {
+                return Err(IntcodeError::UnknownOpcode {
+                    position,
+                    opcode: self.read_at(position),
+                });
+            }
+            return Err(self.too_big(address - position - 1));
+        }
+
+        let mut inputs = vec![];
+        for i in 0..parameter_count {
+            if Some(i) == written_parameter {
+                continue;
+            }
+            match self.big_input(inst, i) {
+                Some(value) => inputs.push(value.clone()),
+                None => inputs.push(BigInt::from(self.get_value(inst, i)?)),
+            }
+        }
+
+        let big_parameter = match inputs.iter().position(|input| !fits(input)) {
+            Some(i) => i,
+            None => return Ok(None),
+        };
+
+        match inst.opcode {
+            1 => return self.set_big_value(inst, &inputs[0] + &inputs[1]).map(Some),
+            2 => return self.set_big_value(inst, &inputs[0] * &inputs[1]).map(Some),
+            7 | 8 => {
+                let answer = if inst.opcode == 7 {
+                    inputs[0] < inputs[1]
+                } else {
+                    inputs[0] == inputs[1]
+                };
+                self.set_value(inst, 2, i64::from(answer))?;
+                self.current_position += 4;
+            }
+            4 => {
+                if !io.output_big(&inputs[0]) {
+                    return Err(self.too_big(0));
+                }
+                self.current_position += 2;
+                // There's only room for the low 64 bits here; `io` got all of them
+                return Ok(Some(State::Output(wrap(&inputs[0]))));
+            }
+            5 | 6 => {
+                if !fits(&inputs[1]) {
+                    return Err(self.too_big(1));
+                }
+                // So it's the value being tested that's big, and a big value isn't 0
+                if inst.opcode == 5 {
+                    self.jump(inst, 1)?;
+                } else {
+                    self.current_position += 3;
+                }
+            }
+            _ => return Err(self.too_big(big_parameter)),
+        }
+
+        Ok(Some(State::Running))
+    }
+
+    // The value `parameter` reads, if it's one that's too big for an i64.
+    fn big_input(&self, inst: &Instruction, parameter: usize) -> Option<&BigInt> {
+        if self.big_values.is_empty() {
+            return None;
+        }
+        let value = self.read_at(self.current_position + parameter + 1);
+        let address = match inst.mode(parameter) {
+            Mode::Position => value,
+            Mode::Immediate => return None,
+            Mode::Relative => value.checked_add(self.relative_base as i64)?,
+        };
+        if address < 0 {
+            return None;
+        }
+        self.big_values.get(&(address as usize))
+    }
+
+    fn too_big(&self, parameter: usize) -> IntcodeError {
+        IntcodeError::TooBig {
+            position: self.current_position,
+            opcode: self.read_at(self.current_position),
+            parameter,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum State {
+    Running,
+    NeedsInput,
+    Output(i64),
+    Halted,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Mode {
+    Position = 0,
+    Immediate = 1,
+    Relative = 2,
+}
+
+// No instruction has more than 3 parameters, so that's all the modes there's room for. Any mode
+// digits past those are still checked, and counted so the disassembler can tell they're there.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Instruction {
+    pub opcode: i64,
+    modes: [Mode; 3],
+    mode_count: u8,
+}
+
+impl Instruction {
+    pub fn mode(&self, parameter: usize) -> Mode {
+        self.modes.get(parameter).copied().unwrap_or(Mode::Position)
+    }
+
+    // How many mode digits the value had.
+    pub fn mode_count(&self) -> usize {
+        self.mode_count as usize
+    }
+}
+
+pub fn instruction(mut full_opcode: i64) -> Result<Instruction, UnexpectedMode> {
+    let opcode = full_opcode % 100;
+    full_opcode /= 100;
+
+    let mut modes = [Mode::Position; 3];
+    let mut mode_count = 0;
+
+    while full_opcode > 0 {
+        let mode = match full_opcode % 10 {
+            0 => Mode::Position,
+            1 => Mode::Immediate,
+            2 => Mode::Relative,
+            other => {
+                return Err(UnexpectedMode {
+                    parameter: mode_count,
+                    mode: other,
+                })
+            }
+        };
+        if let Some(slot) = modes.get_mut(mode_count) {
+            *slot = mode;
+        }
+        mode_count += 1;
+        full_opcode /= 10;
+    }
+
+    Ok(Instruction {
+        opcode,
+        modes,
+        mode_count: mode_count as u8,
+    })
+}
+
+// Where a relative mode parameter points, as long as that fits in an i64.
+fn relative_address(
+    memory: &Memory,
+    instruction_pointer: usize,
+    parameter_index: usize,
+    relative_base: usize,
+) -> Result<i64, IntcodeError> {
+    let parameter = memory.get(instruction_pointer + parameter_index + 1);
+    parameter
+        .checked_add(relative_base as i64)
+        .ok_or_else(|| IntcodeError::AddressOverflow {
+            position: instruction_pointer,
+            opcode: memory.get(instruction_pointer),
+            parameter: parameter_index,
+        })
+}
+
+fn get_value(
+    memory: &Memory,
+    instruction_pointer: usize,
+    inst: &Instruction,
+    parameter_index: usize,
+    relative_base: usize,
+) -> Result<i64, IntcodeError> {
+    let parameter_location = instruction_pointer + parameter_index + 1;
+    let parameter = memory.get(parameter_location);
+
+    let memory_location = match inst.mode(parameter_index) {
+        Mode::Position => parameter,
+        Mode::Immediate => return Ok(parameter),
+        Mode::Relative => {
+            relative_address(memory, instruction_pointer, parameter_index, relative_base)?
+        }
+    };
+
+    if memory_location < 0 {
+        return Err(IntcodeError::NegativeAddress {
+            position: instruction_pointer,
+            opcode: memory.get(instruction_pointer),
+            parameter: parameter_index,
+            address: memory_location,
+        });
+    }
+
+    Ok(memory.get(memory_location as usize))
+}
+
+fn set_value(
+    memory: &mut Memory,
+    instruction_pointer: usize,
+    inst: &Instruction,
+    parameter_index: usize,
+    relative_base: usize,
+    value: i64,
+) -> Result<(usize, i64), IntcodeError> {
+    let parameter_location = instruction_pointer + parameter_index + 1;
+    let parameter = memory.get(parameter_location);
+    let opcode = memory.get(instruction_pointer);
+
+    let memory_location = match inst.mode(parameter_index) {
+        Mode::Position => parameter,
+        Mode::Immediate => {
+            return Err(IntcodeError::WriteInImmediateMode {
+                position: instruction_pointer,
+                opcode,
+                parameter: parameter_index,
+            })
+        }
+        Mode::Relative => {
+            relative_address(memory, instruction_pointer, parameter_index, relative_base)?
+        }
+    };
+
+    if memory_location < 0 {
+        return Err(IntcodeError::NegativeAddress {
+            position: instruction_pointer,
+            opcode,
+            parameter: parameter_index,
+            address: memory_location,
+        });
+    }
+
+    let previous = memory.get(memory_location as usize);
+    memory.set(memory_location as usize, value);
+    Ok((memory_location as usize, previous))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    // Returns the memory the program started out in, along with everything it output.
+    fn run_intcode(program: Vec<i64>, input: Vec<i64>) -> (Vec<i64>, Vec<i64>) {
+        let program_length = program.len();
+        let mut computer = Computer::new(program);
+        let mut output = vec![];
+
+        computer
+            .run(&mut (input.into_iter(), &mut output))
+            .expect("Program should have run");
+
+        let memory = (0..program_length).map(|i| computer.read_at(i)).collect();
+        (memory, output)
+    }
+
+    #[test]
+    fn opcode_99_ends() {
+        let program = vec![99];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![99]);
+    }
+
+    #[test]
+    fn opcode_1_adds() {
+        let program = vec![1, 0, 0, 0, 99];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![2, 0, 0, 0, 99]);
+    }
+
+    #[test]
+    fn opcode_2_multiplies() {
+        let program = vec![2, 3, 0, 3, 99];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![2, 3, 0, 6, 99]);
+    }
+
+    #[test]
+    fn multiply_and_store_after_program() {
+        let program = vec![2, 4, 4, 5, 99, 0];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![2, 4, 4, 5, 99, 9801]);
+    }
+
+    #[test]
+    fn program_keeps_going_if_an_instruction_changes() {
+        let program = vec![1, 1, 1, 4, 99, 5, 6, 0, 99];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![30, 1, 1, 4, 2, 5, 6, 0, 99]);
+    }
+
+    #[test]
+    fn opcode_3_takes_input() {
+        let program = vec![3, 0, 99];
+        let (answer, _output) = run_intcode(program, vec![7]);
+        assert_eq!(answer, vec![7, 0, 99]);
+    }
+
+    #[test]
+    fn opcode_4_returns_output() {
+        let program = vec![4, 2, 99];
+        let (_answer, output) = run_intcode(program, vec![]);
+        assert_eq!(output, vec![99]);
+    }
+
+    #[test]
+    fn opcode_5_jumps_if_true() {
+        // Test value is false; 42 gets printed
+        let program = vec![1005, 6, 5, 104, 42, 99, 0];
+        let (_answer, output) = run_intcode(program, vec![]);
+        assert_eq!(output, vec![42]);
+
+        // Test value is true; print gets jumped over
+        let program = vec![1005, 6, 5, 104, 42, 99, 3];
+        let (_answer, output) = run_intcode(program, vec![]);
+        assert_eq!(output, Vec::<i64>::new());
+    }
+
+    #[test]
+    fn opcode_6_jumps_if_false() {
+        // Test value is false; print gets jumped over
+        let program = vec![1006, 6, 5, 104, 42, 99, 0];
+        let (_answer, output) = run_intcode(program, vec![]);
+        assert_eq!(output, Vec::<i64>::new());
+
+        // Test value is true; 42 gets printed
+        let program = vec![1006, 6, 5, 104, 42, 99, 3];
+        let (_answer, output) = run_intcode(program, vec![]);
+        assert_eq!(output, vec![42]);
+    }
+
+    #[test]
+    fn opcode_7_less_than() {
+        let program = vec![1107, 4, 5, 3, 99];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![1107, 4, 5, 1, 99]);
+
+        let program = vec![1107, 5, 4, 3, 99];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![1107, 5, 4, 0, 99]);
+    }
+
+    #[test]
+    fn opcode_8_equals() {
+        let program = vec![1108, 4, 4, 3, 99];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![1108, 4, 4, 1, 99]);
+
+        let program = vec![1108, 5, 4, 3, 99];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![1108, 5, 4, 0, 99]);
+    }
+
+    #[test]
+    fn opcode_9_adjusts_relative_base() {
+        let program = vec![109, 19, 204, -34, 99];
+        let mut computer = Computer::new(program);
+        computer.relative_base = 2000;
+        let mut output = vec![];
+        computer.write_at(1985, 42);
+
+        computer
+            .run(&mut (vec![].into_iter(), &mut output))
+            .unwrap();
+
+        assert_eq!(computer.relative_base, 2019);
+        assert_eq!(output, vec![42]);
+    }
+
+    #[test]
+    fn step_reports_what_each_instruction_did() {
+        let program = vec![3, 0, 4, 0, 99];
+        let mut computer = Computer::new(program);
+        let mut input = VecDeque::new();
+        let mut output = vec![];
+
+        assert_eq!(
+            computer.step(&mut (&mut input, &mut output)).unwrap(),
+            State::NeedsInput
+        );
+        assert_eq!(computer.current_position, 0);
+
+        input.push_back(8);
+        assert_eq!(
+            computer.step(&mut (&mut input, &mut output)).unwrap(),
+            State::Running
+        );
+        assert_eq!(
+            computer.step(&mut (&mut input, &mut output)).unwrap(),
+            State::Output(8)
+        );
+        assert_eq!(
+            computer.step(&mut (&mut input, &mut output)).unwrap(),
+            State::Halted
+        );
+        assert_eq!(
+            computer.step(&mut (&mut input, &mut output)).unwrap(),
+            State::Halted
+        );
+        assert_eq!(output, vec![8]);
+    }
+
+    #[test]
+    fn run_until_output_stops_after_each_output() {
+        let program = vec![104, 1, 104, 2, 99];
+        let mut computer = Computer::new(program);
+        let mut io = (vec![].into_iter(), vec![]);
+
+        assert_eq!(
+            computer.run_until_output(&mut io).unwrap(),
+            State::Output(1)
+        );
+        assert_eq!(
+            computer.run_until_output(&mut io).unwrap(),
+            State::Output(2)
+        );
+        assert_eq!(computer.run_until_output(&mut io).unwrap(), State::Halted);
+        assert_eq!(io.1, vec![1, 2]);
+    }
+
+    #[test]
+    fn run_until_input_can_be_resumed() {
+        // Doubles each input until it gets a 0
+        let program = vec![
+            3, 15, 1006, 15, 14, 1002, 15, 2, 15, 4, 15, 1105, 1, 0, 99, 0,
+        ];
+        let mut computer = Computer::new(program);
+        let mut input = VecDeque::new();
+        let mut output = vec![];
+
+        assert_eq!(
+            computer
+                .run_until_input(&mut (&mut input, &mut output))
+                .unwrap(),
+            State::NeedsInput
+        );
+
+        input.push_back(5);
+        assert_eq!(
+            computer
+                .run_until_input(&mut (&mut input, &mut output))
+                .unwrap(),
+            State::NeedsInput
+        );
+        assert_eq!(output, vec![10]);
+
+        input.push_back(21);
+        input.push_back(0);
+        assert_eq!(
+            computer
+                .run_until_input(&mut (&mut input, &mut output))
+                .unwrap(),
+            State::Halted
+        );
+        assert_eq!(output, vec![10, 42]);
+    }
+
+    #[test]
+    fn instructions_that_get_overwritten_are_decoded_again() {
+        // Prints 7, then turns its first instruction into a halt and jumps back to it
+        let program = vec![104, 7, 1001, 0, -5, 0, 1105, 1, 0];
+        let mut computer = Computer::new(program);
+        let mut io = (vec![].into_iter(), vec![]);
+
+        assert_eq!(computer.run_until_output(&mut io), Ok(State::Output(7)));
+        assert_eq!(computer.run_until_output(&mut io), Ok(State::Halted));
+
+        let program = vec![104, 7, 1105, 1, 0];
+        let mut computer = Computer::new(program);
+
+        assert_eq!(computer.run_until_output(&mut io), Ok(State::Output(7)));
+        assert_eq!(computer.step(&mut io), Ok(State::Running));
+        computer.write_at(0, 99);
+        assert_eq!(computer.step(&mut io), Ok(State::Halted));
+    }
+
+    #[test]
+    fn last_write_is_where_the_last_step_wrote() {
+        let program = vec![1101, 2, 3, 7, 104, 1, 99, 0];
+        let mut computer = Computer::new(program);
+        let mut io = (vec![].into_iter(), vec![]);
+
+        assert_eq!(computer.last_write(), None);
+        computer.step(&mut io).unwrap();
+        assert_eq!(computer.last_write(), Some(7));
+        computer.step(&mut io).unwrap();
+        assert_eq!(computer.last_write(), None);
+    }
+
+    #[test]
+    fn step_back_undoes_instructions() {
+        // Counts down from the input to 1
+        let program = vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, -1];
+        let mut computer = Computer::new(program.clone());
+        let mut io = (vec![2].into_iter(), vec![]);
+        computer.record_history();
+
+        assert_eq!(computer.run_until_input(&mut io), Ok(State::Halted));
+        assert_eq!(computer.history().len(), 7);
+        assert_eq!(computer.last_writer(12), Some(4));
+        assert_eq!(computer.read_at(12), 0);
+
+        while computer.current_position != 4 {
+            computer.step_back().unwrap();
+        }
+        assert_eq!(computer.read_at(12), 1);
+
+        let positions: Vec<usize> = (0..3)
+            .map(|_| computer.step_back().unwrap().position)
+            .collect();
+        assert_eq!(positions, vec![2, 8, 4]);
+        assert_eq!(computer.read_at(12), 2);
+        computer.step_back();
+
+        assert_eq!(
+            computer.step_back(),
+            Some(Change {
+                position: 0,
+                relative_base: 0,
+                write: Some((12, -1)),
+                input: Some(2)
+            })
+        );
+        assert_eq!(computer.step_back(), None);
+
+        let memory: Vec<i64> = (0..program.len()).map(|i| computer.read_at(i)).collect();
+        assert_eq!(memory, program);
+        assert_eq!(computer.current_position, 0);
+    }
+
+    #[test]
+    fn step_back_undoes_relative_base_changes_and_self_modifying_code() {
+        // Moves the relative base, then turns its own first instruction into a halt
+        let program = vec![109, 5, 1101, 0, 99, 0, 1105, 1, 0];
+        let mut computer = Computer::new(program);
+        let mut io = (vec![].into_iter(), vec![]);
+        computer.record_history();
+
+        assert_eq!(computer.run(&mut io), Ok(()));
+        assert_eq!(computer.relative_base, 5);
+
+        computer.step_back();
+        computer.step_back();
+        assert_eq!(computer.read_at(0), 109);
+        assert_eq!(computer.current_position, 2);
+        assert_eq!(computer.relative_base, 5);
+
+        computer.step_back();
+        assert_eq!(computer.relative_base, 0);
+        assert_eq!(computer.step(&mut io), Ok(State::Running));
+        assert_eq!(computer.relative_base, 5);
+    }
+
+    #[test]
+    fn history_only_goes_back_so_far() {
+        // Counts down from the input to 1
+        let program = vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, -1];
+        let mut computer = Computer::new(program);
+        let mut io = (vec![100].into_iter(), vec![]);
+        computer.record_history_up_to(5);
+
+        assert_eq!(computer.run_until_input(&mut io), Ok(State::Halted));
+        assert_eq!(computer.history().len(), 5);
+
+        let positions: Vec<usize> = std::iter::from_fn(|| computer.step_back())
+            .map(|change| change.position)
+            .collect();
+        assert_eq!(positions, vec![8, 4, 2, 8, 4]);
+        assert_eq!(computer.read_at(12), 2);
+    }
+
+    #[test]
+    fn run_without_enough_input_is_an_error() {
+        let program = vec![1101, 1, 1, 5, 3, 0, 99];
+        let mut computer = Computer::new(program);
+        let error = computer.run(&mut (vec![].into_iter(), vec![])).unwrap_err();
+
+        assert_eq!(
+            error,
+            IntcodeError::MissingInput {
+                position: 4,
+                opcode: 3
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Should have had input for opcode 3 (instruction 3 at position 4)"
+        );
+    }
+
+    #[test]
+    fn unknown_opcode_is_an_error() {
+        let program = vec![42];
+        let mut computer = Computer::new(program);
+        let error = computer.run(&mut (vec![].into_iter(), vec![])).unwrap_err();
+
+        assert_eq!(
+            error,
+            IntcodeError::UnknownOpcode {
+                position: 0,
+                opcode: 42
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Unknown opcode: 42 (instruction 42 at position 0)"
+        );
+        assert_eq!(error.parameter(), None);
+    }
+
+    #[test]
+    fn unexpected_mode_is_an_error() {
+        let program = vec![104, 1, 10301, 0, 0, 0, 99];
+        let mut computer = Computer::new(program);
+        let mut output = vec![];
+        let error = computer
+            .run(&mut (vec![].into_iter(), &mut output))
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            IntcodeError::UnexpectedMode {
+                position: 2,
+                opcode: 10301,
+                parameter: 0,
+                mode: 3
+            }
+        );
+        assert_eq!(output, vec![1]);
+    }
+
+    #[test]
+    fn negative_addresses_are_errors() {
+        let program = vec![109, -5, 201, 1, 0, 0, 99];
+        let mut computer = Computer::new(program);
+        let error = computer.run(&mut (vec![].into_iter(), vec![])).unwrap_err();
+        assert_eq!(
+            error,
+            IntcodeError::NegativeAddress {
+                position: 2,
+                opcode: 201,
+                parameter: 0,
+                address: -4
+            }
+        );
+        assert_eq!(error.parameter(), Some(0));
+
+        let program = vec![1, -1, 0, 0, 99];
+        let mut computer = Computer::new(program);
+        let error = computer.run(&mut (vec![].into_iter(), vec![])).unwrap_err();
+        assert_eq!(
+            error,
+            IntcodeError::NegativeAddress {
+                position: 0,
+                opcode: 1,
+                parameter: 0,
+                address: -1
+            }
+        );
+
+        let program = vec![1105, 1, -3, 99];
+        let mut computer = Computer::new(program);
+        let error = computer.run(&mut (vec![].into_iter(), vec![])).unwrap_err();
+        assert_eq!(
+            error,
+            IntcodeError::NegativeAddress {
+                position: 0,
+                opcode: 1105,
+                parameter: 1,
+                address: -3
+            }
+        );
+    }
+
+    #[test]
+    fn relative_addresses_that_overflow_are_errors() {
+        let program = vec![109, 1, 204, i64::MAX, 99];
+        let mut computer = Computer::new(program);
+        assert_eq!(
+            computer.run(&mut (vec![].into_iter(), vec![])),
+            Err(IntcodeError::AddressOverflow {
+                position: 2,
+                opcode: 204,
+                parameter: 0,
+            })
+        );
+
+        let program = vec![109, i64::MAX, 109, 1, 99];
+        let mut computer = Computer::new(program);
+        assert_eq!(
+            computer.run(&mut (vec![].into_iter(), vec![])),
+            Err(IntcodeError::AddressOverflow {
+                position: 2,
+                opcode: 109,
+                parameter: 0,
+            })
+        );
+        assert_eq!(computer.relative_base, i64::MAX as usize);
+    }
+
+    #[test]
+    fn writing_in_immediate_mode_is_an_error() {
+        let program = vec![11101, 1, 1, 0, 99];
+        let mut computer = Computer::new(program);
+        let error = computer.run(&mut (vec![].into_iter(), vec![])).unwrap_err();
+
+        assert_eq!(
+            error,
+            IntcodeError::WriteInImmediateMode {
+                position: 0,
+                opcode: 11101,
+                parameter: 2
+            }
+        );
+    }
+
+    #[test]
+    fn a_failed_step_leaves_the_computer_where_it_was() {
+        let program = vec![1101, 2, 3, 7, 42, 99, 0, 0];
+        let mut computer = Computer::new(program);
+        let mut io = (vec![].into_iter(), vec![]);
+
+        assert_eq!(computer.step(&mut io), Ok(State::Running));
+        assert!(computer.step(&mut io).is_err());
+        assert_eq!(computer.current_position, 4);
+
+        computer.write_at(4, 99);
+        assert_eq!(computer.run(&mut io), Ok(()));
+        assert_eq!(computer.read_at(7), 5);
+    }
+
+    #[test]
+    fn interpret_unexpected_parameter_modes() {
+        assert_eq!(
+            instruction(301),
+            Err(UnexpectedMode {
+                parameter: 0,
+                mode: 3
+            })
+        );
+        assert_eq!(
+            instruction(9001),
+            Err(UnexpectedMode {
+                parameter: 1,
+                mode: 9
+            })
+        );
+    }
+
+    #[test]
+    fn interpret_parameter_modes_all_position_mode() {
+        let inst = instruction(2).unwrap();
+        assert_eq!(inst.opcode, 2);
+        assert_eq!(inst.mode(0), Mode::Position);
+        assert_eq!(inst.mode(1), Mode::Position);
+        assert_eq!(inst.mode(2), Mode::Position);
+
+        let inst = instruction(4).unwrap();
+        assert_eq!(inst.opcode, 4);
+        assert_eq!(inst.mode(0), Mode::Position);
+        assert_eq!(inst.mode(1), Mode::Position);
+        assert_eq!(inst.mode(2), Mode::Position);
+
+        let inst = instruction(99).unwrap();
+        assert_eq!(inst.opcode, 99);
+    }
+
+    #[test]
+    fn interpret_parameter_modes_that_have_some_immediate_mode() {
+        let inst = instruction(104).unwrap();
+        assert_eq!(inst.opcode, 4);
+        assert_eq!(inst.mode(0), Mode::Immediate);
+
+        let inst = instruction(1002).unwrap();
+        assert_eq!(inst.opcode, 2);
+        assert_eq!(inst.mode(0), Mode::Position);
+        assert_eq!(inst.mode(1), Mode::Immediate);
+        assert_eq!(inst.mode(2), Mode::Position);
+
+        let inst = instruction(204).unwrap();
+        assert_eq!(inst.opcode, 4);
+        assert_eq!(inst.mode(0), Mode::Relative);
+
+        let inst = instruction(1201101).unwrap();
+        assert_eq!(inst.opcode, 1);
+        assert_eq!(inst.mode(2), Mode::Position);
+        assert_eq!(inst.mode(3), Mode::Position);
+        assert_eq!(inst.mode_count(), 5);
+    }
+
+    #[test]
+    fn use_parameter_modes_in_programs() {
+        let program = vec![1002, 4, 3, 4, 33];
+        let (answer, _output) = run_intcode(program, vec![]);
+        assert_eq!(answer, vec![1002, 4, 3, 4, 99]);
+    }
+
+    #[test]
+    fn get_value_helper_function() {
+        let program = Memory::new(vec![1, 0, 0, 0, 99]);
+        let inst = instruction(program.get(0)).unwrap();
+        let instruction_pointer = 0;
+
+        assert_eq!(get_value(&program, instruction_pointer, &inst, 0, 0), Ok(1));
+
+        let program = Memory::new(vec![104, 18, 99]);
+        let inst = instruction(program.get(0)).unwrap();
+        assert_eq!(
+            get_value(&program, instruction_pointer, &inst, 0, 0),
+            Ok(18)
+        );
+
+        let program = Memory::new(vec![109, 1, 204, -1]);
+        let inst = instruction(program.get(2)).unwrap();
+        assert_eq!(get_value(&program, 2, &inst, 0, 1), Ok(109));
+    }
+
+    #[test]
+    fn stress_tests() {
+        let program = vec![1102, 34915192, 34915192, 7, 4, 7, 99, 0];
+        let (_answer, output) = run_intcode(program, vec![]);
+        assert_eq!(output, vec![1219070632396864]);
+
+        let program = vec![104, 1125899906842624, 99];
+        let (_answer, output) = run_intcode(program, vec![]);
+        assert_eq!(output, vec![1125899906842624]);
+
+        let program = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        let (_answer, output) = run_intcode(program, vec![]);
+        assert_eq!(
+            output,
+            vec![109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99]
+        );
+    }
+}
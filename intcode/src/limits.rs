@@ -0,0 +1,159 @@
+use crate::{Computer, IntcodeError};
+use std::time::{Duration, Instant};
+
+// How far a program is allowed to go, so ones nobody has looked at can be run without hanging or
+// eating all the memory. `None` means no limit. The step count and the clock start over with
+// each call to one of the `Computer`'s run methods.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Limits {
+    pub max_steps: Option<u64>,
+    // Addresses at or past this can't be read or written
+    pub max_memory: Option<usize>,
+    pub timeout: Option<Duration>,
+}
+
+// Looking at the clock is slow next to running an instruction, so only do it this often.
+const STEPS_PER_CLOCK_CHECK: u64 = 1024;
+
+// What's left of the limits during one run.
+pub(crate) struct Budget {
+    limits: Limits,
+    steps: u64,
+    // The next step count that needs a closer look, at either the step limit or a clock check
+    next_check: u64,
+    started: Instant,
+}
+
+impl Budget {
+    pub(crate) fn new(limits: Limits) -> Budget {
+        let next_check = match (limits.max_steps, limits.timeout) {
+            (_, Some(_)) => 0,
+            (Some(max_steps), None) => max_steps,
+            (None, None) => u64::MAX,
+        };
+
+        Budget {
+            limits,
+            steps: 0,
+            next_check,
+            started: Instant::now(),
+        }
+    }
+
+    // Call before each step. Fails, without using anything up, if there's no room for another.
+    #[inline]
+    pub(crate) fn spend(&mut self, computer: &Computer) -> Result<(), IntcodeError> {
+        if self.steps >= self.next_check {
+            self.check(computer)?;
+        }
+        self.steps += 1;
+        Ok(())
+    }
+
+    fn check(&mut self, computer: &Computer) -> Result<(), IntcodeError> {
+        let position = computer.current_position;
+        let max_steps = self.limits.max_steps.unwrap_or(u64::MAX);
+
+        if self.steps >= max_steps {
+            return Err(IntcodeError::StepLimit {
+                position,
+                opcode: computer.read_at(position),
+                steps: max_steps,
+            });
+        }
+
+        if let Some(timeout) = self.limits.timeout {
+            if self.started.elapsed() >= timeout {
+                return Err(IntcodeError::Timeout {
+                    position,
+                    opcode: computer.read_at(position),
+                    timeout,
+                });
+            }
+            self.next_check = max_steps.min(self.steps + STEPS_PER_CLOCK_CHECK);
+        } else {
+            self.next_check = max_steps;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::State;
+
+    // Adds 1 to address 9 forever
+    const LOOP: [i64; 10] = [1001, 9, 1, 9, 1105, 1, 0, 99, 0, 0];
+
+    #[test]
+    fn stop_after_too_many_steps() {
+        let mut computer = Computer::new(LOOP.to_vec());
+        computer.set_limits(Limits {
+            max_steps: Some(10),
+            ..Limits::default()
+        });
+        let mut io = (vec![].into_iter(), vec![]);
+
+        assert_eq!(
+            computer.run(&mut io),
+            Err(IntcodeError::StepLimit {
+                position: 0,
+                opcode: 1001,
+                steps: 10,
+            })
+        );
+        assert_eq!(computer.read_at(9), 5);
+
+        // Each run gets a fresh budget, so it can carry on
+        assert!(computer.run_until_input(&mut io).is_err());
+        assert_eq!(computer.read_at(9), 10);
+    }
+
+    #[test]
+    fn stop_at_the_memory_limit() {
+        // Sets the relative base way out, then writes there
+        let program = vec![109, 1_000_000_000, 21101, 1, 2, 5, 99];
+        let mut computer = Computer::new(program);
+        computer.set_limits(Limits {
+            max_memory: Some(1 << 16),
+            ..Limits::default()
+        });
+
+        assert_eq!(
+            computer.run(&mut (vec![].into_iter(), vec![])),
+            Err(IntcodeError::MemoryLimit {
+                position: 2,
+                opcode: 21101,
+                parameter: 2,
+                address: 1_000_000_005,
+                limit: 1 << 16,
+            })
+        );
+
+        // Without the limit, it's fine
+        computer.set_limits(Limits::default());
+        assert_eq!(
+            computer.run_until_input(&mut (vec![].into_iter(), vec![])),
+            Ok(State::Halted)
+        );
+        assert_eq!(computer.read_at(1_000_000_005), 3);
+    }
+
+    #[test]
+    fn stop_after_too_long() {
+        let mut computer = Computer::new(LOOP.to_vec());
+        computer.set_limits(Limits {
+            timeout: Some(Duration::from_millis(20)),
+            ..Limits::default()
+        });
+
+        let started = Instant::now();
+        let error = computer.run(&mut (vec![].into_iter(), vec![])).unwrap_err();
+
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert!(matches!(error, IntcodeError::Timeout { .. }));
+        assert!(computer.read_at(9) > 0);
+    }
+}
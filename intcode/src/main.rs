@@ -0,0 +1,126 @@
+use intcode::{
+    assemble, compile, parse_program, Computer, ControlFlowGraph, Debugger, JsonTracer, Profile,
+    Symbolic,
+};
+use std::env;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+const USAGE: &str = "usage: intcode disassemble <program file>
+       intcode assemble <assembly file>
+       intcode compile <program file>
+       intcode cfg <program file>
+       intcode debug <program file>
+       intcode trace <program file> <trace file>
+       intcode profile <program file>
+       intcode symbolic <program file> [<address>=<name>...]";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["disassemble", path] => {
+            let program = parse_program(&fs::read_to_string(path)?)?;
+
+            let graph = ControlFlowGraph::new(&program);
+            let labels = graph.labels();
+
+            for line in graph.lines(&program) {
+                if let Some(label) = labels.get(&line.address()) {
+                    println!("{}:", label);
+                }
+                println!("{}", line.with_labels(&labels));
+            }
+        }
+        ["assemble", path] => {
+            let program = assemble(&fs::read_to_string(path)?)?;
+            let values: Vec<String> = program.iter().map(i64::to_string).collect();
+
+            println!("{}", values.join(","));
+        }
+        ["compile", path] => {
+            let program = parse_program(&fs::read_to_string(path)?)?;
+            print!("{}", compile(&program));
+        }
+        ["cfg", path] => {
+            let program = parse_program(&fs::read_to_string(path)?)?;
+            print!("{}", ControlFlowGraph::new(&program).to_dot());
+        }
+        ["debug", path] => {
+            let program = parse_program(&fs::read_to_string(path)?)?;
+            debug(Debugger::new(program))?;
+        }
+        ["trace", path, trace_path] => {
+            let program = parse_program(&fs::read_to_string(path)?)?;
+            let mut tracer = JsonTracer::new(BufWriter::new(File::create(trace_path)?));
+
+            Computer::new(program).run_traced(&mut (io::stdin(), io::stdout()), &mut tracer)?;
+            tracer.finish()?;
+        }
+        ["profile", path] => {
+            let program = parse_program(&fs::read_to_string(path)?)?;
+            let mut profile = Profile::new();
+
+            Computer::new(program).run_traced(&mut (io::stdin(), io::stdout()), &mut profile)?;
+            eprintln!("{}", profile);
+        }
+        ["symbolic", path, variables @ ..] => {
+            let program = parse_program(&fs::read_to_string(path)?)?;
+            let mut symbolic = Symbolic::new(&program);
+            for variable in variables {
+                let (address, name) = variable
+                    .split_once('=')
+                    .ok_or_else(|| format!("`{}` should be <address>=<name>", variable))?;
+                symbolic = symbolic.variable(address.parse()?, name);
+            }
+
+            for (i, path) in symbolic.run().iter().enumerate() {
+                println!("path {}: {}", i + 1, path.end);
+                for constraint in &path.constraints {
+                    println!("  if {}", constraint);
+                }
+                println!("  [0] = {}", path.read(0));
+                for output in &path.outputs {
+                    println!("  output {}", output);
+                }
+            }
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+fn debug(mut debugger: Debugger) -> io::Result<()> {
+    println!("{}", debugger.current_line());
+    print!("(intcode) ");
+    io::stdout().flush()?;
+
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+
+        match line.trim() {
+            "quit" | "q" => break,
+            "" => {}
+            command => match debugger.execute(command) {
+                Ok(report) => println!("{}", report),
+                Err(e) => eprintln!("{}", e),
+            },
+        }
+
+        print!("(intcode) ");
+        io::stdout().flush()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+// Addresses below this live in a `Vec` that grows as the program writes further out; anything
+// past it, which only happens with a wild relative base, goes in a `HashMap` so one far away
+// write doesn't allocate gigabytes.
+pub(crate) const DENSE_LIMIT: usize = 1 << 20;
+
+// A program's memory. Every address reads as 0 until something is written there.
+//
+// With the `hashmap-memory` feature, every address goes in the `HashMap`, the way memory was
+// kept before there was a `Vec`, so the benchmarks have something to compare against.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Memory {
+    dense: Vec<i64>,
+    sparse: HashMap<usize, i64>,
+}
+
+impl Memory {
+    pub fn new(program: Vec<i64>) -> Memory {
+        Memory::from_parts(program, HashMap::new())
+    }
+
+    pub(crate) fn from_parts(dense: Vec<i64>, mut sparse: HashMap<usize, i64>) -> Memory {
+        if cfg!(feature = "hashmap-memory") {
+            sparse.extend(dense.into_iter().enumerate());
+            return Memory {
+                dense: vec![],
+                sparse,
+            };
+        }
+        Memory { dense, sparse }
+    }
+
+    pub(crate) fn parts(&self) -> (&[i64], &HashMap<usize, i64>) {
+        (&self.dense, &self.sparse)
+    }
+
+    pub fn get(&self, address: usize) -> i64 {
+        if cfg!(feature = "hashmap-memory") {
+            return self.sparse.get(&address).copied().unwrap_or(0);
+        }
+        match self.dense.get(address) {
+            Some(&value) => value,
+            None if address < DENSE_LIMIT => 0,
+            None => self.sparse.get(&address).copied().unwrap_or(0),
+        }
+    }
+
+    pub fn set(&mut self, address: usize, value: i64) {
+        if cfg!(feature = "hashmap-memory") {
+            self.sparse.insert(address, value);
+        } else if address < self.dense.len() {
+            self.dense[address] = value;
+        } else if address < DENSE_LIMIT {
+            self.dense.resize(address + 1, 0);
+            self.dense[address] = value;
+        } else {
+            self.sparse.insert(address, value);
+        }
+    }
+}
+
+// These look at how the memory is split up, which the feature changes
+#[cfg(all(test, not(feature = "hashmap-memory")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unwritten_memory_is_zero() {
+        let mut memory = Memory::new(vec![1, 2, 3]);
+
+        assert_eq!(memory.get(2), 3);
+        assert_eq!(memory.get(3), 0);
+        assert_eq!(memory.get(DENSE_LIMIT * 4), 0);
+
+        memory.set(10, 7);
+        assert_eq!(memory.get(10), 7);
+        assert_eq!(memory.get(9), 0);
+        assert_eq!(memory.dense.len(), 11);
+    }
+
+    #[test]
+    fn far_away_writes_dont_grow_the_dense_memory() {
+        let mut memory = Memory::new(vec![1, 2, 3]);
+
+        memory.set(DENSE_LIMIT + 5, 42);
+        assert_eq!(memory.get(DENSE_LIMIT + 5), 42);
+        assert_eq!(memory.dense.len(), 3);
+    }
+}
@@ -0,0 +1,526 @@
+use crate::{Computer, IntcodeError, Io, Snapshot, State};
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+
+// How many instructions a machine runs in one turn at most, so one that never waits for input
+// still lets the others run, and its output gets delivered as it goes.
+const QUANTUM: u64 = 10_000;
+
+// Where each machine's output goes.
+#[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Topology {
+    // Each machine feeds the next one; the last machine's output is the network's output.
+    Chain,
+    // Like `Chain`, but the last machine feeds the first one too, like day 7's feedback loop.
+    Ring,
+    // Every machine's output goes to every other machine, and to the network's output.
+    Broadcast,
+    // Machines send packets of three values: an address, then two values for the machine at that
+    // address. Packets for addresses that aren't in the network go to the network's output.
+    // Machines never wait for input; when there's nothing for them, they read -1.
+    Packet,
+    // Each machine's output goes wherever `Network::connect` said it should.
+    Links,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Link {
+    Machine(usize),
+    Output,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Outcome {
+    // Every machine halted.
+    Halted,
+    // Nothing is happening in a packet network: nobody has sent anything, and every machine that
+    // hasn't halted has read -1 and come back to exactly where it was the last time it did.
+    Idle,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum NetworkError {
+    Machine { machine: usize, error: IntcodeError },
+    // Every machine that hasn't halted is waiting for input that nobody is going to send.
+    Deadlock { waiting: Vec<usize> },
+    // Still going after `Network::set_max_rounds` rounds.
+    RoundLimit { rounds: u64 },
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::Machine { machine, error } => write!(f, "machine {}: {}", machine, error),
+            NetworkError::Deadlock { waiting } => {
+                let waiting: Vec<String> = waiting.iter().map(usize::to_string).collect();
+                write!(
+                    f,
+                    "deadlock: machines {} are all waiting for input",
+                    waiting.join(", ")
+                )
+            }
+            NetworkError::RoundLimit { rounds } => {
+                write!(f, "still running after {} rounds", rounds)
+            }
+        }
+    }
+}
+
+impl Error for NetworkError {}
+
+struct Machine {
+    computer: Computer,
+    input: VecDeque<i64>,
+    // Output that hasn't made a whole packet yet
+    partial_packet: Vec<i64>,
+    links: Vec<Link>,
+    last_output: Option<i64>,
+    halted: bool,
+    // Its last turn ran out of time rather than waiting for input
+    busy: bool,
+    // In a packet network, the state the machine was in after its last turn if all it did was
+    // read -1, and whether the turn before that left it in the same state
+    idle_state: Option<Snapshot>,
+    blocked: bool,
+}
+
+// Runs any number of `Computer`s on one thread, taking turns: each one runs until it needs input
+// nobody has sent it yet, or for `QUANTUM` instructions, then its output is delivered and the next
+// one goes.
+pub struct Network {
+    topology: Topology,
+    machines: Vec<Machine>,
+    pub output: Vec<i64>,
+    max_rounds: Option<u64>,
+}
+
+impl Network {
+    pub fn new(topology: Topology) -> Network {
+        Network {
+            topology,
+            machines: vec![],
+            output: vec![],
+            max_rounds: None,
+        }
+    }
+
+    // Gives up with `NetworkError::RoundLimit` after every machine has had this many turns in one
+    // call to `run`, for networks where something might never stop.
+    pub fn set_max_rounds(&mut self, max_rounds: Option<u64>) {
+        self.max_rounds = max_rounds;
+    }
+
+    // Adds a machine that starts with `input` waiting for it, and returns its address.
+    pub fn add(&mut self, computer: Computer, input: impl IntoIterator<Item = i64>) -> usize {
+        self.machines.push(Machine {
+            computer,
+            input: input.into_iter().collect(),
+            partial_packet: vec![],
+            links: vec![],
+            last_output: None,
+            halted: false,
+            busy: false,
+            idle_state: None,
+            blocked: false,
+        });
+        self.machines.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.machines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.machines.is_empty()
+    }
+
+    // Sends everything the machine at `from` outputs to `to` as well as anywhere it already goes.
+    // Only used by `Topology::Links`.
+    pub fn connect(&mut self, from: usize, to: Link) {
+        self.machines[from].links.push(to);
+    }
+
+    // Gives the machine at `address` some input from outside the network.
+    pub fn send(&mut self, address: usize, value: i64) {
+        self.machines[address].input.push_back(value);
+    }
+
+    pub fn computer(&self, address: usize) -> &Computer {
+        &self.machines[address].computer
+    }
+
+    pub fn last_output(&self, address: usize) -> Option<i64> {
+        self.machines[address].last_output
+    }
+
+    pub fn run(&mut self) -> Result<Outcome, NetworkError> {
+        let mut rounds = 0;
+        loop {
+            if self
+                .max_rounds
+                .is_some_and(|max_rounds| rounds >= max_rounds)
+            {
+                return Err(NetworkError::RoundLimit { rounds });
+            }
+            rounds += 1;
+            let mut anything_sent = false;
+
+            for address in 0..self.machines.len() {
+                if self.machines[address].halted {
+                    continue;
+                }
+
+                let output = self.take_turn(address)?;
+                anything_sent |= !output.is_empty();
+                self.deliver(address, output);
+            }
+            // Nothing is going to read what's sent to a machine that halted
+            for machine in self.machines.iter_mut().filter(|machine| machine.halted) {
+                machine.input.clear();
+            }
+
+            let waiting: Vec<usize> = (0..self.machines.len())
+                .filter(|&address| !self.machines[address].halted)
+                .collect();
+
+            if waiting.is_empty() {
+                return Ok(Outcome::Halted);
+            }
+            let busy = |a: &usize| self.machines[*a].busy || !self.machines[*a].input.is_empty();
+            if anything_sent || waiting.iter().any(busy) {
+                continue;
+            }
+            // A machine that reads -1 might still be working something out, so a packet network
+            // is only idle once a whole round goes by without any machine changing
+            match self.topology {
+                Topology::Packet if waiting.iter().all(|&a| self.machines[a].blocked) => {
+                    return Ok(Outcome::Idle)
+                }
+                Topology::Packet => {}
+                _ => return Err(NetworkError::Deadlock { waiting }),
+            }
+        }
+    }
+
+    fn take_turn(&mut self, address: usize) -> Result<Vec<i64>, NetworkError> {
+        let machine = &mut self.machines[address];
+        let had_input = !machine.input.is_empty();
+        let mut port = Port {
+            input: &mut machine.input,
+            output: vec![],
+            idle_value: if self.topology == Topology::Packet {
+                Some(-1)
+            } else {
+                None
+            },
+        };
+
+        let state = machine
+            .computer
+            .run_for(&mut port, QUANTUM)
+            .map_err(|error| NetworkError::Machine {
+                machine: address,
+                error,
+            })?;
+        machine.halted = state == State::Halted;
+        machine.busy = state == State::Running;
+
+        let read_idle_value = self.topology == Topology::Packet && port.idle_value.is_none();
+        if read_idle_value && !had_input && port.output.is_empty() && !machine.busy {
+            let state = machine.computer.snapshot();
+            machine.blocked = machine.idle_state.as_ref() == Some(&state);
+            machine.idle_state = Some(state);
+        } else {
+            machine.idle_state = None;
+            machine.blocked = false;
+        }
+
+        Ok(port.output)
+    }
+
+    fn deliver(&mut self, from: usize, output: Vec<i64>) {
+        let count = self.machines.len();
+        if let Some(&last) = output.last() {
+            self.machines[from].last_output = Some(last);
+        }
+
+        match self.topology {
+            Topology::Chain if from + 1 < count => self.machines[from + 1].input.extend(output),
+            Topology::Chain => self.output.extend(output),
+            Topology::Ring => {
+                if from + 1 == count {
+                    self.output.extend(&output);
+                }
+                self.machines[(from + 1) % count].input.extend(output);
+            }
+            Topology::Broadcast => {
+                for (address, machine) in self.machines.iter_mut().enumerate() {
+                    if address != from {
+                        machine.input.extend(&output);
+                    }
+                }
+                self.output.extend(output);
+            }
+            Topology::Packet => {
+                let mut values = std::mem::take(&mut self.machines[from].partial_packet);
+                values.extend(output);
+
+                let mut packets = values.chunks_exact(3);
+                for packet in &mut packets {
+                    match self.machines.get_mut(packet[0] as usize) {
+                        Some(machine) if packet[0] >= 0 => machine.input.extend(&packet[1..]),
+                        _ => self.output.extend(packet),
+                    }
+                }
+                self.machines[from].partial_packet = packets.remainder().to_vec();
+            }
+            Topology::Links => {
+                for link in self.machines[from].links.clone() {
+                    match link {
+                        Link::Machine(to) => self.machines[to].input.extend(&output),
+                        Link::Output => self.output.extend(&output),
+                    }
+                }
+            }
+        }
+    }
+}
+
+// A machine's side of the network for one turn.
+struct Port<'a> {
+    input: &'a mut VecDeque<i64>,
+    output: Vec<i64>,
+    // What to read once when there's no input, instead of waiting
+    idle_value: Option<i64>,
+}
+
+impl Io for Port<'_> {
+    fn input(&mut self) -> Option<i64> {
+        self.input.pop_front().or_else(|| self.idle_value.take())
+    }
+
+    fn output(&mut self, value: i64) {
+        self.output.push(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assemble;
+
+    // Adds its input to what the phase setting was, forever
+    fn adder() -> Computer {
+        Computer::new(
+            assemble(
+                "
+                        in [phase]
+                loop:   in [value]
+                        add [value], [phase], [value]
+                        out [value]
+                        jt #1, #loop
+                phase:  .data 0
+                value:  .data 0
+                ",
+            )
+            .unwrap(),
+        )
+    }
+
+    // Reads one value, outputs it doubled and halts
+    fn doubler() -> Computer {
+        Computer::new(vec![3, 9, 1002, 9, 2, 9, 4, 9, 99, 0])
+    }
+
+    #[test]
+    fn chain() {
+        let mut network = Network::new(Topology::Chain);
+        network.add(doubler(), vec![3]);
+        network.add(doubler(), vec![]);
+        network.add(doubler(), vec![]);
+
+        assert_eq!(network.run(), Ok(Outcome::Halted));
+        assert_eq!(network.output, vec![24]);
+    }
+
+    #[test]
+    fn ring_deadlocks_if_nothing_halts() {
+        let mut network = Network::new(Topology::Ring);
+        network.add(adder(), vec![1]);
+        network.add(adder(), vec![10]);
+
+        assert_eq!(
+            network.run(),
+            Err(NetworkError::Deadlock {
+                waiting: vec![0, 1]
+            })
+        );
+        assert_eq!(
+            network.run().unwrap_err().to_string(),
+            "deadlock: machines 0, 1 are all waiting for input"
+        );
+
+        // Once something gets the loop going, it goes forever; take it around a few times
+        network.send(0, 0);
+        for _ in 0..3 {
+            let _ = network
+                .take_turn(0)
+                .map(|output| network.deliver(0, output));
+            let _ = network
+                .take_turn(1)
+                .map(|output| network.deliver(1, output));
+        }
+        assert_eq!(network.output, vec![11, 22, 33]);
+    }
+
+    #[test]
+    fn ring_runs_day_7_feedback_loop() {
+        let program = vec![
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let mut network = Network::new(Topology::Ring);
+        for &phase_setting in &[9, 8, 7, 6, 5] {
+            network.add(Computer::new(program.clone()), vec![phase_setting]);
+        }
+        network.send(0, 0);
+
+        assert_eq!(network.run(), Ok(Outcome::Halted));
+        assert_eq!(network.output.last(), Some(&139629729));
+    }
+
+    #[test]
+    fn broadcast() {
+        let mut network = Network::new(Topology::Broadcast);
+        network.add(Computer::new(vec![104, 5, 99]), vec![]);
+        network.add(doubler(), vec![]);
+        network.add(doubler(), vec![]);
+
+        assert_eq!(network.run(), Ok(Outcome::Halted));
+        assert_eq!(network.output, vec![5, 10, 10]);
+    }
+
+    #[test]
+    fn packets() {
+        // Machine 0 sends (1, 7, 8) to machine 1, which adds them up and sends the total to 255
+        let sender = Computer::new(vec![104, 1, 104, 7, 104, 8, 99]);
+        let receiver = Computer::new(
+            assemble(
+                "
+                wait:   in [x]
+                        eq [x], #-1, [idle]
+                        jt [idle], #wait
+                        in [y]
+                        add [x], [y], [x]
+                        out #255
+                        out #0
+                        out [x]
+                        hlt
+                x:      .data 0
+                y:      .data 0
+                idle:   .data 0
+                ",
+            )
+            .unwrap(),
+        );
+
+        let mut network = Network::new(Topology::Packet);
+        network.add(sender, vec![]);
+        network.add(receiver, vec![]);
+
+        assert_eq!(network.run(), Ok(Outcome::Halted));
+        assert_eq!(network.output, vec![255, 0, 15]);
+    }
+
+    #[test]
+    fn idle_packet_network() {
+        // Reads -1 forever
+        let program = vec![3, 100, 1105, 1, 0];
+        let mut network = Network::new(Topology::Packet);
+        for _ in 0..200 {
+            network.add(Computer::new(program.clone()), vec![]);
+        }
+
+        assert_eq!(network.run(), Ok(Outcome::Idle));
+        assert_eq!(network.len(), 200);
+    }
+
+    #[test]
+    fn packet_network_waits_for_machines_still_working() {
+        // Counts down from 5, reading -1 each time, before sending its packet
+        let slow = Computer::new(
+            assemble(
+                "
+                loop:   in [x]
+                        add [count], #-1, [count]
+                        jt [count], #loop
+                        out #255
+                        out #1
+                        out #2
+                idle:   in [x]
+                        jt #1, #idle
+                count:  .data 5
+                x:      .data 0
+                ",
+            )
+            .unwrap(),
+        );
+        let mut network = Network::new(Topology::Packet);
+        network.add(Computer::new(vec![3, 100, 1105, 1, 0]), vec![]);
+        network.add(slow, vec![]);
+
+        assert_eq!(network.run(), Ok(Outcome::Idle));
+        assert_eq!(network.output, vec![255, 1, 2]);
+    }
+
+    #[test]
+    fn machines_that_never_wait_take_turns_too() {
+        // Outputs 1 forever
+        let mut network = Network::new(Topology::Chain);
+        network.add(Computer::new(vec![104, 1, 1105, 1, 0]), vec![]);
+        network.add(doubler(), vec![]);
+        network.set_max_rounds(Some(10));
+
+        assert_eq!(network.run(), Err(NetworkError::RoundLimit { rounds: 10 }));
+        assert_eq!(network.output, vec![2]);
+        assert!(network.machines[1].input.is_empty());
+    }
+
+    #[test]
+    fn links() {
+        // 0 sends to 1 and 2, which both send to the output, and 2 also sends back to 1
+        let mut network = Network::new(Topology::Links);
+        network.add(Computer::new(vec![104, 5, 99]), vec![]);
+        network.add(adder(), vec![100]);
+        network.add(doubler(), vec![]);
+        network.connect(0, Link::Machine(1));
+        network.connect(0, Link::Machine(2));
+        network.connect(1, Link::Output);
+        network.connect(2, Link::Output);
+        network.connect(2, Link::Machine(1));
+
+        assert_eq!(
+            network.run(),
+            Err(NetworkError::Deadlock { waiting: vec![1] })
+        );
+        assert_eq!(network.output, vec![105, 10, 110]);
+        assert_eq!(network.last_output(0), Some(5));
+        assert_eq!(network.last_output(1), Some(110));
+    }
+
+    #[test]
+    fn errors_say_which_machine_failed() {
+        let mut network = Network::new(Topology::Chain);
+        network.add(doubler(), vec![1]);
+        network.add(Computer::new(vec![42]), vec![]);
+
+        let error = network.run().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "machine 1: Unknown opcode: 42 (instruction 42 at position 0)"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use crate::{Computer, Instruction, IntcodeError, Io, State};
+use std::sync::{Arc, OnceLock};
+
+// What an added opcode does. It reads and writes its parameters through the `Operation`, and
+// returns the state to stop in like `Computer::step` does. Unless it jumps, the computer moves on
+// to the next instruction afterwards, except for `Halted` and `NeedsInput`, which stay put.
+pub type Handler = fn(&mut Operation<'_>) -> Result<State, IntcodeError>;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Run {
+    // One of the opcodes built into the `Computer`
+    Standard,
+    Custom(Handler),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Opcode {
+    pub mnemonic: &'static str,
+    pub parameters: usize,
+    // The parameter it writes to, which can't be in immediate mode
+    pub written: Option<usize>,
+    pub(crate) run: Run,
+}
+
+impl Opcode {
+    pub fn new(
+        mnemonic: &'static str,
+        parameters: usize,
+        written: Option<usize>,
+        handler: Handler,
+    ) -> Opcode {
+        assert!(
+            written.is_none_or(|written| written < parameters),
+            "`{}` can't write to a parameter it doesn't have",
+            mnemonic
+        );
+        Opcode {
+            mnemonic,
+            parameters,
+            written,
+            run: Run::Custom(handler),
+        }
+    }
+
+    const fn standard(mnemonic: &'static str, parameters: usize, written: Option<usize>) -> Opcode {
+        Opcode {
+            mnemonic,
+            parameters,
+            written,
+            run: Run::Standard,
+        }
+    }
+}
+
+const STANDARD: [(i64, Opcode); 10] = [
+    (1, Opcode::standard("add", 3, Some(2))),
+    (2, Opcode::standard("mul", 3, Some(2))),
+    (3, Opcode::standard("in", 1, Some(0))),
+    (4, Opcode::standard("out", 1, None)),
+    (5, Opcode::standard("jt", 2, None)),
+    (6, Opcode::standard("jf", 2, None)),
+    (7, Opcode::standard("lt", 3, Some(2))),
+    (8, Opcode::standard("eq", 3, Some(2))),
+    (9, Opcode::standard("arb", 1, None)),
+    (99, Opcode::standard("hlt", 0, None)),
+];
+
+// The opcodes a `Computer` runs. Only the last two digits of a value are the opcode, so there's
+// room for 100 of them.
+#[derive(Debug, Clone)]
+pub struct Opcodes {
+    table: Vec<Option<Opcode>>,
+    // A bit for each opcode that's built into the `Computer`, so running those doesn't have to
+    // look in the table
+    standard: u128,
+}
+
+impl Opcodes {
+    pub fn empty() -> Opcodes {
+        Opcodes {
+            table: vec![None; 100],
+            standard: 0,
+        }
+    }
+
+    // Every opcode from the puzzles.
+    pub fn standard() -> Opcodes {
+        Opcodes::day(9)
+    }
+
+    // The opcodes the puzzles had by `day`: 1, 2 and 99 from day 2, 3 to 8 from day 5, and 9
+    // from day 9.
+    pub fn day(day: u32) -> Opcodes {
+        let mut opcodes = Opcodes::empty();
+        for &(opcode, definition) in &STANDARD {
+            let added_on = match opcode {
+                1 | 2 | 99 => 2,
+                3..=8 => 5,
+                _ => 9,
+            };
+            if day >= added_on {
+                opcodes.insert(opcode, definition);
+            }
+        }
+        opcodes
+    }
+
+    // Adds `opcode`, or replaces what was there.
+    pub fn insert(&mut self, opcode: i64, definition: Opcode) {
+        assert!(
+            (0..100).contains(&opcode),
+            "opcodes are from 0 to 99, not {}",
+            opcode
+        );
+        self.table[opcode as usize] = Some(definition);
+        match definition.run {
+            Run::Standard => self.standard |= 1 << opcode,
+            Run::Custom(_) => self.standard &= !(1 << opcode),
+        }
+    }
+
+    pub fn remove(&mut self, opcode: i64) -> Option<Opcode> {
+        let removed = self.table.get_mut(opcode as usize)?.take();
+        self.standard &= !(1 << opcode);
+        removed
+    }
+
+    pub fn get(&self, opcode: i64) -> Option<&Opcode> {
+        if opcode < 0 {
+            return None;
+        }
+        self.table.get(opcode as usize)?.as_ref()
+    }
+
+    // Whether these are exactly the opcodes from the puzzles, and nothing else.
+    pub fn are_standard(&self) -> bool {
+        let standard = STANDARD
+            .iter()
+            .fold(0_u128, |bits, &(opcode, _)| bits | 1 << opcode);
+        self.standard == standard && self.table.iter().flatten().count() == STANDARD.len()
+    }
+
+    #[inline]
+    pub(crate) fn is_standard(&self, opcode: i64) -> bool {
+        (0..100).contains(&opcode) && self.standard & (1 << opcode) != 0
+    }
+}
+
+impl Default for Opcodes {
+    fn default() -> Opcodes {
+        Opcodes::standard()
+    }
+}
+
+// Shared by every `Computer` that hasn't been given opcodes of its own
+pub(crate) fn standard() -> &'static Arc<Opcodes> {
+    static STANDARD_OPCODES: OnceLock<Arc<Opcodes>> = OnceLock::new();
+    STANDARD_OPCODES.get_or_init(|| Arc::new(Opcodes::standard()))
+}
+
+// An added opcode's view of the computer while it runs.
+pub struct Operation<'a> {
+    computer: &'a mut Computer,
+    instruction: Instruction,
+    io: &'a mut dyn Io,
+    jumped: bool,
+}
+
+impl Operation<'_> {
+    pub fn computer(&mut self) -> &mut Computer {
+        self.computer
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        self.instruction
+    }
+
+    // The value of `parameter`, in whatever mode it's in.
+    pub fn read(&self, parameter: usize) -> Result<i64, IntcodeError> {
+        if self
+            .computer
+            .big_input(&self.instruction, parameter)
+            .is_some()
+        {
+            return Err(self.computer.too_big(parameter));
+        }
+        self.computer.get_value(&self.instruction, parameter)
+    }
+
+    pub fn write(&mut self, parameter: usize, value: i64) -> Result<(), IntcodeError> {
+        self.computer.set_value(&self.instruction, parameter, value)
+    }
+
+    pub fn input(&mut self) -> Option<i64> {
+        self.io.input()
+    }
+
+    pub fn output(&mut self, value: i64) {
+        self.io.output(value)
+    }
+
+    // Carries on from the address in `parameter` instead of the next instruction.
+    pub fn jump(&mut self, parameter: usize) -> Result<(), IntcodeError> {
+        self.computer.jump(&self.instruction, parameter)?;
+        self.jumped = true;
+        Ok(())
+    }
+}
+
+pub(crate) fn run_custom(
+    computer: &mut Computer,
+    instruction: Instruction,
+    handler: Handler,
+    parameters: usize,
+    io: &mut dyn Io,
+) -> Result<State, IntcodeError> {
+    let mut operation = Operation {
+        computer,
+        instruction,
+        io,
+        jumped: false,
+    };
+    let state = handler(&mut operation)?;
+
+    if let State::Running | State::Output(_) = state {
+        if !operation.jumped {
+            operation.computer.current_position += parameters + 1;
+        }
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(opcodes: Opcodes, program: Vec<i64>) -> Result<Vec<i64>, IntcodeError> {
+        let mut computer = Computer::new(program);
+        computer.set_opcodes(opcodes);
+        let mut output = vec![];
+        computer.run(&mut (vec![5].into_iter(), &mut output))?;
+        Ok(output)
+    }
+
+    #[test]
+    fn restrict_to_a_days_opcodes() {
+        // The day 2 example runs, but input is from day 5
+        let program = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        assert!(run(Opcodes::day(2), program).is_ok());
+
+        let program = vec![1101, 1, 2, 9, 3, 9, 4, 9, 99, 0];
+        assert_eq!(
+            run(Opcodes::day(2), program.clone()),
+            Err(IntcodeError::UnknownOpcode {
+                position: 4,
+                opcode: 3,
+            })
+        );
+        assert_eq!(run(Opcodes::day(5), program), Ok(vec![5]));
+
+        assert_eq!(
+            run(Opcodes::day(5), vec![109, 1, 99]),
+            Err(IntcodeError::UnknownOpcode {
+                position: 0,
+                opcode: 109,
+            })
+        );
+    }
+
+    #[test]
+    fn add_opcodes() {
+        let mut opcodes = Opcodes::standard();
+        // Divides, rounding towards 0
+        opcodes.insert(
+            10,
+            Opcode::new("div", 3, Some(2), |operation| {
+                let quotient = operation.read(0)? / operation.read(1)?;
+                operation.write(2, quotient)?;
+                Ok(State::Running)
+            }),
+        );
+        // Jumps no matter what
+        opcodes.insert(
+            11,
+            Opcode::new("jmp", 1, None, |operation| {
+                operation.jump(0)?;
+                Ok(State::Running)
+            }),
+        );
+        // Outputs an exit code and stops
+        opcodes.insert(
+            12,
+            Opcode::new("exit", 1, None, |operation| {
+                let code = operation.read(0)?;
+                operation.output(code);
+                Ok(State::Halted)
+            }),
+        );
+
+        // [13] = [14] / 2, then skips the halts and exits with [13]
+        let program = vec![1010, 14, 2, 13, 111, 8, 99, 99, 12, 13, 99, 0, 0, 0, 17];
+        let mut computer = Computer::new(program);
+        computer.set_opcodes(opcodes);
+        let mut output = vec![];
+
+        assert_eq!(
+            computer.run_until_input(&mut (vec![].into_iter(), &mut output)),
+            Ok(State::Halted)
+        );
+        assert_eq!(output, vec![8]);
+        assert_eq!(computer.current_position, 8);
+    }
+
+    #[test]
+    fn opcodes_describe_themselves() {
+        let opcodes = Opcodes::standard();
+        let add = opcodes.get(1).unwrap();
+
+        assert_eq!(
+            (add.mnemonic, add.parameters, add.written),
+            ("add", 3, Some(2))
+        );
+        assert!(opcodes.get(42).is_none());
+        assert!(opcodes.get(-1).is_none());
+        assert!(Opcodes::empty().get(99).is_none());
+        assert!(opcodes.are_standard());
+        assert!(!Opcodes::day(5).are_standard());
+    }
+}
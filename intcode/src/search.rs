@@ -0,0 +1,397 @@
+use crate::{Computer, IntcodeError, Limits};
+use rayon::prelude::*;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+// Looks for values to patch into a program so that running it gives what's wanted, like the noun
+// and verb on day 2. The program doesn't get any input, and each run stops at the search's
+// `Limits`, so values that make it loop forever fail like any others.
+#[derive(Debug, Clone)]
+pub struct Search {
+    program: Vec<i64>,
+    patches: Vec<(usize, RangeInclusive<i64>)>,
+    limits: Limits,
+}
+
+const DEFAULT_MAX_STEPS: u64 = 1_000_000;
+
+// A result that's `constant` plus each patched value times its coefficient.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Linear {
+    pub constant: i64,
+    pub coefficients: Vec<i64>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SearchError {
+    Run(IntcodeError),
+    // There are more combinations to try than fit in a u64, or a number the search worked out
+    // doesn't fit in an i64
+    Overflow,
+}
+
+impl fmt::Display for SearchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchError::Run(error) => write!(f, "{}", error),
+            SearchError::Overflow => write!(f, "the search is too big for 64 bits"),
+        }
+    }
+}
+
+impl Error for SearchError {}
+
+impl From<IntcodeError> for SearchError {
+    fn from(error: IntcodeError) -> SearchError {
+        SearchError::Run(error)
+    }
+}
+
+impl Search {
+    pub fn new(program: Vec<i64>) -> Search {
+        Search {
+            program,
+            patches: vec![],
+            limits: Limits {
+                max_steps: Some(DEFAULT_MAX_STEPS),
+                ..Limits::default()
+            },
+        }
+    }
+
+    // Runs each combination with these limits instead of a million steps.
+    pub fn limits(mut self, limits: Limits) -> Search {
+        self.limits = limits;
+        self
+    }
+
+    // Tries every value in `values` at `address`.
+    pub fn patch(mut self, address: usize, values: RangeInclusive<i64>) -> Search {
+        self.patches.push((address, values));
+        self
+    }
+
+    // Runs the program with `values` patched in, in the order the patches were added.
+    pub fn run(&self, values: &[i64]) -> Result<Computer, IntcodeError> {
+        let mut program = self.program.clone();
+        for ((address, _), &value) in self.patches.iter().zip(values) {
+            if *address >= program.len() {
+                program.resize(address + 1, 0);
+            }
+            program[*address] = value;
+        }
+
+        let mut computer = Computer::new(program);
+        computer.set_limits(self.limits);
+        computer.run(&mut (vec![].into_iter(), vec![]))?;
+        Ok(computer)
+    }
+
+    // Tries every combination of values across all the threads there are, and returns one that
+    // makes `wanted` true as soon as any thread finds it. Which one that is, if there's more than
+    // one, is up to how the threads go. Values the program fails with are skipped like any others
+    // that don't make `wanted` true.
+    pub fn find<F>(&self, wanted: F) -> Result<Option<Vec<i64>>, SearchError>
+    where
+        F: Fn(&Computer) -> bool + Sync,
+    {
+        let combinations = self
+            .patches
+            .iter()
+            .map(|(_, values)| range_length(values))
+            .try_fold(1_u128, u128::checked_mul)
+            .and_then(|combinations| u64::try_from(combinations).ok())
+            .ok_or(SearchError::Overflow)?;
+
+        Ok((0..combinations).into_par_iter().find_map_any(|i| {
+            let values = self.combination(i);
+            match self.run(&values) {
+                Ok(computer) if wanted(&computer) => Some(values),
+                _ => None,
+            }
+        }))
+    }
+
+    // Works out `result` as a linear function of the patched values, from a few runs. `None`
+    // means it isn't one, as far as those runs can tell.
+    pub fn linear<F>(&self, result: F) -> Result<Option<Linear>, SearchError>
+    where
+        F: Fn(&Computer) -> i64,
+    {
+        let lowest: Vec<i64> = self
+            .patches
+            .iter()
+            .map(|(_, values)| *values.start())
+            .collect();
+        let at_lowest = result(&self.run(&lowest)?);
+
+        let mut coefficients = vec![];
+        for i in 0..self.patches.len() {
+            let mut values = lowest.clone();
+            if range_length(&self.patches[i].1) > 1 {
+                values[i] += 1;
+                let coefficient = i128::from(result(&self.run(&values)?)) - i128::from(at_lowest);
+                coefficients.push(narrow(coefficient)?);
+            } else {
+                coefficients.push(0);
+            }
+        }
+        let constant = narrow(i128::from(at_lowest) - dot(&coefficients, &lowest))?;
+        let linear = Linear {
+            constant,
+            coefficients,
+        };
+
+        // Check it against the highest value of each patch, all of them at once, and the middle
+        let mut checks = vec![];
+        let highest: Vec<i64> = self
+            .patches
+            .iter()
+            .map(|(_, values)| *values.end())
+            .collect();
+        for i in 0..self.patches.len() {
+            let mut values = lowest.clone();
+            values[i] = highest[i];
+            checks.push(values);
+        }
+        checks.push(
+            lowest
+                .iter()
+                .zip(&highest)
+                .map(|(&low, &high)| ((i128::from(low) + i128::from(high)) / 2) as i64)
+                .collect(),
+        );
+        checks.push(highest);
+
+        for values in checks {
+            if i128::from(result(&self.run(&values)?)) != linear.at_wide(&values) {
+                return Ok(None);
+            }
+        }
+        Ok(Some(linear))
+    }
+
+    // Finds values that make `result` equal `target`. If `result` looks linear, they're worked
+    // out from that and checked with one run; otherwise, or if that doesn't pan out, this is
+    // `find`.
+    pub fn solve<F>(&self, result: F, target: i64) -> Result<Option<Vec<i64>>, SearchError>
+    where
+        F: Fn(&Computer) -> i64 + Sync,
+    {
+        if let Ok(Some(linear)) = self.linear(&result) {
+            let ranges: Vec<_> = self
+                .patches
+                .iter()
+                .map(|(_, values)| values.clone())
+                .collect();
+            if let Some(values) = linear.solve(&ranges, target) {
+                if self.run(&values).map(|computer| result(&computer)) == Ok(target) {
+                    return Ok(Some(values));
+                }
+            }
+        }
+
+        self.find(|computer| result(computer) == target)
+    }
+
+    // The `i`th combination of values, counting with the first patch changing slowest.
+    fn combination(&self, i: u64) -> Vec<i64> {
+        let mut i = u128::from(i);
+        let mut values = vec![0; self.patches.len()];
+        for (value, (_, range)) in values.iter_mut().zip(&self.patches).rev() {
+            let length = range_length(range);
+            *value = (i128::from(*range.start()) + (i % length) as i128) as i64;
+            i /= length;
+        }
+        values
+    }
+}
+
+impl Linear {
+    pub fn at(&self, values: &[i64]) -> Result<i64, SearchError> {
+        narrow(self.at_wide(values))
+    }
+
+    fn at_wide(&self, values: &[i64]) -> i128 {
+        i128::from(self.constant) + dot(&self.coefficients, values)
+    }
+
+    // Values from `ranges` that come out to `target`, found by trying every value of all but the
+    // one with the most values, and working out what that one has to be.
+    pub fn solve(&self, ranges: &[RangeInclusive<i64>], target: i64) -> Option<Vec<i64>> {
+        let mut values: Vec<i64> = ranges.iter().map(|values| *values.start()).collect();
+        let solved = (0..ranges.len())
+            .filter(|&i| self.coefficients[i] != 0)
+            .max_by_key(|&i| range_length(&ranges[i]));
+        let solved = match solved {
+            Some(solved) => solved,
+            None if self.constant == target => return Some(values),
+            None => return None,
+        };
+
+        loop {
+            values[solved] = 0;
+            let rest = i128::from(target) - self.at_wide(&values);
+            let coefficient = i128::from(self.coefficients[solved]);
+            if rest % coefficient == 0 {
+                if let Ok(value) = i64::try_from(rest / coefficient) {
+                    if ranges[solved].contains(&value) {
+                        values[solved] = value;
+                        return Some(values);
+                    }
+                }
+            }
+
+            // Counts up through the other values, like an odometer
+            let mut i = 0;
+            loop {
+                if i == ranges.len() {
+                    return None;
+                }
+                if i != solved && values[i] < *ranges[i].end() {
+                    values[i] += 1;
+                    break;
+                }
+                values[i] = *ranges[i].start();
+                i += 1;
+            }
+        }
+    }
+}
+
+// Products of two i64s fit in an i128 with plenty of room to add up as many as there are patches
+fn dot(a: &[i64], b: &[i64]) -> i128 {
+    a.iter()
+        .zip(b)
+        .map(|(&a, &b)| i128::from(a) * i128::from(b))
+        .sum()
+}
+
+fn narrow(value: i128) -> Result<i64, SearchError> {
+    i64::try_from(value).map_err(|_| SearchError::Overflow)
+}
+
+// Up to 2^64, for the range of every i64
+fn range_length(range: &RangeInclusive<i64>) -> u128 {
+    if range.is_empty() {
+        0
+    } else {
+        (i128::from(*range.end()) - i128::from(*range.start())) as u128 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // [0] = [9] * 3 + [10]
+    const LINEAR: [i64; 11] = [1002, 9, 3, 9, 1, 9, 10, 0, 99, 0, 0];
+    // [0] = [5] * [6]
+    const PRODUCT: [i64; 7] = [2, 5, 6, 0, 99, 0, 0];
+
+    fn first_value(computer: &Computer) -> i64 {
+        computer.read_at(0)
+    }
+
+    #[test]
+    fn find_values_in_parallel() {
+        let search = Search::new(PRODUCT.to_vec())
+            .patch(5, 2..=20)
+            .patch(6, 2..=20);
+
+        let found = search.find(|computer| computer.read_at(0) == 91).unwrap();
+        let found = found.unwrap();
+        assert_eq!(found[0] * found[1], 91);
+
+        assert_eq!(search.find(|computer| computer.read_at(0) == 23), Ok(None));
+    }
+
+    #[test]
+    fn values_the_program_fails_with_are_skipped() {
+        // 0 isn't an opcode, 1 adds and 2 multiplies [0] by itself
+        let search = Search::new(vec![0, 0, 0, 0, 99]).patch(0, 0..=2);
+        assert_eq!(
+            search.find(|computer| computer.read_at(0) == 4),
+            Ok(Some(vec![2]))
+        );
+
+        // None of these are opcodes
+        let search = Search::new(vec![0, 0, 0, 0, 99]).patch(0, 10..=12);
+        assert_eq!(search.find(|_| true), Ok(None));
+    }
+
+    #[test]
+    fn values_that_loop_forever_are_skipped() {
+        // Jumps back to the start forever unless [1] is 0
+        let search = Search::new(vec![1105, 0, 0, 99]).patch(1, 1..=3);
+        assert!(matches!(
+            search.run(&[1]),
+            Err(IntcodeError::StepLimit { .. })
+        ));
+        assert_eq!(search.find(|_| true), Ok(None));
+
+        let search = Search::new(vec![1105, 0, 0, 99])
+            .patch(1, -2..=2)
+            .limits(Limits {
+                max_steps: Some(100),
+                ..Limits::default()
+            });
+        assert_eq!(search.find(|_| true), Ok(Some(vec![0])));
+    }
+
+    #[test]
+    fn searches_too_big_for_64_bits_are_errors() {
+        let search = Search::new(PRODUCT.to_vec()).patch(5, i64::MIN..=i64::MAX);
+        assert_eq!(search.find(|_| true), Err(SearchError::Overflow));
+        let search = Search::new(PRODUCT.to_vec())
+            .patch(5, 0..=i64::MAX)
+            .patch(6, 0..=3);
+        assert_eq!(search.find(|_| true), Err(SearchError::Overflow));
+
+        let linear = Linear {
+            constant: 1,
+            coefficients: vec![i64::MAX],
+        };
+        assert_eq!(linear.at(&[1]), Err(SearchError::Overflow));
+        assert_eq!(linear.at(&[-1]), Ok(i64::MIN + 2));
+        assert_eq!(linear.solve(&[i64::MIN..=i64::MAX], i64::MIN), None);
+    }
+
+    #[test]
+    fn spot_linear_results() {
+        let search = Search::new(LINEAR.to_vec())
+            .patch(9, 0..=99)
+            .patch(10, 0..=99);
+        assert_eq!(
+            search.linear(first_value),
+            Ok(Some(Linear {
+                constant: 0,
+                coefficients: vec![3, 1],
+            }))
+        );
+
+        let search = Search::new(PRODUCT.to_vec())
+            .patch(5, 0..=99)
+            .patch(6, 0..=99);
+        assert_eq!(search.linear(first_value), Ok(None));
+    }
+
+    #[test]
+    fn solve_linear_results_directly() {
+        let search = Search::new(LINEAR.to_vec())
+            .patch(9, 0..=99)
+            .patch(10, 0..=9);
+
+        assert_eq!(search.solve(first_value, 127), Ok(Some(vec![42, 1])));
+        assert_eq!(search.solve(first_value, 1000), Ok(None));
+
+        // Falls back to trying everything
+        let search = Search::new(PRODUCT.to_vec())
+            .patch(5, 0..=10)
+            .patch(6, 0..=10);
+        let found = search.solve(first_value, 49).unwrap();
+        assert_eq!(found, Some(vec![7, 7]));
+    }
+}
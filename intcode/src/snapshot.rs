@@ -0,0 +1,257 @@
+use crate::Memory;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8; 4] = b"ICS\x01";
+
+// Everything needed to pick a program up where it left off: the `Computer` itself, plus whatever
+// input it hasn't read yet, output nobody has handled yet, and any other state the `Io` it was
+// talking to had, stored by name.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Snapshot {
+    pub memory: Memory,
+    pub current_position: usize,
+    pub relative_base: usize,
+    pub input: Vec<i64>,
+    pub output: Vec<i64>,
+    pub extra: BTreeMap<String, Vec<i64>>,
+}
+
+impl Snapshot {
+    // The file starts with `MAGIC`, and every number after that is a LEB128 varint; values
+    // that can be negative are zigzag encoded first so small negative numbers stay small too.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let (dense, sparse) = self.memory.parts();
+        let mut sparse: Vec<(&usize, &i64)> = sparse.iter().collect();
+        sparse.sort();
+
+        writer.write_all(MAGIC)?;
+        write_unsigned(&mut writer, self.current_position as u64)?;
+        write_unsigned(&mut writer, self.relative_base as u64)?;
+
+        write_values(&mut writer, dense)?;
+        write_unsigned(&mut writer, sparse.len() as u64)?;
+        for (&address, &value) in sparse {
+            write_unsigned(&mut writer, address as u64)?;
+            write_signed(&mut writer, value)?;
+        }
+
+        write_values(&mut writer, &self.input)?;
+        write_values(&mut writer, &self.output)?;
+
+        write_unsigned(&mut writer, self.extra.len() as u64)?;
+        for (name, values) in &self.extra {
+            write_unsigned(&mut writer, name.len() as u64)?;
+            writer.write_all(name.as_bytes())?;
+            write_values(&mut writer, values)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read_from(mut reader: impl Read) -> io::Result<Snapshot> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not an Intcode snapshot"));
+        }
+
+        let current_position = read_usize(&mut reader)?;
+        let relative_base = read_usize(&mut reader)?;
+
+        let dense = read_values(&mut reader)?;
+        let mut sparse = HashMap::new();
+        for _ in 0..read_usize(&mut reader)? {
+            let address = read_usize(&mut reader)?;
+            sparse.insert(address, read_signed(&mut reader)?);
+        }
+
+        let input = read_values(&mut reader)?;
+        let output = read_values(&mut reader)?;
+
+        let mut extra = BTreeMap::new();
+        for _ in 0..read_usize(&mut reader)? {
+            let mut name = vec![0; read_usize(&mut reader)?];
+            reader.read_exact(&mut name)?;
+            let name = String::from_utf8(name).map_err(|_| invalid("name isn't UTF-8"))?;
+            extra.insert(name, read_values(&mut reader)?);
+        }
+
+        Ok(Snapshot {
+            memory: Memory::from_parts(dense, sparse),
+            current_position,
+            relative_base,
+            input,
+            output,
+            extra,
+        })
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_unsigned(writer: &mut impl Write, mut value: u64) -> io::Result<()> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn write_signed(writer: &mut impl Write, value: i64) -> io::Result<()> {
+    write_unsigned(writer, ((value << 1) ^ (value >> 63)) as u64)
+}
+
+fn write_values(writer: &mut impl Write, values: &[i64]) -> io::Result<()> {
+    write_unsigned(writer, values.len() as u64)?;
+    for &value in values {
+        write_signed(writer, value)?;
+    }
+    Ok(())
+}
+
+fn read_unsigned(reader: &mut impl Read) -> io::Result<u64> {
+    let mut value = 0;
+
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(invalid("number is too long"))
+}
+
+fn read_usize(reader: &mut impl Read) -> io::Result<usize> {
+    let value = read_unsigned(reader)?;
+    if value > usize::MAX as u64 {
+        return Err(invalid("number is too big for this platform"));
+    }
+    Ok(value as usize)
+}
+
+fn read_signed(reader: &mut impl Read) -> io::Result<i64> {
+    let value = read_unsigned(reader)?;
+    Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+}
+
+fn read_values(reader: &mut impl Read) -> io::Result<Vec<i64>> {
+    let length = read_usize(reader)?;
+    // Don't trust the length enough to allocate all of it up front
+    let mut values = Vec::with_capacity(length.min(1 << 16));
+    for _ in 0..length {
+        values.push(read_signed(reader)?);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Arithmetic, Computer, IntcodeError, Limits, Opcodes, State};
+    use std::collections::VecDeque;
+
+    fn round_trip(snapshot: &Snapshot) -> Snapshot {
+        let mut bytes = vec![];
+        snapshot.write_to(&mut bytes).unwrap();
+        Snapshot::read_from(&bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn restored_computers_carry_on_where_they_left_off() {
+        // Doubles each input until it gets a 0
+        let program = vec![
+            3, 15, 1006, 15, 14, 1002, 15, 2, 15, 4, 15, 1105, 1, 0, 99, 0,
+        ];
+        let mut computer = Computer::new(program);
+        let mut input = VecDeque::from(vec![5]);
+        let mut output = vec![];
+
+        computer
+            .run_until_input(&mut (&mut input, &mut output))
+            .unwrap();
+        computer.write_at(5000, 7);
+        computer.write_at(usize::MAX - 1, -7);
+
+        let mut snapshot = computer.snapshot();
+        snapshot.input = vec![21, 0];
+        snapshot.output = output.clone();
+        let snapshot = round_trip(&snapshot);
+
+        let mut restored = Computer::restore(&snapshot);
+        let mut input = VecDeque::from(snapshot.input.clone());
+        let mut output = snapshot.output.clone();
+
+        assert_eq!(restored.read_at(5000), 7);
+        assert_eq!(restored.read_at(usize::MAX - 1), -7);
+        assert_eq!(
+            restored.run_until_input(&mut (&mut input, &mut output)),
+            Ok(State::Halted)
+        );
+        assert_eq!(output, vec![10, 42]);
+    }
+
+    #[test]
+    fn only_memory_and_registers_are_restored() {
+        // [9] = [9] * [9], twice, then outputs [9]
+        let program = vec![2, 9, 9, 9, 2, 9, 9, 9, 99, 1 << 20];
+        let mut computer = Computer::with_arithmetic(program, Arithmetic::Wrapping);
+        let mut opcodes = Opcodes::standard();
+        opcodes.remove(9);
+        computer.set_opcodes(opcodes);
+        computer.set_limits(Limits {
+            max_steps: Some(1),
+            ..Limits::default()
+        });
+        computer.step(&mut (vec![].into_iter(), vec![])).unwrap();
+
+        let snapshot = computer.snapshot();
+        let mut restored = Computer::restore(&round_trip(&snapshot));
+        assert_eq!(restored.snapshot(), snapshot);
+        assert!(restored.opcodes().get(9).is_some());
+
+        // The step limit is gone, and the arithmetic is checked again
+        assert_eq!(
+            restored.run(&mut (vec![].into_iter(), vec![])),
+            Err(IntcodeError::Overflow {
+                position: 4,
+                opcode: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn extra_state_round_trips() {
+        let mut snapshot = Computer::new(vec![109, -3, 99]).snapshot();
+        snapshot.relative_base = 12;
+        snapshot
+            .extra
+            .insert(String::from("score"), vec![i64::MIN, -1, 0, i64::MAX]);
+        snapshot.extra.insert(String::from("output_x"), vec![]);
+
+        assert_eq!(round_trip(&snapshot), snapshot);
+    }
+
+    #[test]
+    fn reading_something_else_is_an_error() {
+        let error = Snapshot::read_from(&b"1,2,3,99"[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let mut bytes = vec![];
+        Computer::new(vec![1, 2, 3])
+            .snapshot()
+            .write_to(&mut bytes)
+            .unwrap();
+        bytes.pop();
+        let error = Snapshot::read_from(&bytes[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{Mode, Opcodes};
+use std::convert::TryFrom;
+use std::fmt;
+
+// Which puzzle's description of the Intcode computer to hold programs to. Each level allows
+// everything the ones before it did.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum SpecLevel {
+    // Add, multiply and halt, with every parameter in position mode, in the program's own memory
+    Day2,
+    // Input, output, jumps, comparisons and immediate mode
+    Day5,
+    // Relative mode and the opcode to adjust the relative base, with memory going on past the
+    // end of the program
+    #[default]
+    Day9,
+}
+
+impl SpecLevel {
+    pub fn opcodes(self) -> Opcodes {
+        match self {
+            SpecLevel::Day2 => Opcodes::day(2),
+            SpecLevel::Day5 => Opcodes::day(5),
+            SpecLevel::Day9 => Opcodes::day(9),
+        }
+    }
+
+    pub fn allows(self, mode: Mode) -> bool {
+        match mode {
+            Mode::Position => true,
+            Mode::Immediate => self >= SpecLevel::Day5,
+            Mode::Relative => self >= SpecLevel::Day9,
+        }
+    }
+
+    // Whether there's memory past the end of the program.
+    pub fn unbounded_memory(self) -> bool {
+        self >= SpecLevel::Day9
+    }
+
+    // Before day 9, every value fits in 32 bits.
+    pub fn allows_value(self, value: i64) -> bool {
+        self >= SpecLevel::Day9 || i32::try_from(value).is_ok()
+    }
+}
+
+// What a program did that the puzzle for its `SpecLevel` doesn't allow.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Violation {
+    Mode { mode: Mode },
+    PastTheEnd { address: i64, end: usize },
+    ValueOutOfRange { value: i64 },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Mode { mode } => write!(f, "it doesn't have {:?} mode", mode),
+            Violation::PastTheEnd { address, end } => write!(
+                f,
+                "memory at {} is past the end of the program at {}",
+                address, end
+            ),
+            Violation::ValueOutOfRange { value } => {
+                write!(f, "{} doesn't fit in 32 bits", value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Computer, IntcodeError};
+
+    fn run(level: SpecLevel, program: Vec<i64>) -> Result<Vec<i64>, IntcodeError> {
+        let mut output = vec![];
+        Computer::with_spec_level(program, level).run(&mut (vec![7].into_iter(), &mut output))?;
+        Ok(output)
+    }
+
+    #[test]
+    fn day_2_is_position_mode_only() {
+        let program = vec![1, 0, 0, 0, 99];
+        assert_eq!(run(SpecLevel::Day2, program), Ok(vec![]));
+
+        let program = vec![1002, 4, 3, 4, 33];
+        assert_eq!(
+            run(SpecLevel::Day2, program.clone()),
+            Err(IntcodeError::SpecViolation {
+                position: 0,
+                opcode: 1002,
+                parameter: 1,
+                level: SpecLevel::Day2,
+                violation: Violation::Mode {
+                    mode: Mode::Immediate
+                },
+            })
+        );
+        assert_eq!(run(SpecLevel::Day5, program), Ok(vec![]));
+    }
+
+    #[test]
+    fn day_5_has_no_relative_mode() {
+        let program = vec![3, 0, 4, 0, 99];
+        assert_eq!(run(SpecLevel::Day5, program), Ok(vec![7]));
+
+        let program = vec![203, 0, 204, 0, 99];
+        assert_eq!(
+            run(SpecLevel::Day5, program.clone()),
+            Err(IntcodeError::SpecViolation {
+                position: 0,
+                opcode: 203,
+                parameter: 0,
+                level: SpecLevel::Day5,
+                violation: Violation::Mode {
+                    mode: Mode::Relative
+                },
+            })
+        );
+        assert_eq!(run(SpecLevel::Day9, program), Ok(vec![7]));
+
+        assert_eq!(
+            run(SpecLevel::Day5, vec![109, 1, 99]),
+            Err(IntcodeError::UnknownOpcode {
+                position: 0,
+                opcode: 109,
+            })
+        );
+    }
+
+    #[test]
+    fn memory_ends_with_the_program_before_day_9() {
+        let program = vec![1101, 1, 2, 100, 4, 100, 99];
+
+        let error = run(SpecLevel::Day5, program.clone()).unwrap_err();
+        assert_eq!(
+            error,
+            IntcodeError::SpecViolation {
+                position: 0,
+                opcode: 1101,
+                parameter: 2,
+                level: SpecLevel::Day5,
+                violation: Violation::PastTheEnd {
+                    address: 100,
+                    end: 7
+                },
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Parameter 2 breaks the Day5 spec: memory at 100 is past the end of the program at 7 \
+             (instruction 1101 at position 0)"
+        );
+        assert_eq!(run(SpecLevel::Day9, program), Ok(vec![3]));
+    }
+
+    #[test]
+    fn values_are_32_bits_before_day_9() {
+        // Squares 65536, which is fine, and squares that, which isn't
+        let program = vec![1002, 9, 65536, 9, 1002, 9, 65536, 9, 99, 1];
+        assert_eq!(
+            run(SpecLevel::Day5, program.clone()),
+            Err(IntcodeError::SpecViolation {
+                position: 4,
+                opcode: 1002,
+                parameter: 2,
+                level: SpecLevel::Day5,
+                violation: Violation::ValueOutOfRange { value: 1 << 32 },
+            })
+        );
+        assert!(run(SpecLevel::Day9, program).is_ok());
+
+        // Reading a value that's already too big is just as wrong
+        assert_eq!(
+            run(SpecLevel::Day2, vec![1, 5, 5, 0, 99, 1 << 40]),
+            Err(IntcodeError::SpecViolation {
+                position: 0,
+                opcode: 1,
+                parameter: 0,
+                level: SpecLevel::Day2,
+                violation: Violation::ValueOutOfRange { value: 1 << 40 },
+            })
+        );
+    }
+}
@@ -0,0 +1,935 @@
+use crate::memory::DENSE_LIMIT;
+use crate::search::Linear;
+use crate::{instruction, IntcodeError, Mode, UnexpectedMode};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::ops::{Add, Mul};
+use std::rc::Rc;
+
+// A value in a program being run symbolically: a number, one of the cells or inputs being
+// treated as a variable, or what the program made of them. Math on numbers wraps, like
+// `Arithmetic::Wrapping`.
+//
+// Expressions share their parts, so a loop can build one that's small as a graph but enormous as
+// a tree. Anything that walks one remembers what it's already seen by address.
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Const(i64),
+    Var(Rc<str>),
+    Add(Rc<Expr>, Rc<Expr>),
+    Mul(Rc<Expr>, Rc<Expr>),
+    // 1 if the first is less than the second, otherwise 0
+    Lt(Rc<Expr>, Rc<Expr>),
+    // 1 if they're equal, otherwise 0
+    Eq(Rc<Expr>, Rc<Expr>),
+    // What was in memory at an address that depends on a variable, when it was read, after the
+    // path had written to memory this many times. Reads from the same address only have to be
+    // the same value if nothing was written in between.
+    Load(Rc<Expr>, usize),
+}
+
+impl Expr {
+    pub fn var(name: &str) -> Expr {
+        Expr::Var(Rc::from(name))
+    }
+
+    pub fn less_than(a: Expr, b: Expr) -> Expr {
+        match (&a, &b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(i64::from(a < b)),
+            _ if a == b => Expr::Const(0),
+            _ => Expr::Lt(Rc::new(a), Rc::new(b)),
+        }
+    }
+
+    pub fn equals(a: Expr, b: Expr) -> Expr {
+        match (&a, &b) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(i64::from(a == b)),
+            _ if a == b => Expr::Const(1),
+            _ => Expr::Eq(Rc::new(a), Rc::new(b)),
+        }
+    }
+
+    pub fn constant(&self) -> Option<i64> {
+        match *self {
+            Expr::Const(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    // What it comes to with each variable's value from `values`, or `None` if one's missing or
+    // it depends on memory.
+    pub fn evaluate(&self, values: &BTreeMap<&str, i64>) -> Option<i64> {
+        self.evaluate_with(values, &mut HashMap::new())
+    }
+
+    fn evaluate_with(
+        &self,
+        values: &BTreeMap<&str, i64>,
+        seen: &mut Seen<Option<i64>>,
+    ) -> Option<i64> {
+        let mut evaluate =
+            |expr: &Rc<Expr>| remember(expr, seen, |expr, seen| expr.evaluate_with(values, seen));
+        let value = match self {
+            Expr::Const(value) => *value,
+            Expr::Var(name) => *values.get(&**name)?,
+            Expr::Add(a, b) => evaluate(a)?.wrapping_add(evaluate(b)?),
+            Expr::Mul(a, b) => evaluate(a)?.wrapping_mul(evaluate(b)?),
+            Expr::Lt(a, b) => i64::from(evaluate(a)? < evaluate(b)?),
+            Expr::Eq(a, b) => i64::from(evaluate(a)? == evaluate(b)?),
+            Expr::Load(..) => return None,
+        };
+        Some(value)
+    }
+
+    // The sum of terms it comes to, if it's only adds and multiplies and it doesn't multiply out
+    // to more than `MAX_TERMS` terms or `MAX_DEGREE` variables in one term.
+    pub fn polynomial(&self) -> Option<Polynomial> {
+        self.polynomial_with(&mut HashMap::new())
+    }
+
+    fn polynomial_with(&self, seen: &mut Seen<Option<Polynomial>>) -> Option<Polynomial> {
+        let mut polynomial =
+            |expr: &Rc<Expr>| remember(expr, seen, |expr, seen| expr.polynomial_with(seen));
+        let mut terms = BTreeMap::new();
+
+        match self {
+            Expr::Const(value) => {
+                terms.insert(vec![], *value);
+            }
+            Expr::Var(name) => {
+                terms.insert(vec![name.to_string()], 1);
+            }
+            Expr::Add(a, b) => {
+                terms = polynomial(a)?.terms;
+                for (variables, coefficient) in polynomial(b)?.terms {
+                    let sum = terms.entry(variables).or_insert(0);
+                    *sum = sum.wrapping_add(coefficient);
+                }
+            }
+            Expr::Mul(a, b) => {
+                let b = polynomial(b)?;
+                for (a_variables, a_coefficient) in polynomial(a)?.terms {
+                    for (b_variables, b_coefficient) in &b.terms {
+                        if a_variables.len() + b_variables.len() > MAX_DEGREE {
+                            return None;
+                        }
+                        let mut variables = a_variables.clone();
+                        variables.extend(b_variables.iter().cloned());
+                        variables.sort();
+                        let sum = terms.entry(variables).or_insert(0);
+                        *sum = sum.wrapping_add(a_coefficient.wrapping_mul(*b_coefficient));
+                    }
+                }
+            }
+            Expr::Lt(..) | Expr::Eq(..) | Expr::Load(..) => return None,
+        }
+
+        terms.retain(|_, coefficient| *coefficient != 0);
+        if terms.len() > MAX_TERMS {
+            return None;
+        }
+        Some(Polynomial { terms })
+    }
+
+    // The constant and coefficients of `variables`, in that order, if it's linear in them and
+    // nothing else.
+    pub fn linear(&self, variables: &[&str]) -> Option<Linear> {
+        let polynomial = self.polynomial()?;
+        let mut linear = Linear {
+            constant: 0,
+            coefficients: vec![0; variables.len()],
+        };
+
+        for (term, &coefficient) in &polynomial.terms {
+            match &term[..] {
+                [] => linear.constant = coefficient,
+                [variable] => {
+                    let i = variables.iter().position(|name| name == variable)?;
+                    linear.coefficients[i] = coefficient;
+                }
+                _ => return None,
+            }
+        }
+        Some(linear)
+    }
+
+    // `seen` has the pairs of shared parts already found to be equal; finding any that aren't
+    // settles it, so those don't need remembering.
+    fn equals_with(&self, other: &Expr, seen: &mut HashSet<(*const Expr, *const Expr)>) -> bool {
+        let mut same = |a: &Rc<Expr>, b: &Rc<Expr>| {
+            let pair = (Rc::as_ptr(a), Rc::as_ptr(b));
+            if Rc::ptr_eq(a, b) || seen.contains(&pair) {
+                return true;
+            }
+            let same = a.equals_with(b, seen);
+            if same {
+                seen.insert(pair);
+            }
+            same
+        };
+        match (self, other) {
+            (Expr::Const(a), Expr::Const(b)) => a == b,
+            (Expr::Var(a), Expr::Var(b)) => a == b,
+            (Expr::Add(a1, b1), Expr::Add(a2, b2))
+            | (Expr::Mul(a1, b1), Expr::Mul(a2, b2))
+            | (Expr::Lt(a1, b1), Expr::Lt(a2, b2))
+            | (Expr::Eq(a1, b1), Expr::Eq(a2, b2)) => same(a1, a2) && same(b1, b2),
+            (Expr::Load(a, a_writes), Expr::Load(b, b_writes)) => {
+                a_writes == b_writes && same(a, b)
+            }
+            _ => false,
+        }
+    }
+}
+
+// How far `Expr::polynomial` multiplies things out. A loop that squares something can make a
+// polynomial that's far too big to work out, let alone show.
+pub const MAX_TERMS: usize = 64;
+pub const MAX_DEGREE: usize = 16;
+
+// What walking each shared part of an expression came to, by its address.
+type Seen<T> = HashMap<*const Expr, T>;
+
+fn remember<T: Clone>(
+    expr: &Rc<Expr>,
+    seen: &mut Seen<T>,
+    walk: impl FnOnce(&Expr, &mut Seen<T>) -> T,
+) -> T {
+    if let Some(result) = seen.get(&Rc::as_ptr(expr)) {
+        return result.clone();
+    }
+    let result = walk(expr, seen);
+    seen.insert(Rc::as_ptr(expr), result.clone());
+    result
+}
+
+impl PartialEq for Expr {
+    fn eq(&self, other: &Expr) -> bool {
+        self.equals_with(other, &mut HashSet::new())
+    }
+}
+
+impl Add for Expr {
+    type Output = Expr;
+
+    fn add(self, other: Expr) -> Expr {
+        match (&self, &other) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(a.wrapping_add(*b)),
+            (Expr::Const(0), _) => other,
+            (_, Expr::Const(0)) => self,
+            _ => Expr::Add(Rc::new(self), Rc::new(other)),
+        }
+    }
+}
+
+impl Mul for Expr {
+    type Output = Expr;
+
+    fn mul(self, other: Expr) -> Expr {
+        match (&self, &other) {
+            (Expr::Const(a), Expr::Const(b)) => Expr::Const(a.wrapping_mul(*b)),
+            (Expr::Const(0), _) | (_, Expr::Const(0)) => Expr::Const(0),
+            (Expr::Const(1), _) => other,
+            (_, Expr::Const(1)) => self,
+            _ => Expr::Mul(Rc::new(self), Rc::new(other)),
+        }
+    }
+}
+
+// Shows an expression as its polynomial if it has one, and otherwise as what it's made of. A part
+// that's used more than once is shown once and named, like `(t0)*(t0) where t0 = x*x + 1`, so
+// the text stays about as big as the expression really is.
+struct Printer {
+    polynomials: Seen<Option<Polynomial>>,
+    uses: HashMap<*const Expr, usize>,
+    names: HashMap<*const Expr, usize>,
+    definitions: Vec<String>,
+}
+
+impl Printer {
+    fn count_uses(&mut self, expr: &Expr) {
+        if expr.polynomial_with(&mut self.polynomials).is_some() {
+            return;
+        }
+        for part in expr.parts() {
+            let uses = self.uses.entry(Rc::as_ptr(part)).or_insert(0);
+            *uses += 1;
+            if *uses == 1 {
+                self.count_uses(part);
+            }
+        }
+    }
+
+    fn show(&mut self, expr: &Expr) -> String {
+        if let Some(polynomial) = expr.polynomial_with(&mut self.polynomials) {
+            return polynomial.to_string();
+        }
+
+        match expr {
+            Expr::Add(a, b) => format!("({} + {})", self.part(a), self.part(b)),
+            Expr::Mul(a, b) => format!("({})*({})", self.part(a), self.part(b)),
+            Expr::Lt(a, b) => format!("({} < {})", self.part(a), self.part(b)),
+            Expr::Eq(a, b) => format!("({} == {})", self.part(a), self.part(b)),
+            Expr::Load(address, _) => format!("[{}]", self.part(address)),
+            Expr::Const(_) | Expr::Var(_) => unreachable!("those are always polynomials"),
+        }
+    }
+
+    fn part(&mut self, part: &Rc<Expr>) -> String {
+        let shared = self
+            .uses
+            .get(&Rc::as_ptr(part))
+            .is_some_and(|&uses| uses > 1);
+        if !shared || matches!(**part, Expr::Const(_) | Expr::Var(_)) {
+            return self.show(part);
+        }
+
+        if let Some(name) = self.names.get(&Rc::as_ptr(part)) {
+            return format!("t{}", name);
+        }
+        let name = self.definitions.len();
+        self.names.insert(Rc::as_ptr(part), name);
+        self.definitions.push(String::new());
+        self.definitions[name] = self.show(part);
+        format!("t{}", name)
+    }
+}
+
+impl Expr {
+    fn parts(&self) -> Vec<&Rc<Expr>> {
+        match self {
+            Expr::Const(_) | Expr::Var(_) => vec![],
+            Expr::Add(a, b) | Expr::Mul(a, b) | Expr::Lt(a, b) | Expr::Eq(a, b) => vec![a, b],
+            Expr::Load(address, _) => vec![address],
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut printer = Printer {
+            polynomials: HashMap::new(),
+            uses: HashMap::new(),
+            names: HashMap::new(),
+            definitions: vec![],
+        };
+        printer.count_uses(self);
+        write!(f, "{}", printer.show(self))?;
+
+        for (name, definition) in printer.definitions.iter().enumerate() {
+            let separator = if name == 0 { " where " } else { ", " };
+            write!(f, "{}t{} = {}", separator, name, definition)?;
+        }
+        Ok(())
+    }
+}
+
+// Each product of variables, sorted, with its coefficient. The constant term has no variables.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polynomial {
+    pub terms: BTreeMap<Vec<String>, i64>,
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Highest degree first, so the constant comes last
+        let mut terms: Vec<_> = self.terms.iter().collect();
+        terms.sort_by_key(|(variables, _)| std::cmp::Reverse(variables.len()));
+
+        if terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        for (i, (variables, &coefficient)) in terms.into_iter().enumerate() {
+            let magnitude = coefficient.unsigned_abs();
+            match (i, coefficient < 0) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+
+            if variables.is_empty() {
+                write!(f, "{}", magnitude)?;
+            } else {
+                if magnitude != 1 {
+                    write!(f, "{}*", magnitude)?;
+                }
+                write!(f, "{}", variables.join("*"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Something a path assumed about an expression when it went one way at a jump.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Constraint {
+    pub expr: Expr,
+    pub nonzero: bool,
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let comparison = if self.nonzero { "!=" } else { "==" };
+        write!(f, "{} {} 0", self.expr, comparison)
+    }
+}
+
+// Why a path stopped.
+#[derive(Debug, PartialEq, Clone)]
+pub enum End {
+    Halted,
+    // An opcode, a parameter, an address, a jump target or a relative base adjustment was an
+    // expression, and there's no telling where to go from there
+    Symbolic { position: usize, value: Expr },
+    Error(IntcodeError),
+    StepLimit,
+}
+
+impl fmt::Display for End {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            End::Halted => write!(f, "halted"),
+            End::Symbolic { position, value } => {
+                write!(f, "stopped at {}, needing {} as a number", position, value)
+            }
+            End::Error(e) => write!(f, "{}", e),
+            End::StepLimit => write!(f, "ran out of steps"),
+        }
+    }
+}
+
+// One way through the program.
+#[derive(Debug, Clone)]
+pub struct Path {
+    pub constraints: Vec<Constraint>,
+    pub outputs: Vec<Expr>,
+    pub end: End,
+    // Where the instruction pointer was when the path stopped
+    pub position: usize,
+    memory: Cells,
+}
+
+impl Path {
+    pub fn read(&self, address: usize) -> Expr {
+        self.memory.get(address)
+    }
+}
+
+// A path's memory, split at `DENSE_LIMIT` the way `Memory` is, so one far away write doesn't make
+// a cell for every address before it.
+#[derive(Debug, Clone, Default)]
+struct Cells {
+    dense: Vec<Expr>,
+    sparse: HashMap<usize, Expr>,
+}
+
+impl Cells {
+    fn get(&self, address: usize) -> Expr {
+        match self.dense.get(address) {
+            Some(value) => value.clone(),
+            None if address < DENSE_LIMIT => Expr::Const(0),
+            None => self.sparse.get(&address).cloned().unwrap_or(Expr::Const(0)),
+        }
+    }
+
+    fn set(&mut self, address: usize, value: Expr) {
+        if address < self.dense.len() {
+            self.dense[address] = value;
+        } else if address < DENSE_LIMIT {
+            self.dense.resize(address + 1, Expr::Const(0));
+            self.dense[address] = value;
+        } else {
+            self.sparse.insert(address, value);
+        }
+    }
+}
+
+// Runs a program with some of its cells, and all of its input, as variables instead of numbers.
+// A jump that depends on a variable goes both ways, as separate paths.
+pub struct Symbolic {
+    program: Cells,
+    max_paths: usize,
+    max_steps: u64,
+}
+
+impl Symbolic {
+    pub fn new(program: &[i64]) -> Symbolic {
+        Symbolic {
+            program: Cells {
+                dense: program.iter().map(|&value| Expr::Const(value)).collect(),
+                sparse: HashMap::new(),
+            },
+            max_paths: 64,
+            max_steps: 1_000_000,
+        }
+    }
+
+    // Treats the value at `address` as the variable `name`.
+    pub fn variable(mut self, address: usize, name: &str) -> Symbolic {
+        self.program.set(address, Expr::var(name));
+        self
+    }
+
+    // Stops forking once there are this many paths; any more jumps on variables end the path
+    // with `End::Symbolic`.
+    pub fn max_paths(mut self, max_paths: usize) -> Symbolic {
+        self.max_paths = max_paths;
+        self
+    }
+
+    // How many instructions each path can run before it ends with `End::StepLimit`.
+    pub fn max_steps(mut self, max_steps: u64) -> Symbolic {
+        self.max_steps = max_steps;
+        self
+    }
+
+    // Every path through the program, in the order they ended.
+    pub fn run(&self) -> Vec<Path> {
+        let mut waiting = vec![State {
+            memory: self.program.clone(),
+            position: 0,
+            relative_base: 0,
+            constraints: vec![],
+            outputs: vec![],
+            inputs: 0,
+            steps: 0,
+            writes: 0,
+        }];
+        let mut started = 1;
+        let mut paths = vec![];
+
+        while let Some(mut state) = waiting.pop() {
+            let end = loop {
+                if state.steps >= self.max_steps {
+                    break End::StepLimit;
+                }
+                state.steps += 1;
+
+                match state.step() {
+                    Ok(Step::Running) => {}
+                    // Each way gets its own constraint, and runs the jump again knowing the answer
+                    Ok(Step::Fork(test)) if started < self.max_paths => {
+                        let mut other = state.clone();
+                        other.constraints.push(Constraint {
+                            expr: test.clone(),
+                            nonzero: false,
+                        });
+                        state.constraints.push(Constraint {
+                            expr: test,
+                            nonzero: true,
+                        });
+                        waiting.push(other);
+                        started += 1;
+                    }
+                    Ok(Step::Fork(test)) => {
+                        break End::Symbolic {
+                            position: state.position,
+                            value: test,
+                        }
+                    }
+                    Ok(Step::Halted) => break End::Halted,
+                    Err(end) => break end,
+                }
+            };
+
+            paths.push(Path {
+                constraints: state.constraints,
+                outputs: state.outputs,
+                end,
+                position: state.position,
+                memory: state.memory,
+            });
+        }
+
+        paths
+    }
+}
+
+#[derive(Clone)]
+struct State {
+    memory: Cells,
+    position: usize,
+    relative_base: usize,
+    constraints: Vec<Constraint>,
+    outputs: Vec<Expr>,
+    inputs: usize,
+    steps: u64,
+    writes: usize,
+}
+
+enum Step {
+    Running,
+    // A jump that depends on this expression
+    Fork(Expr),
+    Halted,
+}
+
+impl State {
+    fn get(&self, address: usize) -> Expr {
+        self.memory.get(address)
+    }
+
+    fn set(&mut self, address: usize, value: Expr) {
+        self.memory.set(address, value);
+        self.writes += 1;
+    }
+
+    // A value that has to be a number.
+    fn concrete(&self, value: Expr) -> Result<i64, End> {
+        value.constant().ok_or(End::Symbolic {
+            position: self.position,
+            value,
+        })
+    }
+
+    fn address(&self, parameter: usize, mode: Mode) -> Result<Option<usize>, End> {
+        let value = self.concrete(self.get(self.position + parameter + 1))?;
+        let address = match mode {
+            Mode::Position => value,
+            Mode::Immediate => return Ok(None),
+            Mode::Relative => self.relative_address(parameter, value)?,
+        };
+        self.check_address(parameter, address).map(Some)
+    }
+
+    fn relative_address(&self, parameter: usize, value: i64) -> Result<i64, End> {
+        match value.checked_add(self.relative_base as i64) {
+            Some(address) => Ok(address),
+            None => Err(self.address_overflow(parameter)),
+        }
+    }
+
+    fn address_overflow(&self, parameter: usize) -> End {
+        match self.concrete(self.get(self.position)) {
+            Ok(opcode) => End::Error(IntcodeError::AddressOverflow {
+                position: self.position,
+                opcode,
+                parameter,
+            }),
+            Err(end) => end,
+        }
+    }
+
+    fn check_address(&self, parameter: usize, address: i64) -> Result<usize, End> {
+        if address < 0 {
+            return Err(End::Error(IntcodeError::NegativeAddress {
+                position: self.position,
+                opcode: self.concrete(self.get(self.position))?,
+                parameter,
+                address,
+            }));
+        }
+        Ok(address as usize)
+    }
+
+    // Reading from an address that depends on a variable is fine, unlike writing there, since
+    // it doesn't change anything else.
+    fn read(&self, parameter: usize) -> Result<Expr, End> {
+        let value = self.get(self.position + parameter + 1);
+        let address = match self.mode(parameter)? {
+            Mode::Position => value,
+            Mode::Immediate => return Ok(value),
+            Mode::Relative => match value.constant() {
+                Some(value) => Expr::Const(self.relative_address(parameter, value)?),
+                None => value + Expr::Const(self.relative_base as i64),
+            },
+        };
+
+        match address.constant() {
+            Some(address) => Ok(self.get(self.check_address(parameter, address)?)),
+            None => Ok(Expr::Load(Rc::new(address), self.writes)),
+        }
+    }
+
+    fn write(&mut self, parameter: usize, value: Expr) -> Result<(), End> {
+        match self.address(parameter, self.mode(parameter)?)? {
+            Some(address) => {
+                self.set(address, value);
+                Ok(())
+            }
+            None => Err(End::Error(IntcodeError::WriteInImmediateMode {
+                position: self.position,
+                opcode: self.concrete(self.get(self.position))?,
+                parameter,
+            })),
+        }
+    }
+
+    fn mode(&self, parameter: usize) -> Result<Mode, End> {
+        let full_opcode = self.concrete(self.get(self.position))?;
+        instruction(full_opcode)
+            .map(|inst| inst.mode(parameter))
+            .map_err(|UnexpectedMode { parameter, mode }| {
+                End::Error(IntcodeError::UnexpectedMode {
+                    position: self.position,
+                    opcode: full_opcode,
+                    parameter,
+                    mode,
+                })
+            })
+    }
+
+    fn step(&mut self) -> Result<Step, End> {
+        let full_opcode = self.concrete(self.get(self.position))?;
+        let opcode = full_opcode % 100;
+        // Checks the modes
+        self.mode(0)?;
+
+        match opcode {
+            1 | 2 | 7 | 8 => {
+                let a = self.read(0)?;
+                let b = self.read(1)?;
+                let value = match opcode {
+                    1 => a + b,
+                    2 => a * b,
+                    7 => Expr::less_than(a, b),
+                    _ => Expr::equals(a, b),
+                };
+                self.write(2, value)?;
+                self.position += 4;
+            }
+            3 => {
+                let input = Expr::var(&format!("input{}", self.inputs));
+                self.inputs += 1;
+                self.write(0, input)?;
+                self.position += 2;
+            }
+            4 => {
+                let value = self.read(0)?;
+                self.outputs.push(value);
+                self.position += 2;
+            }
+            5 | 6 => {
+                let test = self.read(0)?;
+                let nonzero = match test.constant() {
+                    Some(value) => value != 0,
+                    None => {
+                        // Once a path has gone one way on this, it keeps going that way
+                        let known = self.constraints.iter().find(|c| c.expr == test);
+                        match known {
+                            Some(constraint) => constraint.nonzero,
+                            None => return Ok(Step::Fork(test)),
+                        }
+                    }
+                };
+                if nonzero == (opcode == 5) {
+                    let target = self.read(1)?;
+                    let target = self.concrete(target)?;
+                    if target < 0 {
+                        return Err(End::Error(IntcodeError::NegativeAddress {
+                            position: self.position,
+                            opcode: full_opcode,
+                            parameter: 1,
+                            address: target,
+                        }));
+                    }
+                    self.position = target as usize;
+                } else {
+                    self.position += 3;
+                }
+            }
+            9 => {
+                let adjustment = self.read(0)?;
+                let adjustment = self.concrete(adjustment)?;
+                self.relative_base = match (self.relative_base as i64).checked_add(adjustment) {
+                    Some(relative_base) => relative_base as usize,
+                    None => return Err(self.address_overflow(0)),
+                };
+                self.position += 2;
+            }
+            99 => return Ok(Step::Halted),
+            _ => {
+                return Err(End::Error(IntcodeError::UnknownOpcode {
+                    position: self.position,
+                    opcode: full_opcode,
+                }))
+            }
+        }
+
+        Ok(Step::Running)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_a_formula() {
+        // [0] = ([20] * 3 + [21]) * [20] + 7
+        let program = [
+            1002, 20, 3, 22, 1, 22, 21, 22, 2, 22, 20, 22, 1001, 22, 7, 0, 99, 0, 0, 0, 0, 0, 0,
+        ];
+        let paths = Symbolic::new(&program)
+            .variable(20, "x")
+            .variable(21, "y")
+            .run();
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].end, End::Halted);
+        assert_eq!(paths[0].read(0).to_string(), "3*x*x + x*y + 7");
+    }
+
+    #[test]
+    fn fork_on_jumps_that_depend_on_variables() {
+        // Outputs 1 if the input is less than 5, otherwise 0
+        let program = [
+            3, 20, 1007, 20, 5, 21, 1005, 21, 13, 104, 0, 99, 99, 104, 1, 99, 0, 0, 0, 0, 0, 0,
+        ];
+        let paths = Symbolic::new(&program).run();
+
+        let summary: Vec<(String, String)> = paths
+            .iter()
+            .map(|path| (path.constraints[0].to_string(), path.outputs[0].to_string()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (String::from("(input0 < 5) != 0"), String::from("1")),
+                (String::from("(input0 < 5) == 0"), String::from("0")),
+            ]
+        );
+    }
+
+    #[test]
+    fn stop_where_a_variable_is_needed_as_a_number() {
+        // Jumps to wherever the input says
+        let program = [3, 6, 105, 1, 6, 99, 0];
+        let paths = Symbolic::new(&program).run();
+
+        assert_eq!(
+            paths[0].end,
+            End::Symbolic {
+                position: 2,
+                value: Expr::var("input0"),
+            }
+        );
+    }
+
+    #[test]
+    fn loops_on_a_variable_stop_at_the_limits() {
+        // Counts the input down to 0
+        let program = [3, 10, 1001, 10, -1, 10, 1005, 10, 2, 99, 0];
+        let paths = Symbolic::new(&program).max_paths(4).run();
+
+        assert_eq!(paths.len(), 4);
+        assert!(paths
+            .iter()
+            .any(|path| matches!(path.end, End::Symbolic { .. })));
+    }
+
+    #[test]
+    fn expressions_built_in_a_loop_share_their_parts() {
+        // Doubles x 60 times, then jumps on it twice
+        let program = crate::assemble(
+            "
+            loop:   add [x], [x], [x]
+                    add [n], #-1, [n]
+                    jt [n], #loop
+                    out [x]
+                    jt [x], #again
+            again:  jt [x], #end
+            end:    hlt
+            n:      .data 60
+            x:      .data 0
+            ",
+        )
+        .unwrap();
+        let x = program.len() - 1;
+        let paths = Symbolic::new(&program).variable(x, "x").run();
+
+        // The second jump already knows which way the first one went
+        assert_eq!(paths.len(), 2);
+        let doubled = &paths[0].outputs[0];
+        assert_eq!(doubled.to_string(), "1152921504606846976*x");
+        assert_eq!(
+            doubled.evaluate(&[("x", 3)].iter().copied().collect()),
+            Some(3 << 60)
+        );
+        // Each path's copy is the same expression, which can't be less than itself
+        let other = paths[1].outputs[0].clone();
+        assert_eq!(Expr::less_than(doubled.clone(), other), Expr::Const(0));
+    }
+
+    #[test]
+    fn reads_either_side_of_a_write_can_differ() {
+        // Reads [input0] into 30, writes 7 to 40, then reads [input0] into 31 and outputs
+        // [30] < [31], which is 1 when the input is 40
+        let mut program = vec![
+            3, 7, 1001, 7, 0, 15, 1001, 0, 0, 30, 1101, 7, 0, 40, 1001, 0, 0, 31, 7, 30, 31, 32, 4,
+            32, 99,
+        ];
+        program.resize(41, 0);
+        let mut output = vec![];
+        crate::Computer::new(program.clone())
+            .run(&mut (vec![40].into_iter(), &mut output))
+            .unwrap();
+        assert_eq!(output, vec![1]);
+
+        let paths = Symbolic::new(&program).run();
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].end, End::Halted);
+        assert_eq!(paths[0].outputs[0].constant(), None);
+        assert_eq!(paths[0].outputs[0].to_string(), "([input0] < [input0])");
+    }
+
+    #[test]
+    fn expressions_too_big_to_multiply_out_are_shown_as_they_were_built() {
+        // Squares x 40 times
+        let program = crate::assemble(
+            "
+            loop:   mul [x], [x], [x]
+                    add [n], #-1, [n]
+                    jt [n], #loop
+                    out [x]
+                    hlt
+            n:      .data 40
+            x:      .data 0
+            ",
+        )
+        .unwrap();
+        let x = program.len() - 1;
+        let paths = Symbolic::new(&program).variable(x, "x").run();
+        let squared = &paths[0].outputs[0];
+
+        assert_eq!(squared.polynomial(), None);
+        let shown = squared.to_string();
+        assert!(shown.starts_with("((t0)*(t69))*((t0)*(t69)) where t0 = (t1)*(t68), "));
+        assert!(shown.len() < 10_000);
+
+        // Small enough ones are still multiplied out
+        let x = Expr::var("x") + Expr::Const(1);
+        assert_eq!((x.clone() * x).to_string(), "x*x + 2*x + 1");
+    }
+
+    #[test]
+    fn far_away_writes_dont_allocate_everything_before_them() {
+        let program = [109, 1_000_000_000_000, 21101, 1, 2, 0, 99];
+        let paths = Symbolic::new(&program).variable(1 << 40, "x").run();
+
+        assert_eq!(paths[0].end, End::Halted);
+        assert_eq!(paths[0].read(1_000_000_000_000), Expr::Const(3));
+        assert_eq!(paths[0].read(1 << 40), Expr::var("x"));
+    }
+
+    #[test]
+    fn relative_addresses_that_overflow_are_errors() {
+        for (program, position, opcode) in [
+            (vec![109, 1, 204, i64::MAX, 99], 2, 204),
+            (vec![109, i64::MAX, 109, 1, 99], 2, 109),
+        ] {
+            let paths = Symbolic::new(&program).run();
+            assert_eq!(
+                paths[0].end,
+                End::Error(IntcodeError::AddressOverflow {
+                    position,
+                    opcode,
+                    parameter: 0,
+                })
+            );
+        }
+    }
+}
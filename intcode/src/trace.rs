@@ -0,0 +1,294 @@
+use crate::{Instruction, Mode, State};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::{self, Write};
+
+// Everything about one instruction the `Computer` ran.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Event {
+    pub position: usize,
+    pub relative_base: usize,
+    pub instruction: Instruction,
+    // What the computer's opcode table calls the instruction, and how many parameters it takes
+    pub mnemonic: &'static str,
+    pub parameters: usize,
+    // The values the instruction read, after working out the parameter modes
+    pub values: Vec<i64>,
+    // The address the instruction wrote to and what it wrote there
+    pub write: Option<(usize, i64)>,
+    pub state: State,
+}
+
+// Gets told about every instruction `Computer::step_traced` and `Computer::run_traced` run.
+pub trait Tracer {
+    fn trace(&mut self, event: &Event);
+}
+
+impl<T: Tracer + ?Sized> Tracer for &mut T {
+    fn trace(&mut self, event: &Event) {
+        (**self).trace(event)
+    }
+}
+
+impl<A: Tracer, B: Tracer> Tracer for (A, B) {
+    fn trace(&mut self, event: &Event) {
+        self.0.trace(event);
+        self.1.trace(event);
+    }
+}
+
+impl Tracer for Vec<Event> {
+    fn trace(&mut self, event: &Event) {
+        self.push(event.clone())
+    }
+}
+
+// Writes one JSON object per instruction, one per line, so two traces can be diffed.
+pub struct JsonTracer<W: Write> {
+    writer: W,
+    steps: u64,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> JsonTracer<W> {
+    pub fn new(writer: W) -> JsonTracer<W> {
+        JsonTracer {
+            writer,
+            steps: 0,
+            error: None,
+        }
+    }
+
+    // Flushes the trace and returns the first error there was writing it, if there was one.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Tracer for JsonTracer<W> {
+    fn trace(&mut self, event: &Event) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = writeln!(self.writer, "{}", json(self.steps, event)) {
+            self.error = Some(e);
+        }
+        self.steps += 1;
+    }
+}
+
+fn json(step: u64, event: &Event) -> String {
+    let inst = &event.instruction;
+    let modes: Vec<String> = (0..event.parameters)
+        .map(|i| format!("\"{}\"", mode_name(inst.mode(i))))
+        .collect();
+    let values: Vec<String> = event.values.iter().map(i64::to_string).collect();
+    let write = match event.write {
+        Some((address, value)) => format!("{{\"address\":{},\"value\":{}}}", address, value),
+        None => String::from("null"),
+    };
+
+    format!(
+        "{{\"step\":{},\"ip\":{},\"rb\":{},\"opcode\":{},\"mnemonic\":{},\"modes\":[{}],\"values\":[{}],\"write\":{}}}",
+        step,
+        event.position,
+        event.relative_base,
+        inst.opcode,
+        // Added opcodes can be called anything, so this is the one part that needs escaping
+        serde_json::Value::from(event.mnemonic),
+        modes.join(","),
+        values.join(","),
+        write
+    )
+}
+
+fn mode_name(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Position => "position",
+        Mode::Immediate => "immediate",
+        Mode::Relative => "relative",
+    }
+}
+
+// Counts how many times each opcode and each address ran.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Profile {
+    pub steps: u64,
+    pub opcodes: BTreeMap<i64, u64>,
+    pub mnemonics: BTreeMap<i64, &'static str>,
+    pub addresses: HashMap<usize, u64>,
+}
+
+impl Profile {
+    pub fn new() -> Profile {
+        Profile::default()
+    }
+
+    // The `count` addresses that ran the most, most first.
+    pub fn hottest(&self, count: usize) -> Vec<(usize, u64)> {
+        let mut addresses: Vec<(usize, u64)> = self
+            .addresses
+            .iter()
+            .map(|(&address, &times)| (address, times))
+            .collect();
+        addresses.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        addresses.truncate(count);
+        addresses
+    }
+}
+
+impl Tracer for Profile {
+    fn trace(&mut self, event: &Event) {
+        self.steps += 1;
+        *self.opcodes.entry(event.instruction.opcode).or_insert(0) += 1;
+        self.mnemonics
+            .insert(event.instruction.opcode, event.mnemonic);
+        *self.addresses.entry(event.position).or_insert(0) += 1;
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} steps", self.steps)?;
+
+        writeln!(f, "by opcode:")?;
+        for (&opcode, &times) in &self.opcodes {
+            let name = self.mnemonics.get(&opcode).copied().unwrap_or("?");
+            writeln!(f, "{:>5} {:<4}{:>12}", opcode, name, times)?;
+        }
+
+        write!(f, "hottest addresses:")?;
+        for (address, times) in self.hottest(10) {
+            write!(f, "\n{:>5}{:>16}", address, times)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Computer;
+
+    // Counts down from the input to 1
+    fn countdown() -> Computer {
+        Computer::new(vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, -1])
+    }
+
+    #[test]
+    fn trace_every_instruction() {
+        let mut computer = countdown();
+        let mut events = vec![];
+
+        computer
+            .run_traced(&mut (vec![1].into_iter(), vec![]), &mut events)
+            .unwrap();
+
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| (e.position, e.instruction.opcode, e.values.clone(), e.write))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, 3, vec![], Some((12, 1))),
+                (2, 4, vec![1], None),
+                (4, 1, vec![1, -1], Some((12, 0))),
+                (8, 5, vec![0, 2], None),
+                (11, 99, vec![], None),
+            ]
+        );
+        assert_eq!(events[1].state, State::Output(1));
+        assert_eq!(events[4].state, State::Halted);
+    }
+
+    #[test]
+    fn write_a_json_lines_trace() {
+        let mut computer = countdown();
+        let mut tracer = JsonTracer::new(vec![]);
+
+        computer
+            .run_traced(&mut (vec![1].into_iter(), vec![]), &mut tracer)
+            .unwrap();
+
+        let trace = String::from_utf8(tracer.finish().unwrap()).unwrap();
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[2],
+            "{\"step\":2,\"ip\":4,\"rb\":0,\"opcode\":1,\"mnemonic\":\"add\",\
+             \"modes\":[\"position\",\"immediate\",\"position\"],\"values\":[1,-1],\
+             \"write\":{\"address\":12,\"value\":0}}"
+        );
+        assert!(
+            lines[4].ends_with("\"mnemonic\":\"hlt\",\"modes\":[],\"values\":[],\"write\":null}")
+        );
+    }
+
+    #[test]
+    fn profile_counts_opcodes_and_addresses() {
+        let mut computer = countdown();
+        let mut profile = Profile::new();
+
+        computer
+            .run_traced(&mut (vec![3].into_iter(), vec![]), &mut profile)
+            .unwrap();
+
+        assert_eq!(profile.steps, 11);
+        assert_eq!(
+            profile
+                .opcodes
+                .iter()
+                .map(|(&k, &v)| (k, v))
+                .collect::<Vec<_>>(),
+            vec![(1, 3), (3, 1), (4, 3), (5, 3), (99, 1)]
+        );
+        assert_eq!(profile.hottest(2), vec![(2, 3), (4, 3)]);
+    }
+
+    #[test]
+    fn added_opcodes_are_traced_by_name() {
+        let mut opcodes = crate::Opcodes::standard();
+        opcodes.insert(
+            42,
+            crate::Opcode::new("nop", 2, None, |_| Ok(State::Running)),
+        );
+        let mut computer = Computer::new(vec![1142, 7, 8, 99]);
+        computer.set_opcodes(opcodes);
+        let mut tracer = (JsonTracer::new(vec![]), Profile::new());
+
+        computer
+            .run_traced(&mut (vec![].into_iter(), vec![]), &mut tracer)
+            .unwrap();
+
+        let trace = String::from_utf8(tracer.0.finish().unwrap()).unwrap();
+        assert!(trace.lines().next().unwrap().contains(
+            "\"opcode\":42,\"mnemonic\":\"nop\",\"modes\":[\"immediate\",\"immediate\"],\"values\":[7,8]"
+        ));
+        assert!(tracer.1.to_string().contains("   42 nop            1"));
+    }
+
+    #[test]
+    fn mnemonics_are_escaped() {
+        let mut opcodes = crate::Opcodes::standard();
+        opcodes.insert(
+            42,
+            crate::Opcode::new("say \"hi\"\\", 0, None, |_| Ok(State::Running)),
+        );
+        let mut computer = Computer::new(vec![42, 99]);
+        computer.set_opcodes(opcodes);
+        let mut tracer = JsonTracer::new(vec![]);
+
+        computer
+            .run_traced(&mut (vec![].into_iter(), vec![]), &mut tracer)
+            .unwrap();
+
+        let trace = String::from_utf8(tracer.finish().unwrap()).unwrap();
+        let line: serde_json::Value = serde_json::from_str(trace.lines().next().unwrap()).unwrap();
+        assert_eq!(line["mnemonic"], "say \"hi\"\\");
+    }
+}
@@ -0,0 +1,177 @@
+// The files in `compiled` are made with `intcode compile`; each one has to do exactly what the
+// interpreter does with the same program.
+use intcode::{compile, parse_program, Computer, Io, Limits, SpecLevel};
+use std::fs;
+
+mod day_02 {
+    include!("compiled/day_02.rs");
+}
+mod day_05 {
+    include!("compiled/day_05.rs");
+}
+mod day_09 {
+    include!("compiled/day_09.rs");
+}
+mod day_13 {
+    include!("compiled/day_13.rs");
+}
+mod self_modifying {
+    include!("compiled/self_modifying.rs");
+}
+
+const SELF_MODIFYING: &str = "104,0,1001,1,1,1,1007,1,3,15,1005,15,0,99,0,0";
+
+fn program(day: &str) -> Vec<i64> {
+    parse_program(&fs::read_to_string(format!("../aoc-{}-02/input", day)).unwrap()).unwrap()
+}
+
+#[test]
+fn compiled_programs_are_up_to_date() {
+    let compiled = [
+        (program("02"), "day_02"),
+        (program("05"), "day_05"),
+        (program("09"), "day_09"),
+        (program("13"), "day_13"),
+        (parse_program(SELF_MODIFYING).unwrap(), "self_modifying"),
+    ];
+
+    for (program, name) in &compiled {
+        let file = fs::read_to_string(format!("tests/compiled/{}.rs", name)).unwrap();
+        assert!(
+            compile(program) == file,
+            "tests/compiled/{}.rs is out of date; compile its program again",
+            name
+        );
+    }
+}
+
+#[test]
+fn day_2_noun_and_verb_overwrite_the_first_instruction() {
+    for (noun, verb) in [(12, 2), (0, 0), (64, 21), (99, 99)].iter().copied() {
+        let mut interpreted = Computer::new(program("02"));
+        interpreted.write_at(1, noun);
+        interpreted.write_at(2, verb);
+        let mut compiled = interpreted.clone();
+
+        interpreted.run(&mut (vec![].into_iter(), vec![])).unwrap();
+        day_02::run(&mut compiled, &mut (vec![].into_iter(), vec![])).unwrap();
+
+        assert_eq!(compiled.read_at(0), interpreted.read_at(0));
+    }
+}
+
+fn outputs(
+    program: Vec<i64>,
+    input: Vec<i64>,
+    run: impl Fn(&mut Computer, &mut (std::vec::IntoIter<i64>, &mut Vec<i64>)),
+) -> (Vec<i64>, Vec<i64>) {
+    let mut interpreted = vec![];
+    Computer::new(program.clone())
+        .run(&mut (input.clone().into_iter(), &mut interpreted))
+        .unwrap();
+
+    let mut compiled = vec![];
+    run(
+        &mut Computer::new(program),
+        &mut (input.into_iter(), &mut compiled),
+    );
+
+    (compiled, interpreted)
+}
+
+#[test]
+fn day_5_diagnostics() {
+    for &system in &[1, 5] {
+        let (compiled, interpreted) = outputs(program("05"), vec![system], |computer, io| {
+            day_05::run(computer, io).unwrap()
+        });
+        assert_eq!(compiled, interpreted);
+    }
+}
+
+#[test]
+fn day_9_boost() {
+    for &mode in &[1, 2] {
+        let (compiled, interpreted) = outputs(program("09"), vec![mode], |computer, io| {
+            day_09::run(computer, io).unwrap()
+        });
+        assert_eq!(compiled, interpreted);
+    }
+}
+
+#[test]
+fn computers_that_arent_standard_are_interpreted() {
+    let mut restricted = Computer::with_spec_level(program("09"), SpecLevel::Day5);
+    assert_eq!(
+        day_09::run(&mut restricted.clone(), &mut (vec![1].into_iter(), vec![])),
+        restricted.run(&mut (vec![1].into_iter(), vec![]))
+    );
+
+    let mut limited = Computer::new(program("09"));
+    limited.set_limits(Limits {
+        max_steps: Some(100),
+        ..Limits::default()
+    });
+    assert!(!limited.is_standard());
+    assert!(day_09::run(&mut limited, &mut (vec![2].into_iter(), vec![])).is_err());
+}
+
+#[test]
+fn self_modifying_code_falls_back_to_the_interpreter() {
+    let (compiled, interpreted) = outputs(
+        parse_program(SELF_MODIFYING).unwrap(),
+        vec![],
+        |computer, io| self_modifying::run(computer, io).unwrap(),
+    );
+    assert_eq!(interpreted, vec![0, 1, 2]);
+    assert_eq!(compiled, interpreted);
+}
+
+// Follows the ball with the paddle, and remembers everything drawn.
+struct Breakout {
+    output: Vec<i64>,
+    ball_x: i64,
+    paddle_x: i64,
+}
+
+impl Io for Breakout {
+    fn input(&mut self) -> Option<i64> {
+        Some((self.ball_x - self.paddle_x).signum())
+    }
+
+    fn output(&mut self, value: i64) {
+        self.output.push(value);
+
+        if let [.., x, _, tile] = self.output[..] {
+            if self.output.len().is_multiple_of(3) {
+                match tile {
+                    3 => self.paddle_x = x,
+                    4 => self.ball_x = x,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn day_13_breakout() {
+    let play = |run: fn(&mut Computer, &mut Breakout) -> Result<(), _>| {
+        let mut game = Breakout {
+            output: vec![],
+            ball_x: 0,
+            paddle_x: 0,
+        };
+        let mut computer = Computer::new(program("13"));
+        computer.write_at(0, 2);
+
+        run(&mut computer, &mut game).unwrap();
+        game.output
+    };
+
+    let interpreted = play(|computer, game| computer.run(game));
+    let compiled = play(day_13::run);
+
+    assert!(interpreted.len() > 3 * 1000);
+    assert_eq!(compiled, interpreted);
+}
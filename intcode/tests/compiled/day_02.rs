@@ -0,0 +1,723 @@
+// Compiled from a 157-value Intcode program by `intcode compile`. Don't edit it by hand;
+// compile the program again instead.
+//
+// Each instruction that was found in the program is Rust code here. Any that weren't, or that the
+// program has written over since, are run by the interpreter. So is any add or multiply that
+// overflows, and everything once memory has a value too big for an i64, so the computer's
+// arithmetic decides what happens. Nothing is recorded in the computer's history. A computer that
+// isn't `Computer::is_standard`, with opcodes of its own, an earlier spec level or limits, is run
+// by the interpreter the whole way.
+
+use intcode::{Computer, IntcodeError, Io, State};
+
+const PROGRAM: &[i64] = &[
+    1, 12, 2, 3, 1, 1, 2, 3, 1, 3, 4, 3, 1, 5, 0, 3, 2, 6, 1, 19, 1, 19, 9, 23, 1, 23, 9, 27, 1, 10,
+    27, 31, 1, 13, 31, 35, 1, 35, 10, 39, 2, 39, 9, 43, 1, 43, 13, 47, 1, 5, 47, 51, 1, 6, 51, 55,
+    1, 13, 55, 59, 1, 59, 6, 63, 1, 63, 10, 67, 2, 67, 6, 71, 1, 71, 5, 75, 2, 75, 10, 79, 1, 79, 6,
+    83, 1, 83, 5, 87, 1, 87, 6, 91, 1, 91, 13, 95, 1, 95, 6, 99, 2, 99, 10, 103, 1, 103, 6, 107, 2,
+    6, 107, 111, 1, 13, 111, 115, 2, 115, 10, 119, 1, 119, 5, 123, 2, 10, 123, 127, 2, 127, 9, 131,
+    1, 5, 131, 135, 2, 10, 135, 139, 2, 139, 9, 143, 1, 143, 2, 147, 1, 5, 147, 0, 99, 2, 0, 14, 0,
+];
+
+// Where each compiled instruction starts and how many values it takes up
+const INSTRUCTIONS: &[(usize, usize)] = &[
+    (0, 4), (4, 4), (8, 4), (12, 4), (16, 4), (20, 4), (24, 4), (28, 4), (32, 4), (36, 4), (40, 4),
+    (44, 4), (48, 4), (52, 4), (56, 4), (60, 4), (64, 4), (68, 4), (72, 4), (76, 4), (80, 4),
+    (84, 4), (88, 4), (92, 4), (96, 4), (100, 4), (104, 4), (108, 4), (112, 4), (116, 4), (120, 4),
+    (124, 4), (128, 4), (132, 4), (136, 4), (140, 4), (144, 4), (148, 4), (152, 1), (153, 4),
+];
+
+pub fn run(computer: &mut Computer, io: &mut impl Io) -> Result<(), IntcodeError> {
+    if run_until_input(computer, io)? == State::NeedsInput {
+        return Err(IntcodeError::MissingInput {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+        });
+    }
+    Ok(())
+}
+
+pub fn run_until_input(computer: &mut Computer, io: &mut impl Io) -> Result<State, IntcodeError> {
+    if !computer.is_standard() {
+        return computer.run_until_input(io);
+    }
+    let mut code = Code::new(computer);
+
+    'dispatch: loop {
+        'compiled: {
+            if computer.has_big_values() {
+                break 'compiled;
+            }
+            match computer.current_position {
+                0 => {
+                    // 0: add [12], [2], [3]
+                    if !code.valid[0] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(12);
+                    let b = computer.read_at(2);
+                    let destination = 3;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 4;
+                    continue 'dispatch;
+                }
+                4 => {
+                    // 4: add [1], [2], [3]
+                    if !code.valid[1] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(2);
+                    let destination = 3;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 8: add [3], [4], [3]
+                    computer.current_position = 8;
+                    if !code.valid[2] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(3);
+                    let b = computer.read_at(4);
+                    let destination = 3;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 12: add [5], [0], [3]
+                    computer.current_position = 12;
+                    if !code.valid[3] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(5);
+                    let b = computer.read_at(0);
+                    let destination = 3;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 16: mul [6], [1], [19]
+                    computer.current_position = 16;
+                    if !code.valid[4] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(6);
+                    let b = computer.read_at(1);
+                    let destination = 19;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 20;
+                    continue 'dispatch;
+                }
+                20 => {
+                    // 20: add [19], [9], [23]
+                    if !code.valid[5] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(19);
+                    let b = computer.read_at(9);
+                    let destination = 23;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 24;
+                    continue 'dispatch;
+                }
+                24 => {
+                    // 24: add [23], [9], [27]
+                    if !code.valid[6] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(23);
+                    let b = computer.read_at(9);
+                    let destination = 27;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 28;
+                    continue 'dispatch;
+                }
+                28 => {
+                    // 28: add [10], [27], [31]
+                    if !code.valid[7] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(10);
+                    let b = computer.read_at(27);
+                    let destination = 31;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 32;
+                    continue 'dispatch;
+                }
+                32 => {
+                    // 32: add [13], [31], [35]
+                    if !code.valid[8] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(13);
+                    let b = computer.read_at(31);
+                    let destination = 35;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 36;
+                    continue 'dispatch;
+                }
+                36 => {
+                    // 36: add [35], [10], [39]
+                    if !code.valid[9] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(35);
+                    let b = computer.read_at(10);
+                    let destination = 39;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 40;
+                    continue 'dispatch;
+                }
+                40 => {
+                    // 40: mul [39], [9], [43]
+                    if !code.valid[10] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(39);
+                    let b = computer.read_at(9);
+                    let destination = 43;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 44;
+                    continue 'dispatch;
+                }
+                44 => {
+                    // 44: add [43], [13], [47]
+                    if !code.valid[11] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(43);
+                    let b = computer.read_at(13);
+                    let destination = 47;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 48;
+                    continue 'dispatch;
+                }
+                48 => {
+                    // 48: add [5], [47], [51]
+                    if !code.valid[12] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(5);
+                    let b = computer.read_at(47);
+                    let destination = 51;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 52;
+                    continue 'dispatch;
+                }
+                52 => {
+                    // 52: add [6], [51], [55]
+                    if !code.valid[13] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(6);
+                    let b = computer.read_at(51);
+                    let destination = 55;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 56;
+                    continue 'dispatch;
+                }
+                56 => {
+                    // 56: add [13], [55], [59]
+                    if !code.valid[14] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(13);
+                    let b = computer.read_at(55);
+                    let destination = 59;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 60;
+                    continue 'dispatch;
+                }
+                60 => {
+                    // 60: add [59], [6], [63]
+                    if !code.valid[15] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(59);
+                    let b = computer.read_at(6);
+                    let destination = 63;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 64;
+                    continue 'dispatch;
+                }
+                64 => {
+                    // 64: add [63], [10], [67]
+                    if !code.valid[16] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = computer.read_at(10);
+                    let destination = 67;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 68;
+                    continue 'dispatch;
+                }
+                68 => {
+                    // 68: mul [67], [6], [71]
+                    if !code.valid[17] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(67);
+                    let b = computer.read_at(6);
+                    let destination = 71;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 72;
+                    continue 'dispatch;
+                }
+                72 => {
+                    // 72: add [71], [5], [75]
+                    if !code.valid[18] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(71);
+                    let b = computer.read_at(5);
+                    let destination = 75;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 76;
+                    continue 'dispatch;
+                }
+                76 => {
+                    // 76: mul [75], [10], [79]
+                    if !code.valid[19] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(75);
+                    let b = computer.read_at(10);
+                    let destination = 79;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 80;
+                    continue 'dispatch;
+                }
+                80 => {
+                    // 80: add [79], [6], [83]
+                    if !code.valid[20] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(79);
+                    let b = computer.read_at(6);
+                    let destination = 83;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 84;
+                    continue 'dispatch;
+                }
+                84 => {
+                    // 84: add [83], [5], [87]
+                    if !code.valid[21] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(83);
+                    let b = computer.read_at(5);
+                    let destination = 87;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 88;
+                    continue 'dispatch;
+                }
+                88 => {
+                    // 88: add [87], [6], [91]
+                    if !code.valid[22] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(87);
+                    let b = computer.read_at(6);
+                    let destination = 91;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 92;
+                    continue 'dispatch;
+                }
+                92 => {
+                    // 92: add [91], [13], [95]
+                    if !code.valid[23] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(91);
+                    let b = computer.read_at(13);
+                    let destination = 95;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 96;
+                    continue 'dispatch;
+                }
+                96 => {
+                    // 96: add [95], [6], [99]
+                    if !code.valid[24] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(95);
+                    let b = computer.read_at(6);
+                    let destination = 99;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 100;
+                    continue 'dispatch;
+                }
+                100 => {
+                    // 100: mul [99], [10], [103]
+                    if !code.valid[25] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(99);
+                    let b = computer.read_at(10);
+                    let destination = 103;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 104;
+                    continue 'dispatch;
+                }
+                104 => {
+                    // 104: add [103], [6], [107]
+                    if !code.valid[26] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(103);
+                    let b = computer.read_at(6);
+                    let destination = 107;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 108;
+                    continue 'dispatch;
+                }
+                108 => {
+                    // 108: mul [6], [107], [111]
+                    if !code.valid[27] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(6);
+                    let b = computer.read_at(107);
+                    let destination = 111;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 112;
+                    continue 'dispatch;
+                }
+                112 => {
+                    // 112: add [13], [111], [115]
+                    if !code.valid[28] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(13);
+                    let b = computer.read_at(111);
+                    let destination = 115;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 116;
+                    continue 'dispatch;
+                }
+                116 => {
+                    // 116: mul [115], [10], [119]
+                    if !code.valid[29] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(115);
+                    let b = computer.read_at(10);
+                    let destination = 119;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 120;
+                    continue 'dispatch;
+                }
+                120 => {
+                    // 120: add [119], [5], [123]
+                    if !code.valid[30] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(119);
+                    let b = computer.read_at(5);
+                    let destination = 123;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 124;
+                    continue 'dispatch;
+                }
+                124 => {
+                    // 124: mul [10], [123], [127]
+                    if !code.valid[31] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(10);
+                    let b = computer.read_at(123);
+                    let destination = 127;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 128;
+                    continue 'dispatch;
+                }
+                128 => {
+                    // 128: mul [127], [9], [131]
+                    if !code.valid[32] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(127);
+                    let b = computer.read_at(9);
+                    let destination = 131;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 132;
+                    continue 'dispatch;
+                }
+                132 => {
+                    // 132: add [5], [131], [135]
+                    if !code.valid[33] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(5);
+                    let b = computer.read_at(131);
+                    let destination = 135;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 136;
+                    continue 'dispatch;
+                }
+                136 => {
+                    // 136: mul [10], [135], [139]
+                    if !code.valid[34] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(10);
+                    let b = computer.read_at(135);
+                    let destination = 139;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 140;
+                    continue 'dispatch;
+                }
+                140 => {
+                    // 140: mul [139], [9], [143]
+                    if !code.valid[35] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(139);
+                    let b = computer.read_at(9);
+                    let destination = 143;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 144;
+                    continue 'dispatch;
+                }
+                144 => {
+                    // 144: add [143], [2], [147]
+                    if !code.valid[36] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(143);
+                    let b = computer.read_at(2);
+                    let destination = 147;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 148;
+                    continue 'dispatch;
+                }
+                148 => {
+                    // 148: add [5], [147], [0]
+                    if !code.valid[37] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(5);
+                    let b = computer.read_at(147);
+                    let destination = 0;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 152: hlt
+                    computer.current_position = 152;
+                    if !code.valid[38] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                153 => {
+                    // 153: mul [0], [14], [0]
+                    if !code.valid[39] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(0);
+                    let b = computer.read_at(14);
+                    let destination = 0;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 157;
+                    continue 'dispatch;
+                }
+                _ => break 'compiled,
+            }
+        }
+
+        let state = computer.step(io)?;
+        if let Some(address) = computer.last_write() {
+            code.written(computer, address);
+        }
+        match state {
+            State::Running | State::Output(_) => {}
+            other => return Ok(other),
+        }
+    }
+}
+
+// Which compiled instructions are still what's in memory
+struct Code {
+    owners: Vec<Option<usize>>,
+    valid: Vec<bool>,
+}
+
+impl Code {
+    fn new(computer: &Computer) -> Code {
+        let mut owners = vec![None; PROGRAM.len()];
+        for (i, &(start, length)) in INSTRUCTIONS.iter().enumerate() {
+            for owner in &mut owners[start..start + length] {
+                *owner = Some(i);
+            }
+        }
+
+        let valid = INSTRUCTIONS
+            .iter()
+            .map(|&instruction| unchanged(computer, instruction))
+            .collect();
+
+        Code { owners, valid }
+    }
+
+    fn written(&mut self, computer: &Computer, address: usize) {
+        if let Some(&Some(i)) = self.owners.get(address) {
+            self.valid[i] = unchanged(computer, INSTRUCTIONS[i]);
+        }
+    }
+}
+
+fn unchanged(computer: &Computer, (start, length): (usize, usize)) -> bool {
+    (start..start + length).all(|address| computer.read_at(address) == PROGRAM[address])
+}
+
+fn write(computer: &mut Computer, code: &mut Code, address: usize, value: i64) {
+    computer.write_at(address, value);
+    code.written(computer, address);
+}
@@ -0,0 +1,157 @@
+// Compiled from a 678-value Intcode program by `intcode compile`. Don't edit it by hand;
+// compile the program again instead.
+//
+// Each instruction that was found in the program is Rust code here. Any that weren't, or that the
+// program has written over since, are run by the interpreter. So is any add or multiply that
+// overflows, and everything once memory has a value too big for an i64, so the computer's
+// arithmetic decides what happens. Nothing is recorded in the computer's history. A computer that
+// isn't `Computer::is_standard`, with opcodes of its own, an earlier spec level or limits, is run
+// by the interpreter the whole way.
+
+use intcode::{Computer, IntcodeError, Io, State};
+
+const PROGRAM: &[i64] = &[
+    3, 225, 1, 225, 6, 6, 1100, 1, 238, 225, 104, 0, 1102, 46, 47, 225, 2, 122, 130, 224, 101,
+    -1998, 224, 224, 4, 224, 1002, 223, 8, 223, 1001, 224, 6, 224, 1, 224, 223, 223, 1102, 61, 51,
+    225, 102, 32, 92, 224, 101, -800, 224, 224, 4, 224, 1002, 223, 8, 223, 1001, 224, 1, 224, 1,
+    223, 224, 223, 1101, 61, 64, 225, 1001, 118, 25, 224, 101, -106, 224, 224, 4, 224, 1002, 223, 8,
+    223, 101, 1, 224, 224, 1, 224, 223, 223, 1102, 33, 25, 225, 1102, 73, 67, 224, 101, -4891, 224,
+    224, 4, 224, 1002, 223, 8, 223, 1001, 224, 4, 224, 1, 224, 223, 223, 1101, 14, 81, 225, 1102,
+    17, 74, 225, 1102, 52, 67, 225, 1101, 94, 27, 225, 101, 71, 39, 224, 101, -132, 224, 224, 4,
+    224, 1002, 223, 8, 223, 101, 5, 224, 224, 1, 224, 223, 223, 1002, 14, 38, 224, 101, -1786, 224,
+    224, 4, 224, 102, 8, 223, 223, 1001, 224, 2, 224, 1, 223, 224, 223, 1, 65, 126, 224, 1001, 224,
+    -128, 224, 4, 224, 1002, 223, 8, 223, 101, 6, 224, 224, 1, 224, 223, 223, 1101, 81, 40, 224,
+    1001, 224, -121, 224, 4, 224, 102, 8, 223, 223, 101, 4, 224, 224, 1, 223, 224, 223, 4, 223, 99,
+    0, 0, 0, 677, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1105, 0, 99999, 1105, 227, 247, 1105, 1, 99999,
+    1005, 227, 99999, 1005, 0, 256, 1105, 1, 99999, 1106, 227, 99999, 1106, 0, 265, 1105, 1, 99999,
+    1006, 0, 99999, 1006, 227, 274, 1105, 1, 99999, 1105, 1, 280, 1105, 1, 99999, 1, 225, 225, 225,
+    1101, 294, 0, 0, 105, 1, 0, 1105, 1, 99999, 1106, 0, 300, 1105, 1, 99999, 1, 225, 225, 225,
+    1101, 314, 0, 0, 106, 0, 0, 1105, 1, 99999, 1008, 677, 226, 224, 1002, 223, 2, 223, 1005, 224,
+    329, 1001, 223, 1, 223, 107, 677, 677, 224, 102, 2, 223, 223, 1005, 224, 344, 101, 1, 223, 223,
+    1107, 677, 677, 224, 102, 2, 223, 223, 1005, 224, 359, 1001, 223, 1, 223, 1108, 226, 226, 224,
+    1002, 223, 2, 223, 1006, 224, 374, 101, 1, 223, 223, 107, 226, 226, 224, 1002, 223, 2, 223,
+    1005, 224, 389, 1001, 223, 1, 223, 108, 226, 226, 224, 1002, 223, 2, 223, 1005, 224, 404, 1001,
+    223, 1, 223, 1008, 677, 677, 224, 1002, 223, 2, 223, 1006, 224, 419, 1001, 223, 1, 223, 1107,
+    677, 226, 224, 102, 2, 223, 223, 1005, 224, 434, 1001, 223, 1, 223, 108, 226, 677, 224, 102, 2,
+    223, 223, 1006, 224, 449, 1001, 223, 1, 223, 8, 677, 226, 224, 102, 2, 223, 223, 1006, 224, 464,
+    1001, 223, 1, 223, 1007, 677, 226, 224, 1002, 223, 2, 223, 1006, 224, 479, 1001, 223, 1, 223,
+    1007, 677, 677, 224, 1002, 223, 2, 223, 1005, 224, 494, 1001, 223, 1, 223, 1107, 226, 677, 224,
+    1002, 223, 2, 223, 1006, 224, 509, 101, 1, 223, 223, 1108, 226, 677, 224, 102, 2, 223, 223,
+    1005, 224, 524, 1001, 223, 1, 223, 7, 226, 226, 224, 102, 2, 223, 223, 1005, 224, 539, 1001,
+    223, 1, 223, 8, 677, 677, 224, 1002, 223, 2, 223, 1005, 224, 554, 101, 1, 223, 223, 107, 677,
+    226, 224, 102, 2, 223, 223, 1006, 224, 569, 1001, 223, 1, 223, 7, 226, 677, 224, 1002, 223, 2,
+    223, 1005, 224, 584, 1001, 223, 1, 223, 1008, 226, 226, 224, 1002, 223, 2, 223, 1006, 224, 599,
+    101, 1, 223, 223, 1108, 677, 226, 224, 102, 2, 223, 223, 1006, 224, 614, 101, 1, 223, 223, 7,
+    677, 226, 224, 102, 2, 223, 223, 1005, 224, 629, 1001, 223, 1, 223, 8, 226, 677, 224, 1002, 223,
+    2, 223, 1006, 224, 644, 101, 1, 223, 223, 1007, 226, 226, 224, 102, 2, 223, 223, 1005, 224, 659,
+    101, 1, 223, 223, 108, 677, 677, 224, 1002, 223, 2, 223, 1006, 224, 674, 1001, 223, 1, 223, 4,
+    223, 99, 226,
+];
+
+// Where each compiled instruction starts and how many values it takes up
+const INSTRUCTIONS: &[(usize, usize)] = &[
+    (0, 2), (2, 4),
+];
+
+pub fn run(computer: &mut Computer, io: &mut impl Io) -> Result<(), IntcodeError> {
+    if run_until_input(computer, io)? == State::NeedsInput {
+        return Err(IntcodeError::MissingInput {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+        });
+    }
+    Ok(())
+}
+
+pub fn run_until_input(computer: &mut Computer, io: &mut impl Io) -> Result<State, IntcodeError> {
+    if !computer.is_standard() {
+        return computer.run_until_input(io);
+    }
+    let mut code = Code::new(computer);
+
+    'dispatch: loop {
+        'compiled: {
+            if computer.has_big_values() {
+                break 'compiled;
+            }
+            match computer.current_position {
+                0 => {
+                    // 0: in [225]
+                    if !code.valid[0] {
+                        break 'compiled;
+                    }
+                    let value = match io.input() {
+                        Some(value) => value,
+                        None => return Ok(State::NeedsInput),
+                    };
+                    let destination = 225;
+                    write(computer, &mut code, destination, value);
+                    // 2: add [225], [6], [6]
+                    computer.current_position = 2;
+                    if !code.valid[1] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(225);
+                    let b = computer.read_at(6);
+                    let destination = 6;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 6;
+                    continue 'dispatch;
+                }
+                _ => break 'compiled,
+            }
+        }
+
+        let state = computer.step(io)?;
+        if let Some(address) = computer.last_write() {
+            code.written(computer, address);
+        }
+        match state {
+            State::Running | State::Output(_) => {}
+            other => return Ok(other),
+        }
+    }
+}
+
+// Which compiled instructions are still what's in memory
+struct Code {
+    owners: Vec<Option<usize>>,
+    valid: Vec<bool>,
+}
+
+impl Code {
+    fn new(computer: &Computer) -> Code {
+        let mut owners = vec![None; PROGRAM.len()];
+        for (i, &(start, length)) in INSTRUCTIONS.iter().enumerate() {
+            for owner in &mut owners[start..start + length] {
+                *owner = Some(i);
+            }
+        }
+
+        let valid = INSTRUCTIONS
+            .iter()
+            .map(|&instruction| unchanged(computer, instruction))
+            .collect();
+
+        Code { owners, valid }
+    }
+
+    fn written(&mut self, computer: &Computer, address: usize) {
+        if let Some(&Some(i)) = self.owners.get(address) {
+            self.valid[i] = unchanged(computer, INSTRUCTIONS[i]);
+        }
+    }
+}
+
+fn unchanged(computer: &Computer, (start, length): (usize, usize)) -> bool {
+    (start..start + length).all(|address| computer.read_at(address) == PROGRAM[address])
+}
+
+fn write(computer: &mut Computer, code: &mut Code, address: usize, value: i64) {
+    computer.write_at(address, value);
+    code.written(computer, address);
+}
@@ -0,0 +1,3430 @@
+// Compiled from a 973-value Intcode program by `intcode compile`. Don't edit it by hand;
+// compile the program again instead.
+//
+// Each instruction that was found in the program is Rust code here. Any that weren't, or that the
+// program has written over since, are run by the interpreter. So is any add or multiply that
+// overflows, and everything once memory has a value too big for an i64, so the computer's
+// arithmetic decides what happens. Nothing is recorded in the computer's history. A computer that
+// isn't `Computer::is_standard`, with opcodes of its own, an earlier spec level or limits, is run
+// by the interpreter the whole way.
+
+use intcode::{Computer, IntcodeError, Io, State};
+
+const PROGRAM: &[i64] = &[
+    1102, 34463338, 34463338, 63, 1007, 63, 34463338, 63, 1005, 63, 53, 1101, 3, 0, 1000, 109, 988,
+    209, 12, 9, 1000, 209, 6, 209, 3, 203, 0, 1008, 1000, 1, 63, 1005, 63, 65, 1008, 1000, 2, 63,
+    1005, 63, 904, 1008, 1000, 0, 63, 1005, 63, 58, 4, 25, 104, 0, 99, 4, 0, 104, 0, 99, 4, 17, 104,
+    0, 99, 0, 0, 1102, 1, 550, 1027, 1101, 0, 0, 1020, 1101, 30, 0, 1004, 1101, 0, 22, 1014, 1102,
+    1, 36, 1009, 1101, 37, 0, 1007, 1102, 25, 1, 1010, 1102, 1, 33, 1012, 1102, 282, 1, 1029, 1102,
+    1, 488, 1025, 1101, 0, 31, 1019, 1101, 0, 21, 1008, 1101, 0, 35, 1015, 1101, 664, 0, 1023, 1102,
+    26, 1, 1001, 1101, 28, 0, 1016, 1102, 29, 1, 1005, 1102, 1, 24, 1002, 1101, 20, 0, 1018, 1101,
+    27, 0, 1013, 1101, 38, 0, 1017, 1102, 1, 1, 1021, 1102, 1, 557, 1026, 1102, 1, 39, 1000, 1101,
+    23, 0, 1006, 1101, 493, 0, 1024, 1102, 1, 291, 1028, 1101, 671, 0, 1022, 1101, 0, 34, 1003,
+    1101, 0, 32, 1011, 109, 10, 21108, 40, 40, 8, 1005, 1018, 199, 4, 187, 1105, 1, 203, 1001, 64,
+    1, 64, 1002, 64, 2, 64, 109, -14, 2108, 30, 8, 63, 1005, 63, 225, 4, 209, 1001, 64, 1, 64, 1105,
+    1, 225, 1002, 64, 2, 64, 109, 3, 2102, 1, 4, 63, 1008, 63, 34, 63, 1005, 63, 251, 4, 231, 1001,
+    64, 1, 64, 1106, 0, 251, 1002, 64, 2, 64, 109, 12, 2107, 22, -5, 63, 1005, 63, 269, 4, 257,
+    1105, 1, 273, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 20, 2106, 0, -3, 4, 279, 1001, 64, 1, 64,
+    1106, 0, 291, 1002, 64, 2, 64, 109, -16, 21108, 41, 40, -3, 1005, 1012, 311, 1001, 64, 1, 64,
+    1105, 1, 313, 4, 297, 1002, 64, 2, 64, 109, -13, 2101, 0, 2, 63, 1008, 63, 30, 63, 1005, 63,
+    335, 4, 319, 1105, 1, 339, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -3, 2102, 1, 4, 63, 1008, 63,
+    35, 63, 1005, 63, 359, 1106, 0, 365, 4, 345, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 15, 1205, 6,
+    377, 1105, 1, 383, 4, 371, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 5, 21102, 42, 1, -2, 1008,
+    1017, 39, 63, 1005, 63, 403, 1106, 0, 409, 4, 389, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -17,
+    21107, 43, 44, 10, 1005, 1012, 431, 4, 415, 1001, 64, 1, 64, 1106, 0, 431, 1002, 64, 2, 64, 109,
+    14, 21107, 44, 43, -4, 1005, 1012, 451, 1001, 64, 1, 64, 1106, 0, 453, 4, 437, 1002, 64, 2, 64,
+    109, 1, 21102, 45, 1, -3, 1008, 1014, 45, 63, 1005, 63, 479, 4, 459, 1001, 64, 1, 64, 1105, 1,
+    479, 1002, 64, 2, 64, 109, 7, 2105, 1, 0, 4, 485, 1106, 0, 497, 1001, 64, 1, 64, 1002, 64, 2,
+    64, 109, 5, 1206, -8, 513, 1001, 64, 1, 64, 1106, 0, 515, 4, 503, 1002, 64, 2, 64, 109, -33,
+    2101, 0, 7, 63, 1008, 63, 32, 63, 1005, 63, 535, 1106, 0, 541, 4, 521, 1001, 64, 1, 64, 1002,
+    64, 2, 64, 109, 23, 2106, 0, 8, 1001, 64, 1, 64, 1106, 0, 559, 4, 547, 1002, 64, 2, 64, 109, -1,
+    21101, 46, 0, -5, 1008, 1013, 46, 63, 1005, 63, 585, 4, 565, 1001, 64, 1, 64, 1105, 1, 585,
+    1002, 64, 2, 64, 109, -4, 21101, 47, 0, 2, 1008, 1016, 44, 63, 1005, 63, 605, 1105, 1, 611, 4,
+    591, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -18, 1207, 4, 38, 63, 1005, 63, 627, 1106, 0, 633,
+    4, 617, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 5, 2107, 22, 7, 63, 1005, 63, 649, 1106, 0, 655,
+    4, 639, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, 12, 2105, 1, 10, 1001, 64, 1, 64, 1106, 0, 673,
+    4, 661, 1002, 64, 2, 64, 109, -10, 1208, 6, 33, 63, 1005, 63, 693, 1001, 64, 1, 64, 1106, 0,
+    695, 4, 679, 1002, 64, 2, 64, 109, -7, 2108, 35, 7, 63, 1005, 63, 715, 1001, 64, 1, 64, 1106, 0,
+    717, 4, 701, 1002, 64, 2, 64, 109, 6, 1208, 5, 37, 63, 1005, 63, 735, 4, 723, 1106, 0, 739,
+    1001, 64, 1, 64, 1002, 64, 2, 64, 109, -4, 1202, 5, 1, 63, 1008, 63, 34, 63, 1005, 63, 765, 4,
+    745, 1001, 64, 1, 64, 1105, 1, 765, 1002, 64, 2, 64, 109, 29, 1206, -7, 783, 4, 771, 1001, 64,
+    1, 64, 1105, 1, 783, 1002, 64, 2, 64, 109, -28, 1201, 6, 0, 63, 1008, 63, 29, 63, 1005, 63, 809,
+    4, 789, 1001, 64, 1, 64, 1106, 0, 809, 1002, 64, 2, 64, 109, 5, 1202, 2, 1, 63, 1008, 63, 20,
+    63, 1005, 63, 829, 1106, 0, 835, 4, 815, 1001, 64, 1, 64, 1002, 64, 2, 64, 109, -1, 1201, 6, 0,
+    63, 1008, 63, 35, 63, 1005, 63, 859, 1001, 64, 1, 64, 1105, 1, 861, 4, 841, 1002, 64, 2, 64,
+    109, 2, 1207, -3, 25, 63, 1005, 63, 879, 4, 867, 1105, 1, 883, 1001, 64, 1, 64, 1002, 64, 2, 64,
+    109, 13, 1205, 3, 901, 4, 889, 1001, 64, 1, 64, 1106, 0, 901, 4, 64, 99, 21101, 0, 27, 1, 21101,
+    915, 0, 0, 1106, 0, 922, 21201, 1, 22987, 1, 204, 1, 99, 109, 3, 1207, -2, 3, 63, 1005, 63, 964,
+    21201, -2, -1, 1, 21101, 0, 942, 0, 1106, 0, 922, 22101, 0, 1, -1, 21201, -2, -3, 1, 21101, 0,
+    957, 0, 1106, 0, 922, 22201, 1, -1, -2, 1105, 1, 968, 21202, -2, 1, -2, 109, -3, 2105, 1, 0,
+];
+
+// Where each compiled instruction starts and how many values it takes up
+const INSTRUCTIONS: &[(usize, usize)] = &[
+    (0, 4), (4, 4), (8, 3), (11, 4), (15, 2), (17, 2), (19, 2), (21, 2), (23, 2), (25, 2), (27, 4),
+    (31, 3), (34, 4), (38, 3), (41, 4), (45, 3), (48, 2), (50, 2), (52, 1), (53, 2), (55, 2),
+    (57, 1), (58, 2), (60, 2), (62, 1), (65, 4), (69, 4), (73, 4), (77, 4), (81, 4), (85, 4),
+    (89, 4), (93, 4), (97, 4), (101, 4), (105, 4), (109, 4), (113, 4), (117, 4), (121, 4), (125, 4),
+    (129, 4), (133, 4), (137, 4), (141, 4), (145, 4), (149, 4), (153, 4), (157, 4), (161, 4),
+    (165, 4), (169, 4), (173, 4), (177, 4), (181, 4), (185, 2), (187, 4), (191, 3), (194, 2),
+    (196, 3), (199, 4), (203, 4), (207, 2), (209, 4), (213, 3), (216, 2), (218, 4), (222, 3),
+    (225, 4), (229, 2), (231, 4), (235, 4), (239, 3), (242, 2), (244, 4), (248, 3), (251, 4),
+    (255, 2), (257, 4), (261, 3), (264, 2), (266, 3), (269, 4), (273, 4), (277, 2), (279, 3),
+    (282, 2), (284, 4), (288, 3), (291, 4), (295, 2), (297, 4), (301, 3), (304, 4), (308, 3),
+    (311, 2), (313, 4), (317, 2), (319, 4), (323, 4), (327, 3), (330, 2), (332, 3), (335, 4),
+    (339, 4), (343, 2), (345, 4), (349, 4), (353, 3), (356, 3), (359, 2), (361, 4), (365, 4),
+    (369, 2), (371, 3), (374, 3), (377, 2), (379, 4), (383, 4), (387, 2), (389, 4), (393, 4),
+    (397, 3), (400, 3), (403, 2), (405, 4), (409, 4), (413, 2), (415, 4), (419, 3), (422, 2),
+    (424, 4), (428, 3), (431, 4), (435, 2), (437, 4), (441, 3), (444, 4), (448, 3), (451, 2),
+    (453, 4), (457, 2), (459, 4), (463, 4), (467, 3), (470, 2), (472, 4), (476, 3), (479, 4),
+    (483, 2), (485, 3), (488, 2), (490, 3), (493, 4), (497, 4), (501, 2), (503, 3), (506, 4),
+    (510, 3), (513, 2), (515, 4), (519, 2), (521, 4), (525, 4), (529, 3), (532, 3), (535, 2),
+    (537, 4), (541, 4), (545, 2), (547, 3), (550, 4), (554, 3), (557, 2), (559, 4), (563, 2),
+    (565, 4), (569, 4), (573, 3), (576, 2), (578, 4), (582, 3), (585, 4), (589, 2), (591, 4),
+    (595, 4), (599, 3), (602, 3), (605, 2), (607, 4), (611, 4), (615, 2), (617, 4), (621, 3),
+    (624, 3), (627, 2), (629, 4), (633, 4), (637, 2), (639, 4), (643, 3), (646, 3), (649, 2),
+    (651, 4), (655, 4), (659, 2), (661, 3), (664, 4), (668, 3), (671, 2), (673, 4), (677, 2),
+    (679, 4), (683, 3), (686, 4), (690, 3), (693, 2), (695, 4), (699, 2), (701, 4), (705, 3),
+    (708, 4), (712, 3), (715, 2), (717, 4), (721, 2), (723, 4), (727, 3), (730, 2), (732, 3),
+    (735, 4), (739, 4), (743, 2), (745, 4), (749, 4), (753, 3), (756, 2), (758, 4), (762, 3),
+    (765, 4), (769, 2), (771, 3), (774, 2), (776, 4), (780, 3), (783, 4), (787, 2), (789, 4),
+    (793, 4), (797, 3), (800, 2), (802, 4), (806, 3), (809, 4), (813, 2), (815, 4), (819, 4),
+    (823, 3), (826, 3), (829, 2), (831, 4), (835, 4), (839, 2), (841, 4), (845, 4), (849, 3),
+    (852, 4), (856, 3), (859, 2), (861, 4), (865, 2), (867, 4), (871, 3), (874, 2), (876, 3),
+    (879, 4), (883, 4), (887, 2), (889, 3), (892, 2), (894, 4), (898, 3), (901, 2), (903, 1),
+    (904, 4), (908, 4), (912, 3), (915, 4), (919, 2), (921, 1), (922, 2), (924, 4), (928, 3),
+    (931, 4), (935, 4), (939, 3), (942, 4), (946, 4), (950, 4), (954, 3), (957, 4), (961, 3),
+    (964, 4), (968, 2), (970, 3),
+];
+
+pub fn run(computer: &mut Computer, io: &mut impl Io) -> Result<(), IntcodeError> {
+    if run_until_input(computer, io)? == State::NeedsInput {
+        return Err(IntcodeError::MissingInput {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+        });
+    }
+    Ok(())
+}
+
+pub fn run_until_input(computer: &mut Computer, io: &mut impl Io) -> Result<State, IntcodeError> {
+    if !computer.is_standard() {
+        return computer.run_until_input(io);
+    }
+    let mut code = Code::new(computer);
+
+    'dispatch: loop {
+        'compiled: {
+            if computer.has_big_values() {
+                break 'compiled;
+            }
+            match computer.current_position {
+                0 => {
+                    // 0: mul #34463338, #34463338, [63]
+                    if !code.valid[0] {
+                        break 'compiled;
+                    }
+                    let a = 34463338;
+                    let b = 34463338;
+                    let destination = 63;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 4: lt [63], #34463338, [63]
+                    computer.current_position = 4;
+                    if !code.valid[1] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = 34463338;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 8: jt [63], #53
+                    computer.current_position = 8;
+                    if !code.valid[2] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 53;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 11;
+                    continue 'dispatch;
+                }
+                11 => {
+                    // 11: add #3, #0, [1000]
+                    if !code.valid[3] {
+                        break 'compiled;
+                    }
+                    let a = 3;
+                    let b = 0;
+                    let destination = 1000;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 15: arb #988
+                    computer.current_position = 15;
+                    if !code.valid[4] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 988) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 17: arb rb+12
+                    computer.current_position = 17;
+                    if !code.valid[5] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, computer.read_at(relative(computer, 0, 12)?)) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 19: arb [1000]
+                    computer.current_position = 19;
+                    if !code.valid[6] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, computer.read_at(1000)) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    computer.current_position = 21;
+                    continue 'dispatch;
+                }
+                21 => {
+                    // 21: arb rb+6
+                    if !code.valid[7] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, computer.read_at(relative(computer, 0, 6)?)) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    computer.current_position = 23;
+                    continue 'dispatch;
+                }
+                23 => {
+                    // 23: arb rb+3
+                    if !code.valid[8] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, computer.read_at(relative(computer, 0, 3)?)) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    computer.current_position = 25;
+                    continue 'dispatch;
+                }
+                25 => {
+                    // 25: in rb+0
+                    if !code.valid[9] {
+                        break 'compiled;
+                    }
+                    let value = match io.input() {
+                        Some(value) => value,
+                        None => return Ok(State::NeedsInput),
+                    };
+                    let destination = relative(computer, 0, 0)?;
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 27;
+                    continue 'dispatch;
+                }
+                27 => {
+                    // 27: eq [1000], #1, [63]
+                    if !code.valid[10] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1000);
+                    let b = 1;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    computer.current_position = 31;
+                    continue 'dispatch;
+                }
+                31 => {
+                    // 31: jt [63], #65
+                    if !code.valid[11] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 65;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 34;
+                    continue 'dispatch;
+                }
+                34 => {
+                    // 34: eq [1000], #2, [63]
+                    if !code.valid[12] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1000);
+                    let b = 2;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    computer.current_position = 38;
+                    continue 'dispatch;
+                }
+                38 => {
+                    // 38: jt [63], #904
+                    if !code.valid[13] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 904;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 41;
+                    continue 'dispatch;
+                }
+                41 => {
+                    // 41: eq [1000], #0, [63]
+                    if !code.valid[14] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1000);
+                    let b = 0;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    computer.current_position = 45;
+                    continue 'dispatch;
+                }
+                45 => {
+                    // 45: jt [63], #58
+                    if !code.valid[15] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 58;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 48;
+                    continue 'dispatch;
+                }
+                48 => {
+                    // 48: out [25]
+                    if !code.valid[16] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(25));
+                    // 50: out #0
+                    computer.current_position = 50;
+                    if !code.valid[17] {
+                        break 'compiled;
+                    }
+                    io.output(0);
+                    // 52: hlt
+                    computer.current_position = 52;
+                    if !code.valid[18] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                53 => {
+                    // 53: out [0]
+                    if !code.valid[19] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(0));
+                    // 55: out #0
+                    computer.current_position = 55;
+                    if !code.valid[20] {
+                        break 'compiled;
+                    }
+                    io.output(0);
+                    // 57: hlt
+                    computer.current_position = 57;
+                    if !code.valid[21] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                58 => {
+                    // 58: out [17]
+                    if !code.valid[22] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(17));
+                    // 60: out #0
+                    computer.current_position = 60;
+                    if !code.valid[23] {
+                        break 'compiled;
+                    }
+                    io.output(0);
+                    // 62: hlt
+                    computer.current_position = 62;
+                    if !code.valid[24] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                65 => {
+                    // 65: mul #1, #550, [1027]
+                    if !code.valid[25] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 550;
+                    let destination = 1027;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 69: add #0, #0, [1020]
+                    computer.current_position = 69;
+                    if !code.valid[26] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 0;
+                    let destination = 1020;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 73: add #30, #0, [1004]
+                    computer.current_position = 73;
+                    if !code.valid[27] {
+                        break 'compiled;
+                    }
+                    let a = 30;
+                    let b = 0;
+                    let destination = 1004;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 77: add #0, #22, [1014]
+                    computer.current_position = 77;
+                    if !code.valid[28] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 22;
+                    let destination = 1014;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 81: mul #1, #36, [1009]
+                    computer.current_position = 81;
+                    if !code.valid[29] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 36;
+                    let destination = 1009;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 85: add #37, #0, [1007]
+                    computer.current_position = 85;
+                    if !code.valid[30] {
+                        break 'compiled;
+                    }
+                    let a = 37;
+                    let b = 0;
+                    let destination = 1007;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 89: mul #25, #1, [1010]
+                    computer.current_position = 89;
+                    if !code.valid[31] {
+                        break 'compiled;
+                    }
+                    let a = 25;
+                    let b = 1;
+                    let destination = 1010;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 93: mul #1, #33, [1012]
+                    computer.current_position = 93;
+                    if !code.valid[32] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 33;
+                    let destination = 1012;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 97: mul #282, #1, [1029]
+                    computer.current_position = 97;
+                    if !code.valid[33] {
+                        break 'compiled;
+                    }
+                    let a = 282;
+                    let b = 1;
+                    let destination = 1029;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 101: mul #1, #488, [1025]
+                    computer.current_position = 101;
+                    if !code.valid[34] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 488;
+                    let destination = 1025;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 105: add #0, #31, [1019]
+                    computer.current_position = 105;
+                    if !code.valid[35] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 31;
+                    let destination = 1019;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 109: add #0, #21, [1008]
+                    computer.current_position = 109;
+                    if !code.valid[36] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 21;
+                    let destination = 1008;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 113: add #0, #35, [1015]
+                    computer.current_position = 113;
+                    if !code.valid[37] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 35;
+                    let destination = 1015;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 117: add #664, #0, [1023]
+                    computer.current_position = 117;
+                    if !code.valid[38] {
+                        break 'compiled;
+                    }
+                    let a = 664;
+                    let b = 0;
+                    let destination = 1023;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 121: mul #26, #1, [1001]
+                    computer.current_position = 121;
+                    if !code.valid[39] {
+                        break 'compiled;
+                    }
+                    let a = 26;
+                    let b = 1;
+                    let destination = 1001;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 125: add #28, #0, [1016]
+                    computer.current_position = 125;
+                    if !code.valid[40] {
+                        break 'compiled;
+                    }
+                    let a = 28;
+                    let b = 0;
+                    let destination = 1016;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 129: mul #29, #1, [1005]
+                    computer.current_position = 129;
+                    if !code.valid[41] {
+                        break 'compiled;
+                    }
+                    let a = 29;
+                    let b = 1;
+                    let destination = 1005;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 133: mul #1, #24, [1002]
+                    computer.current_position = 133;
+                    if !code.valid[42] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 24;
+                    let destination = 1002;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 137: add #20, #0, [1018]
+                    computer.current_position = 137;
+                    if !code.valid[43] {
+                        break 'compiled;
+                    }
+                    let a = 20;
+                    let b = 0;
+                    let destination = 1018;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 141: add #27, #0, [1013]
+                    computer.current_position = 141;
+                    if !code.valid[44] {
+                        break 'compiled;
+                    }
+                    let a = 27;
+                    let b = 0;
+                    let destination = 1013;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 145: add #38, #0, [1017]
+                    computer.current_position = 145;
+                    if !code.valid[45] {
+                        break 'compiled;
+                    }
+                    let a = 38;
+                    let b = 0;
+                    let destination = 1017;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 149: mul #1, #1, [1021]
+                    computer.current_position = 149;
+                    if !code.valid[46] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 1;
+                    let destination = 1021;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 153: mul #1, #557, [1026]
+                    computer.current_position = 153;
+                    if !code.valid[47] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 557;
+                    let destination = 1026;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 157: mul #1, #39, [1000]
+                    computer.current_position = 157;
+                    if !code.valid[48] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 39;
+                    let destination = 1000;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 161: add #23, #0, [1006]
+                    computer.current_position = 161;
+                    if !code.valid[49] {
+                        break 'compiled;
+                    }
+                    let a = 23;
+                    let b = 0;
+                    let destination = 1006;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 165: add #493, #0, [1024]
+                    computer.current_position = 165;
+                    if !code.valid[50] {
+                        break 'compiled;
+                    }
+                    let a = 493;
+                    let b = 0;
+                    let destination = 1024;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 169: mul #1, #291, [1028]
+                    computer.current_position = 169;
+                    if !code.valid[51] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 291;
+                    let destination = 1028;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 173: add #671, #0, [1022]
+                    computer.current_position = 173;
+                    if !code.valid[52] {
+                        break 'compiled;
+                    }
+                    let a = 671;
+                    let b = 0;
+                    let destination = 1022;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 177: add #0, #34, [1003]
+                    computer.current_position = 177;
+                    if !code.valid[53] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 34;
+                    let destination = 1003;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 181: add #0, #32, [1011]
+                    computer.current_position = 181;
+                    if !code.valid[54] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 32;
+                    let destination = 1011;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 185: arb #10
+                    computer.current_position = 185;
+                    if !code.valid[55] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 10) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 187: eq #40, #40, rb+8
+                    computer.current_position = 187;
+                    if !code.valid[56] {
+                        break 'compiled;
+                    }
+                    let a = 40;
+                    let b = 40;
+                    let destination = relative(computer, 2, 8)?;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 191: jt [1018], #199
+                    computer.current_position = 191;
+                    if !code.valid[57] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(1018) != 0 {
+                        computer.current_position = 199;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 194;
+                    continue 'dispatch;
+                }
+                194 => {
+                    // 194: out [187]
+                    if !code.valid[58] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(187));
+                    // 196: jt #1, #203
+                    computer.current_position = 196;
+                    if !code.valid[59] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 203;
+                    continue 'dispatch;
+                }
+                199 => {
+                    // 199: add [64], #1, [64]
+                    if !code.valid[60] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 203;
+                    continue 'dispatch;
+                }
+                203 => {
+                    // 203: mul [64], #2, [64]
+                    if !code.valid[61] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 207: arb #-14
+                    computer.current_position = 207;
+                    if !code.valid[62] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -14) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 209: eq #30, rb+8, [63]
+                    computer.current_position = 209;
+                    if !code.valid[63] {
+                        break 'compiled;
+                    }
+                    let a = 30;
+                    let b = computer.read_at(relative(computer, 1, 8)?);
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 213: jt [63], #225
+                    computer.current_position = 213;
+                    if !code.valid[64] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 225;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 216;
+                    continue 'dispatch;
+                }
+                216 => {
+                    // 216: out [209]
+                    if !code.valid[65] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(209));
+                    // 218: add [64], #1, [64]
+                    computer.current_position = 218;
+                    if !code.valid[66] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 222: jt #1, #225
+                    computer.current_position = 222;
+                    if !code.valid[67] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 225;
+                    continue 'dispatch;
+                }
+                225 => {
+                    // 225: mul [64], #2, [64]
+                    if !code.valid[68] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 229: arb #3
+                    computer.current_position = 229;
+                    if !code.valid[69] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 231: mul #1, rb+4, [63]
+                    computer.current_position = 231;
+                    if !code.valid[70] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = computer.read_at(relative(computer, 1, 4)?);
+                    let destination = 63;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 235: eq [63], #34, [63]
+                    computer.current_position = 235;
+                    if !code.valid[71] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = 34;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 239: jt [63], #251
+                    computer.current_position = 239;
+                    if !code.valid[72] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 251;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 242;
+                    continue 'dispatch;
+                }
+                242 => {
+                    // 242: out [231]
+                    if !code.valid[73] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(231));
+                    // 244: add [64], #1, [64]
+                    computer.current_position = 244;
+                    if !code.valid[74] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 248: jf #0, #251
+                    computer.current_position = 248;
+                    if !code.valid[75] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 251;
+                    continue 'dispatch;
+                }
+                251 => {
+                    // 251: mul [64], #2, [64]
+                    if !code.valid[76] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 255: arb #12
+                    computer.current_position = 255;
+                    if !code.valid[77] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 12) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 257: lt #22, rb-5, [63]
+                    computer.current_position = 257;
+                    if !code.valid[78] {
+                        break 'compiled;
+                    }
+                    let a = 22;
+                    let b = computer.read_at(relative(computer, 1, -5)?);
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 261: jt [63], #269
+                    computer.current_position = 261;
+                    if !code.valid[79] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 269;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 264;
+                    continue 'dispatch;
+                }
+                264 => {
+                    // 264: out [257]
+                    if !code.valid[80] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(257));
+                    // 266: jt #1, #273
+                    computer.current_position = 266;
+                    if !code.valid[81] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 273;
+                    continue 'dispatch;
+                }
+                269 => {
+                    // 269: add [64], #1, [64]
+                    if !code.valid[82] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 273;
+                    continue 'dispatch;
+                }
+                273 => {
+                    // 273: mul [64], #2, [64]
+                    if !code.valid[83] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 277: arb #20
+                    computer.current_position = 277;
+                    if !code.valid[84] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 20) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 279: jf #0, rb-3
+                    computer.current_position = 279;
+                    if !code.valid[85] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, -3)?))?;
+                    continue 'dispatch;
+                }
+                282 => {
+                    // 282: out [279]
+                    if !code.valid[86] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(279));
+                    // 284: add [64], #1, [64]
+                    computer.current_position = 284;
+                    if !code.valid[87] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 288: jf #0, #291
+                    computer.current_position = 288;
+                    if !code.valid[88] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 291;
+                    continue 'dispatch;
+                }
+                291 => {
+                    // 291: mul [64], #2, [64]
+                    if !code.valid[89] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 295: arb #-16
+                    computer.current_position = 295;
+                    if !code.valid[90] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -16) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 297: eq #41, #40, rb-3
+                    computer.current_position = 297;
+                    if !code.valid[91] {
+                        break 'compiled;
+                    }
+                    let a = 41;
+                    let b = 40;
+                    let destination = relative(computer, 2, -3)?;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 301: jt [1012], #311
+                    computer.current_position = 301;
+                    if !code.valid[92] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(1012) != 0 {
+                        computer.current_position = 311;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 304;
+                    continue 'dispatch;
+                }
+                304 => {
+                    // 304: add [64], #1, [64]
+                    if !code.valid[93] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 308: jt #1, #313
+                    computer.current_position = 308;
+                    if !code.valid[94] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 313;
+                    continue 'dispatch;
+                }
+                311 => {
+                    // 311: out [297]
+                    if !code.valid[95] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(297));
+                    computer.current_position = 313;
+                    continue 'dispatch;
+                }
+                313 => {
+                    // 313: mul [64], #2, [64]
+                    if !code.valid[96] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 317: arb #-13
+                    computer.current_position = 317;
+                    if !code.valid[97] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -13) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 319: add #0, rb+2, [63]
+                    computer.current_position = 319;
+                    if !code.valid[98] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = computer.read_at(relative(computer, 1, 2)?);
+                    let destination = 63;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 323: eq [63], #30, [63]
+                    computer.current_position = 323;
+                    if !code.valid[99] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = 30;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 327: jt [63], #335
+                    computer.current_position = 327;
+                    if !code.valid[100] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 335;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 330;
+                    continue 'dispatch;
+                }
+                330 => {
+                    // 330: out [319]
+                    if !code.valid[101] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(319));
+                    // 332: jt #1, #339
+                    computer.current_position = 332;
+                    if !code.valid[102] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 339;
+                    continue 'dispatch;
+                }
+                335 => {
+                    // 335: add [64], #1, [64]
+                    if !code.valid[103] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 339;
+                    continue 'dispatch;
+                }
+                339 => {
+                    // 339: mul [64], #2, [64]
+                    if !code.valid[104] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 343: arb #-3
+                    computer.current_position = 343;
+                    if !code.valid[105] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 345: mul #1, rb+4, [63]
+                    computer.current_position = 345;
+                    if !code.valid[106] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = computer.read_at(relative(computer, 1, 4)?);
+                    let destination = 63;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 349: eq [63], #35, [63]
+                    computer.current_position = 349;
+                    if !code.valid[107] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = 35;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 353: jt [63], #359
+                    computer.current_position = 353;
+                    if !code.valid[108] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 359;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 356;
+                    continue 'dispatch;
+                }
+                356 => {
+                    // 356: jf #0, #365
+                    if !code.valid[109] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 365;
+                    continue 'dispatch;
+                }
+                359 => {
+                    // 359: out [345]
+                    if !code.valid[110] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(345));
+                    // 361: add [64], #1, [64]
+                    computer.current_position = 361;
+                    if !code.valid[111] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 365;
+                    continue 'dispatch;
+                }
+                365 => {
+                    // 365: mul [64], #2, [64]
+                    if !code.valid[112] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 369: arb #15
+                    computer.current_position = 369;
+                    if !code.valid[113] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 15) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 371: jt rb+6, #377
+                    computer.current_position = 371;
+                    if !code.valid[114] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(relative(computer, 0, 6)?) != 0 {
+                        computer.current_position = 377;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 374;
+                    continue 'dispatch;
+                }
+                374 => {
+                    // 374: jt #1, #383
+                    if !code.valid[115] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 383;
+                    continue 'dispatch;
+                }
+                377 => {
+                    // 377: out [371]
+                    if !code.valid[116] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(371));
+                    // 379: add [64], #1, [64]
+                    computer.current_position = 379;
+                    if !code.valid[117] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 383;
+                    continue 'dispatch;
+                }
+                383 => {
+                    // 383: mul [64], #2, [64]
+                    if !code.valid[118] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 387: arb #5
+                    computer.current_position = 387;
+                    if !code.valid[119] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 5) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 389: mul #42, #1, rb-2
+                    computer.current_position = 389;
+                    if !code.valid[120] {
+                        break 'compiled;
+                    }
+                    let a = 42;
+                    let b = 1;
+                    let destination = relative(computer, 2, -2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 393: eq [1017], #39, [63]
+                    computer.current_position = 393;
+                    if !code.valid[121] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1017);
+                    let b = 39;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 397: jt [63], #403
+                    computer.current_position = 397;
+                    if !code.valid[122] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 403;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 400;
+                    continue 'dispatch;
+                }
+                400 => {
+                    // 400: jf #0, #409
+                    if !code.valid[123] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 409;
+                    continue 'dispatch;
+                }
+                403 => {
+                    // 403: out [389]
+                    if !code.valid[124] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(389));
+                    // 405: add [64], #1, [64]
+                    computer.current_position = 405;
+                    if !code.valid[125] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 409;
+                    continue 'dispatch;
+                }
+                409 => {
+                    // 409: mul [64], #2, [64]
+                    if !code.valid[126] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 413: arb #-17
+                    computer.current_position = 413;
+                    if !code.valid[127] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -17) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 415: lt #43, #44, rb+10
+                    computer.current_position = 415;
+                    if !code.valid[128] {
+                        break 'compiled;
+                    }
+                    let a = 43;
+                    let b = 44;
+                    let destination = relative(computer, 2, 10)?;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 419: jt [1012], #431
+                    computer.current_position = 419;
+                    if !code.valid[129] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(1012) != 0 {
+                        computer.current_position = 431;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 422;
+                    continue 'dispatch;
+                }
+                422 => {
+                    // 422: out [415]
+                    if !code.valid[130] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(415));
+                    // 424: add [64], #1, [64]
+                    computer.current_position = 424;
+                    if !code.valid[131] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 428: jf #0, #431
+                    computer.current_position = 428;
+                    if !code.valid[132] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 431;
+                    continue 'dispatch;
+                }
+                431 => {
+                    // 431: mul [64], #2, [64]
+                    if !code.valid[133] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 435: arb #14
+                    computer.current_position = 435;
+                    if !code.valid[134] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 14) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 437: lt #44, #43, rb-4
+                    computer.current_position = 437;
+                    if !code.valid[135] {
+                        break 'compiled;
+                    }
+                    let a = 44;
+                    let b = 43;
+                    let destination = relative(computer, 2, -4)?;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 441: jt [1012], #451
+                    computer.current_position = 441;
+                    if !code.valid[136] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(1012) != 0 {
+                        computer.current_position = 451;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 444;
+                    continue 'dispatch;
+                }
+                444 => {
+                    // 444: add [64], #1, [64]
+                    if !code.valid[137] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 448: jf #0, #453
+                    computer.current_position = 448;
+                    if !code.valid[138] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 453;
+                    continue 'dispatch;
+                }
+                451 => {
+                    // 451: out [437]
+                    if !code.valid[139] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(437));
+                    computer.current_position = 453;
+                    continue 'dispatch;
+                }
+                453 => {
+                    // 453: mul [64], #2, [64]
+                    if !code.valid[140] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 457: arb #1
+                    computer.current_position = 457;
+                    if !code.valid[141] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 1) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 459: mul #45, #1, rb-3
+                    computer.current_position = 459;
+                    if !code.valid[142] {
+                        break 'compiled;
+                    }
+                    let a = 45;
+                    let b = 1;
+                    let destination = relative(computer, 2, -3)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 463: eq [1014], #45, [63]
+                    computer.current_position = 463;
+                    if !code.valid[143] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1014);
+                    let b = 45;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 467: jt [63], #479
+                    computer.current_position = 467;
+                    if !code.valid[144] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 479;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 470;
+                    continue 'dispatch;
+                }
+                470 => {
+                    // 470: out [459]
+                    if !code.valid[145] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(459));
+                    // 472: add [64], #1, [64]
+                    computer.current_position = 472;
+                    if !code.valid[146] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 476: jt #1, #479
+                    computer.current_position = 476;
+                    if !code.valid[147] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 479;
+                    continue 'dispatch;
+                }
+                479 => {
+                    // 479: mul [64], #2, [64]
+                    if !code.valid[148] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 483: arb #7
+                    computer.current_position = 483;
+                    if !code.valid[149] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 7) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 485: jt #1, rb+0
+                    computer.current_position = 485;
+                    if !code.valid[150] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, 0)?))?;
+                    continue 'dispatch;
+                }
+                488 => {
+                    // 488: out [485]
+                    if !code.valid[151] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(485));
+                    // 490: jf #0, #497
+                    computer.current_position = 490;
+                    if !code.valid[152] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 497;
+                    continue 'dispatch;
+                }
+                493 => {
+                    // 493: add [64], #1, [64]
+                    if !code.valid[153] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 497;
+                    continue 'dispatch;
+                }
+                497 => {
+                    // 497: mul [64], #2, [64]
+                    if !code.valid[154] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 501: arb #5
+                    computer.current_position = 501;
+                    if !code.valid[155] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 5) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 503: jf rb-8, #513
+                    computer.current_position = 503;
+                    if !code.valid[156] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(relative(computer, 0, -8)?) == 0 {
+                        computer.current_position = 513;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 506;
+                    continue 'dispatch;
+                }
+                506 => {
+                    // 506: add [64], #1, [64]
+                    if !code.valid[157] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 510: jf #0, #515
+                    computer.current_position = 510;
+                    if !code.valid[158] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 515;
+                    continue 'dispatch;
+                }
+                513 => {
+                    // 513: out [503]
+                    if !code.valid[159] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(503));
+                    computer.current_position = 515;
+                    continue 'dispatch;
+                }
+                515 => {
+                    // 515: mul [64], #2, [64]
+                    if !code.valid[160] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 519: arb #-33
+                    computer.current_position = 519;
+                    if !code.valid[161] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -33) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 521: add #0, rb+7, [63]
+                    computer.current_position = 521;
+                    if !code.valid[162] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = computer.read_at(relative(computer, 1, 7)?);
+                    let destination = 63;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 525: eq [63], #32, [63]
+                    computer.current_position = 525;
+                    if !code.valid[163] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = 32;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 529: jt [63], #535
+                    computer.current_position = 529;
+                    if !code.valid[164] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 535;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 532;
+                    continue 'dispatch;
+                }
+                532 => {
+                    // 532: jf #0, #541
+                    if !code.valid[165] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 541;
+                    continue 'dispatch;
+                }
+                535 => {
+                    // 535: out [521]
+                    if !code.valid[166] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(521));
+                    // 537: add [64], #1, [64]
+                    computer.current_position = 537;
+                    if !code.valid[167] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 541;
+                    continue 'dispatch;
+                }
+                541 => {
+                    // 541: mul [64], #2, [64]
+                    if !code.valid[168] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 545: arb #23
+                    computer.current_position = 545;
+                    if !code.valid[169] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 23) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 547: jf #0, rb+8
+                    computer.current_position = 547;
+                    if !code.valid[170] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, 8)?))?;
+                    continue 'dispatch;
+                }
+                550 => {
+                    // 550: add [64], #1, [64]
+                    if !code.valid[171] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 554: jf #0, #559
+                    computer.current_position = 554;
+                    if !code.valid[172] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 559;
+                    continue 'dispatch;
+                }
+                557 => {
+                    // 557: out [547]
+                    if !code.valid[173] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(547));
+                    computer.current_position = 559;
+                    continue 'dispatch;
+                }
+                559 => {
+                    // 559: mul [64], #2, [64]
+                    if !code.valid[174] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 563: arb #-1
+                    computer.current_position = 563;
+                    if !code.valid[175] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -1) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 565: add #46, #0, rb-5
+                    computer.current_position = 565;
+                    if !code.valid[176] {
+                        break 'compiled;
+                    }
+                    let a = 46;
+                    let b = 0;
+                    let destination = relative(computer, 2, -5)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 569: eq [1013], #46, [63]
+                    computer.current_position = 569;
+                    if !code.valid[177] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1013);
+                    let b = 46;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 573: jt [63], #585
+                    computer.current_position = 573;
+                    if !code.valid[178] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 585;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 576;
+                    continue 'dispatch;
+                }
+                576 => {
+                    // 576: out [565]
+                    if !code.valid[179] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(565));
+                    // 578: add [64], #1, [64]
+                    computer.current_position = 578;
+                    if !code.valid[180] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 582: jt #1, #585
+                    computer.current_position = 582;
+                    if !code.valid[181] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 585;
+                    continue 'dispatch;
+                }
+                585 => {
+                    // 585: mul [64], #2, [64]
+                    if !code.valid[182] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 589: arb #-4
+                    computer.current_position = 589;
+                    if !code.valid[183] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -4) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 591: add #47, #0, rb+2
+                    computer.current_position = 591;
+                    if !code.valid[184] {
+                        break 'compiled;
+                    }
+                    let a = 47;
+                    let b = 0;
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 595: eq [1016], #44, [63]
+                    computer.current_position = 595;
+                    if !code.valid[185] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1016);
+                    let b = 44;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 599: jt [63], #605
+                    computer.current_position = 599;
+                    if !code.valid[186] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 605;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 602;
+                    continue 'dispatch;
+                }
+                602 => {
+                    // 602: jt #1, #611
+                    if !code.valid[187] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 611;
+                    continue 'dispatch;
+                }
+                605 => {
+                    // 605: out [591]
+                    if !code.valid[188] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(591));
+                    // 607: add [64], #1, [64]
+                    computer.current_position = 607;
+                    if !code.valid[189] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 611;
+                    continue 'dispatch;
+                }
+                611 => {
+                    // 611: mul [64], #2, [64]
+                    if !code.valid[190] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 615: arb #-18
+                    computer.current_position = 615;
+                    if !code.valid[191] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -18) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 617: lt rb+4, #38, [63]
+                    computer.current_position = 617;
+                    if !code.valid[192] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 4)?);
+                    let b = 38;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 621: jt [63], #627
+                    computer.current_position = 621;
+                    if !code.valid[193] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 627;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 624;
+                    continue 'dispatch;
+                }
+                624 => {
+                    // 624: jf #0, #633
+                    if !code.valid[194] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 633;
+                    continue 'dispatch;
+                }
+                627 => {
+                    // 627: out [617]
+                    if !code.valid[195] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(617));
+                    // 629: add [64], #1, [64]
+                    computer.current_position = 629;
+                    if !code.valid[196] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 633;
+                    continue 'dispatch;
+                }
+                633 => {
+                    // 633: mul [64], #2, [64]
+                    if !code.valid[197] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 637: arb #5
+                    computer.current_position = 637;
+                    if !code.valid[198] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 5) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 639: lt #22, rb+7, [63]
+                    computer.current_position = 639;
+                    if !code.valid[199] {
+                        break 'compiled;
+                    }
+                    let a = 22;
+                    let b = computer.read_at(relative(computer, 1, 7)?);
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 643: jt [63], #649
+                    computer.current_position = 643;
+                    if !code.valid[200] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 649;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 646;
+                    continue 'dispatch;
+                }
+                646 => {
+                    // 646: jf #0, #655
+                    if !code.valid[201] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 655;
+                    continue 'dispatch;
+                }
+                649 => {
+                    // 649: out [639]
+                    if !code.valid[202] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(639));
+                    // 651: add [64], #1, [64]
+                    computer.current_position = 651;
+                    if !code.valid[203] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 655;
+                    continue 'dispatch;
+                }
+                655 => {
+                    // 655: mul [64], #2, [64]
+                    if !code.valid[204] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 659: arb #12
+                    computer.current_position = 659;
+                    if !code.valid[205] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 12) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 661: jt #1, rb+10
+                    computer.current_position = 661;
+                    if !code.valid[206] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, 10)?))?;
+                    continue 'dispatch;
+                }
+                664 => {
+                    // 664: add [64], #1, [64]
+                    if !code.valid[207] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 668: jf #0, #673
+                    computer.current_position = 668;
+                    if !code.valid[208] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 673;
+                    continue 'dispatch;
+                }
+                671 => {
+                    // 671: out [661]
+                    if !code.valid[209] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(661));
+                    computer.current_position = 673;
+                    continue 'dispatch;
+                }
+                673 => {
+                    // 673: mul [64], #2, [64]
+                    if !code.valid[210] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 677: arb #-10
+                    computer.current_position = 677;
+                    if !code.valid[211] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -10) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 679: eq rb+6, #33, [63]
+                    computer.current_position = 679;
+                    if !code.valid[212] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 6)?);
+                    let b = 33;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 683: jt [63], #693
+                    computer.current_position = 683;
+                    if !code.valid[213] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 693;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 686;
+                    continue 'dispatch;
+                }
+                686 => {
+                    // 686: add [64], #1, [64]
+                    if !code.valid[214] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 690: jf #0, #695
+                    computer.current_position = 690;
+                    if !code.valid[215] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 695;
+                    continue 'dispatch;
+                }
+                693 => {
+                    // 693: out [679]
+                    if !code.valid[216] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(679));
+                    computer.current_position = 695;
+                    continue 'dispatch;
+                }
+                695 => {
+                    // 695: mul [64], #2, [64]
+                    if !code.valid[217] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 699: arb #-7
+                    computer.current_position = 699;
+                    if !code.valid[218] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -7) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 701: eq #35, rb+7, [63]
+                    computer.current_position = 701;
+                    if !code.valid[219] {
+                        break 'compiled;
+                    }
+                    let a = 35;
+                    let b = computer.read_at(relative(computer, 1, 7)?);
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 705: jt [63], #715
+                    computer.current_position = 705;
+                    if !code.valid[220] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 715;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 708;
+                    continue 'dispatch;
+                }
+                708 => {
+                    // 708: add [64], #1, [64]
+                    if !code.valid[221] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 712: jf #0, #717
+                    computer.current_position = 712;
+                    if !code.valid[222] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 717;
+                    continue 'dispatch;
+                }
+                715 => {
+                    // 715: out [701]
+                    if !code.valid[223] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(701));
+                    computer.current_position = 717;
+                    continue 'dispatch;
+                }
+                717 => {
+                    // 717: mul [64], #2, [64]
+                    if !code.valid[224] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 721: arb #6
+                    computer.current_position = 721;
+                    if !code.valid[225] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 6) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 723: eq rb+5, #37, [63]
+                    computer.current_position = 723;
+                    if !code.valid[226] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 5)?);
+                    let b = 37;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 727: jt [63], #735
+                    computer.current_position = 727;
+                    if !code.valid[227] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 735;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 730;
+                    continue 'dispatch;
+                }
+                730 => {
+                    // 730: out [723]
+                    if !code.valid[228] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(723));
+                    // 732: jf #0, #739
+                    computer.current_position = 732;
+                    if !code.valid[229] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 739;
+                    continue 'dispatch;
+                }
+                735 => {
+                    // 735: add [64], #1, [64]
+                    if !code.valid[230] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 739;
+                    continue 'dispatch;
+                }
+                739 => {
+                    // 739: mul [64], #2, [64]
+                    if !code.valid[231] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 743: arb #-4
+                    computer.current_position = 743;
+                    if !code.valid[232] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -4) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 745: mul rb+5, #1, [63]
+                    computer.current_position = 745;
+                    if !code.valid[233] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 5)?);
+                    let b = 1;
+                    let destination = 63;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 749: eq [63], #34, [63]
+                    computer.current_position = 749;
+                    if !code.valid[234] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = 34;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 753: jt [63], #765
+                    computer.current_position = 753;
+                    if !code.valid[235] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 765;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 756;
+                    continue 'dispatch;
+                }
+                756 => {
+                    // 756: out [745]
+                    if !code.valid[236] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(745));
+                    // 758: add [64], #1, [64]
+                    computer.current_position = 758;
+                    if !code.valid[237] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 762: jt #1, #765
+                    computer.current_position = 762;
+                    if !code.valid[238] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 765;
+                    continue 'dispatch;
+                }
+                765 => {
+                    // 765: mul [64], #2, [64]
+                    if !code.valid[239] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 769: arb #29
+                    computer.current_position = 769;
+                    if !code.valid[240] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 29) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 771: jf rb-7, #783
+                    computer.current_position = 771;
+                    if !code.valid[241] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(relative(computer, 0, -7)?) == 0 {
+                        computer.current_position = 783;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 774;
+                    continue 'dispatch;
+                }
+                774 => {
+                    // 774: out [771]
+                    if !code.valid[242] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(771));
+                    // 776: add [64], #1, [64]
+                    computer.current_position = 776;
+                    if !code.valid[243] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 780: jt #1, #783
+                    computer.current_position = 780;
+                    if !code.valid[244] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 783;
+                    continue 'dispatch;
+                }
+                783 => {
+                    // 783: mul [64], #2, [64]
+                    if !code.valid[245] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 787: arb #-28
+                    computer.current_position = 787;
+                    if !code.valid[246] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -28) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 789: add rb+6, #0, [63]
+                    computer.current_position = 789;
+                    if !code.valid[247] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 6)?);
+                    let b = 0;
+                    let destination = 63;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 793: eq [63], #29, [63]
+                    computer.current_position = 793;
+                    if !code.valid[248] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = 29;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 797: jt [63], #809
+                    computer.current_position = 797;
+                    if !code.valid[249] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 809;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 800;
+                    continue 'dispatch;
+                }
+                800 => {
+                    // 800: out [789]
+                    if !code.valid[250] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(789));
+                    // 802: add [64], #1, [64]
+                    computer.current_position = 802;
+                    if !code.valid[251] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 806: jf #0, #809
+                    computer.current_position = 806;
+                    if !code.valid[252] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 809;
+                    continue 'dispatch;
+                }
+                809 => {
+                    // 809: mul [64], #2, [64]
+                    if !code.valid[253] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 813: arb #5
+                    computer.current_position = 813;
+                    if !code.valid[254] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 5) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 815: mul rb+2, #1, [63]
+                    computer.current_position = 815;
+                    if !code.valid[255] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 2)?);
+                    let b = 1;
+                    let destination = 63;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 819: eq [63], #20, [63]
+                    computer.current_position = 819;
+                    if !code.valid[256] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = 20;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 823: jt [63], #829
+                    computer.current_position = 823;
+                    if !code.valid[257] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 829;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 826;
+                    continue 'dispatch;
+                }
+                826 => {
+                    // 826: jf #0, #835
+                    if !code.valid[258] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 835;
+                    continue 'dispatch;
+                }
+                829 => {
+                    // 829: out [815]
+                    if !code.valid[259] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(815));
+                    // 831: add [64], #1, [64]
+                    computer.current_position = 831;
+                    if !code.valid[260] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 835;
+                    continue 'dispatch;
+                }
+                835 => {
+                    // 835: mul [64], #2, [64]
+                    if !code.valid[261] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 839: arb #-1
+                    computer.current_position = 839;
+                    if !code.valid[262] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -1) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 841: add rb+6, #0, [63]
+                    computer.current_position = 841;
+                    if !code.valid[263] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 6)?);
+                    let b = 0;
+                    let destination = 63;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 845: eq [63], #35, [63]
+                    computer.current_position = 845;
+                    if !code.valid[264] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(63);
+                    let b = 35;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 849: jt [63], #859
+                    computer.current_position = 849;
+                    if !code.valid[265] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 859;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 852;
+                    continue 'dispatch;
+                }
+                852 => {
+                    // 852: add [64], #1, [64]
+                    if !code.valid[266] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 856: jt #1, #861
+                    computer.current_position = 856;
+                    if !code.valid[267] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 861;
+                    continue 'dispatch;
+                }
+                859 => {
+                    // 859: out [841]
+                    if !code.valid[268] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(841));
+                    computer.current_position = 861;
+                    continue 'dispatch;
+                }
+                861 => {
+                    // 861: mul [64], #2, [64]
+                    if !code.valid[269] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 865: arb #2
+                    computer.current_position = 865;
+                    if !code.valid[270] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 2) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 867: lt rb-3, #25, [63]
+                    computer.current_position = 867;
+                    if !code.valid[271] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = 25;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 871: jt [63], #879
+                    computer.current_position = 871;
+                    if !code.valid[272] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 879;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 874;
+                    continue 'dispatch;
+                }
+                874 => {
+                    // 874: out [867]
+                    if !code.valid[273] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(867));
+                    // 876: jt #1, #883
+                    computer.current_position = 876;
+                    if !code.valid[274] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 883;
+                    continue 'dispatch;
+                }
+                879 => {
+                    // 879: add [64], #1, [64]
+                    if !code.valid[275] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 883;
+                    continue 'dispatch;
+                }
+                883 => {
+                    // 883: mul [64], #2, [64]
+                    if !code.valid[276] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 2;
+                    let destination = 64;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 887: arb #13
+                    computer.current_position = 887;
+                    if !code.valid[277] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 13) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 889: jt rb+3, #901
+                    computer.current_position = 889;
+                    if !code.valid[278] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(relative(computer, 0, 3)?) != 0 {
+                        computer.current_position = 901;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 892;
+                    continue 'dispatch;
+                }
+                892 => {
+                    // 892: out [889]
+                    if !code.valid[279] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(889));
+                    // 894: add [64], #1, [64]
+                    computer.current_position = 894;
+                    if !code.valid[280] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(64);
+                    let b = 1;
+                    let destination = 64;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 898: jf #0, #901
+                    computer.current_position = 898;
+                    if !code.valid[281] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 901;
+                    continue 'dispatch;
+                }
+                901 => {
+                    // 901: out [64]
+                    if !code.valid[282] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(64));
+                    // 903: hlt
+                    computer.current_position = 903;
+                    if !code.valid[283] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                904 => {
+                    // 904: add #0, #27, rb+1
+                    if !code.valid[284] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 27;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 908: add #915, #0, rb+0
+                    computer.current_position = 908;
+                    if !code.valid[285] {
+                        break 'compiled;
+                    }
+                    let a = 915;
+                    let b = 0;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 912: jf #0, #922
+                    computer.current_position = 912;
+                    if !code.valid[286] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 922;
+                    continue 'dispatch;
+                }
+                915 => {
+                    // 915: add rb+1, #22987, rb+1
+                    if !code.valid[287] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 1)?);
+                    let b = 22987;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 919: out rb+1
+                    computer.current_position = 919;
+                    if !code.valid[288] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(relative(computer, 0, 1)?));
+                    // 921: hlt
+                    computer.current_position = 921;
+                    if !code.valid[289] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                922 => {
+                    // 922: arb #3
+                    if !code.valid[290] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 924: lt rb-2, #3, [63]
+                    computer.current_position = 924;
+                    if !code.valid[291] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = 3;
+                    let destination = 63;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 928: jt [63], #964
+                    computer.current_position = 928;
+                    if !code.valid[292] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(63) != 0 {
+                        computer.current_position = 964;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 931;
+                    continue 'dispatch;
+                }
+                931 => {
+                    // 931: add rb-2, #-1, rb+1
+                    if !code.valid[293] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = -1;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 935: add #0, #942, rb+0
+                    computer.current_position = 935;
+                    if !code.valid[294] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 942;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 939: jf #0, #922
+                    computer.current_position = 939;
+                    if !code.valid[295] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 922;
+                    continue 'dispatch;
+                }
+                942 => {
+                    // 942: add #0, rb+1, rb-1
+                    if !code.valid[296] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = computer.read_at(relative(computer, 1, 1)?);
+                    let destination = relative(computer, 2, -1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 946: add rb-2, #-3, rb+1
+                    computer.current_position = 946;
+                    if !code.valid[297] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = -3;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 950: add #0, #957, rb+0
+                    computer.current_position = 950;
+                    if !code.valid[298] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 957;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 954: jf #0, #922
+                    computer.current_position = 954;
+                    if !code.valid[299] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 922;
+                    continue 'dispatch;
+                }
+                957 => {
+                    // 957: add rb+1, rb-1, rb-2
+                    if !code.valid[300] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 1)?);
+                    let b = computer.read_at(relative(computer, 1, -1)?);
+                    let destination = relative(computer, 2, -2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 961: jt #1, #968
+                    computer.current_position = 961;
+                    if !code.valid[301] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 968;
+                    continue 'dispatch;
+                }
+                964 => {
+                    // 964: mul rb-2, #1, rb-2
+                    if !code.valid[302] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = 1;
+                    let destination = relative(computer, 2, -2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 968;
+                    continue 'dispatch;
+                }
+                968 => {
+                    // 968: arb #-3
+                    if !code.valid[303] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 970: jt #1, rb+0
+                    computer.current_position = 970;
+                    if !code.valid[304] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, 0)?))?;
+                    continue 'dispatch;
+                }
+                _ => break 'compiled,
+            }
+        }
+
+        let state = computer.step(io)?;
+        if let Some(address) = computer.last_write() {
+            code.written(computer, address);
+        }
+        match state {
+            State::Running | State::Output(_) => {}
+            other => return Ok(other),
+        }
+    }
+}
+
+// Which compiled instructions are still what's in memory
+struct Code {
+    owners: Vec<Option<usize>>,
+    valid: Vec<bool>,
+}
+
+impl Code {
+    fn new(computer: &Computer) -> Code {
+        let mut owners = vec![None; PROGRAM.len()];
+        for (i, &(start, length)) in INSTRUCTIONS.iter().enumerate() {
+            for owner in &mut owners[start..start + length] {
+                *owner = Some(i);
+            }
+        }
+
+        let valid = INSTRUCTIONS
+            .iter()
+            .map(|&instruction| unchanged(computer, instruction))
+            .collect();
+
+        Code { owners, valid }
+    }
+
+    fn written(&mut self, computer: &Computer, address: usize) {
+        if let Some(&Some(i)) = self.owners.get(address) {
+            self.valid[i] = unchanged(computer, INSTRUCTIONS[i]);
+        }
+    }
+}
+
+fn unchanged(computer: &Computer, (start, length): (usize, usize)) -> bool {
+    (start..start + length).all(|address| computer.read_at(address) == PROGRAM[address])
+}
+
+fn write(computer: &mut Computer, code: &mut Code, address: usize, value: i64) {
+    computer.write_at(address, value);
+    code.written(computer, address);
+}
+
+fn relative(computer: &Computer, parameter: usize, offset: i64) -> Result<usize, IntcodeError> {
+    match i64::checked_add(computer.relative_base as i64, offset) {
+        Some(value) => address(computer, parameter, value),
+        None => Err(IntcodeError::AddressOverflow {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+            parameter,
+        }),
+    }
+}
+
+fn address(computer: &Computer, parameter: usize, address: i64) -> Result<usize, IntcodeError> {
+    if address < 0 {
+        return Err(IntcodeError::NegativeAddress {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+            parameter,
+            address,
+        });
+    }
+    Ok(address as usize)
+}
@@ -0,0 +1,2430 @@
+// Compiled from a 2368-value Intcode program by `intcode compile`. Don't edit it by hand;
+// compile the program again instead.
+//
+// Each instruction that was found in the program is Rust code here. Any that weren't, or that the
+// program has written over since, are run by the interpreter. So is any add or multiply that
+// overflows, and everything once memory has a value too big for an i64, so the computer's
+// arithmetic decides what happens. Nothing is recorded in the computer's history. A computer that
+// isn't `Computer::is_standard`, with opcodes of its own, an earlier spec level or limits, is run
+// by the interpreter the whole way.
+
+use intcode::{Computer, IntcodeError, Io, State};
+
+const PROGRAM: &[i64] = &[
+    2, 380, 379, 385, 1008, 2367, 810138, 381, 1005, 381, 12, 99, 109, 2368, 1101, 0, 0, 383, 1102,
+    0, 1, 382, 21001, 382, 0, 1, 20101, 0, 383, 2, 21102, 37, 1, 0, 1106, 0, 578, 4, 382, 4, 383,
+    204, 1, 1001, 382, 1, 382, 1007, 382, 36, 381, 1005, 381, 22, 1001, 383, 1, 383, 1007, 383, 24,
+    381, 1005, 381, 18, 1006, 385, 69, 99, 104, -1, 104, 0, 4, 386, 3, 384, 1007, 384, 0, 381, 1005,
+    381, 94, 107, 0, 384, 381, 1005, 381, 108, 1105, 1, 161, 107, 1, 392, 381, 1006, 381, 161, 1102,
+    1, -1, 384, 1106, 0, 119, 1007, 392, 34, 381, 1006, 381, 161, 1101, 0, 1, 384, 20101, 0, 392, 1,
+    21101, 22, 0, 2, 21101, 0, 0, 3, 21101, 0, 138, 0, 1105, 1, 549, 1, 392, 384, 392, 21001, 392,
+    0, 1, 21102, 22, 1, 2, 21101, 3, 0, 3, 21101, 161, 0, 0, 1106, 0, 549, 1101, 0, 0, 384, 20001,
+    388, 390, 1, 21001, 389, 0, 2, 21102, 1, 180, 0, 1106, 0, 578, 1206, 1, 213, 1208, 1, 2, 381,
+    1006, 381, 205, 20001, 388, 390, 1, 20101, 0, 389, 2, 21101, 0, 205, 0, 1106, 0, 393, 1002, 390,
+    -1, 390, 1101, 0, 1, 384, 21002, 388, 1, 1, 20001, 389, 391, 2, 21102, 228, 1, 0, 1106, 0, 578,
+    1206, 1, 261, 1208, 1, 2, 381, 1006, 381, 253, 21002, 388, 1, 1, 20001, 389, 391, 2, 21101, 0,
+    253, 0, 1105, 1, 393, 1002, 391, -1, 391, 1102, 1, 1, 384, 1005, 384, 161, 20001, 388, 390, 1,
+    20001, 389, 391, 2, 21102, 279, 1, 0, 1105, 1, 578, 1206, 1, 316, 1208, 1, 2, 381, 1006, 381,
+    304, 20001, 388, 390, 1, 20001, 389, 391, 2, 21101, 0, 304, 0, 1106, 0, 393, 1002, 390, -1, 390,
+    1002, 391, -1, 391, 1101, 1, 0, 384, 1005, 384, 161, 21002, 388, 1, 1, 20102, 1, 389, 2, 21102,
+    1, 0, 3, 21102, 1, 338, 0, 1105, 1, 549, 1, 388, 390, 388, 1, 389, 391, 389, 21001, 388, 0, 1,
+    21002, 389, 1, 2, 21101, 4, 0, 3, 21101, 365, 0, 0, 1105, 1, 549, 1007, 389, 23, 381, 1005, 381,
+    75, 104, -1, 104, 0, 104, 0, 99, 0, 1, 0, 0, 0, 0, 0, 0, 213, 16, 19, 1, 1, 18, 109, 3, 21202,
+    -2, 1, 1, 22102, 1, -1, 2, 21101, 0, 0, 3, 21102, 1, 414, 0, 1105, 1, 549, 21201, -2, 0, 1,
+    21201, -1, 0, 2, 21101, 429, 0, 0, 1106, 0, 601, 2101, 0, 1, 435, 1, 386, 0, 386, 104, -1, 104,
+    0, 4, 386, 1001, 387, -1, 387, 1005, 387, 451, 99, 109, -3, 2106, 0, 0, 109, 8, 22202, -7, -6,
+    -3, 22201, -3, -5, -3, 21202, -4, 64, -2, 2207, -3, -2, 381, 1005, 381, 492, 21202, -2, -1, -1,
+    22201, -3, -1, -3, 2207, -3, -2, 381, 1006, 381, 481, 21202, -4, 8, -2, 2207, -3, -2, 381, 1005,
+    381, 518, 21202, -2, -1, -1, 22201, -3, -1, -3, 2207, -3, -2, 381, 1006, 381, 507, 2207, -3, -4,
+    381, 1005, 381, 540, 21202, -4, -1, -1, 22201, -3, -1, -3, 2207, -3, -4, 381, 1006, 381, 529,
+    21201, -3, 0, -7, 109, -8, 2106, 0, 0, 109, 4, 1202, -2, 36, 566, 201, -3, 566, 566, 101, 639,
+    566, 566, 1202, -1, 1, 0, 204, -3, 204, -2, 204, -1, 109, -4, 2105, 1, 0, 109, 3, 1202, -1, 36,
+    594, 201, -2, 594, 594, 101, 639, 594, 594, 20102, 1, 0, -2, 109, -3, 2105, 1, 0, 109, 3, 22102,
+    24, -2, 1, 22201, 1, -1, 1, 21102, 1, 439, 2, 21102, 1, 233, 3, 21102, 1, 864, 4, 21101, 0, 630,
+    0, 1106, 0, 456, 21201, 1, 1503, -2, 109, -3, 2105, 1, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 2, 0, 0,
+    2, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 2, 2, 0, 2, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 1,
+    0, 0, 2, 2, 0, 0, 0, 2, 2, 2, 0, 0, 0, 2, 0, 2, 2, 0, 0, 0, 0, 2, 2, 2, 0, 0, 0, 2, 2, 0, 0, 0,
+    2, 0, 1, 1, 0, 0, 0, 2, 2, 2, 2, 2, 0, 0, 2, 0, 2, 0, 2, 0, 2, 0, 2, 0, 0, 0, 0, 2, 2, 0, 0, 2,
+    0, 2, 2, 2, 2, 0, 1, 1, 0, 0, 2, 0, 2, 0, 0, 2, 0, 2, 0, 0, 0, 2, 2, 2, 0, 0, 2, 0, 2, 2, 0, 2,
+    2, 2, 0, 0, 0, 0, 0, 2, 2, 0, 1, 1, 0, 2, 0, 2, 0, 2, 2, 2, 2, 0, 0, 2, 2, 0, 0, 2, 0, 2, 2, 0,
+    2, 2, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 1, 1, 0, 0, 2, 0, 0, 0, 2, 2, 2, 0, 0, 0, 0, 0, 2, 0,
+    2, 0, 0, 0, 2, 0, 2, 2, 2, 0, 0, 2, 0, 2, 0, 0, 0, 0, 1, 1, 0, 2, 0, 0, 0, 2, 0, 2, 2, 0, 0, 0,
+    0, 2, 2, 0, 2, 0, 0, 2, 2, 0, 0, 0, 0, 0, 2, 0, 2, 2, 0, 0, 0, 0, 1, 1, 0, 2, 2, 2, 0, 2, 0, 0,
+    2, 0, 2, 0, 0, 0, 2, 2, 0, 2, 0, 0, 0, 0, 2, 0, 0, 2, 0, 2, 0, 2, 0, 0, 2, 0, 1, 1, 0, 0, 0, 0,
+    0, 0, 2, 2, 0, 0, 2, 2, 0, 2, 2, 0, 2, 0, 0, 0, 2, 2, 0, 0, 0, 0, 0, 2, 0, 0, 2, 2, 2, 0, 1, 1,
+    0, 2, 2, 0, 2, 0, 2, 2, 0, 2, 2, 2, 0, 2, 0, 0, 2, 2, 0, 0, 2, 2, 0, 2, 0, 0, 0, 0, 0, 2, 0, 0,
+    0, 0, 1, 1, 0, 0, 2, 0, 0, 2, 2, 0, 2, 2, 0, 2, 2, 0, 2, 2, 0, 0, 2, 0, 0, 0, 0, 0, 2, 2, 0, 0,
+    0, 2, 2, 0, 0, 0, 1, 1, 0, 0, 2, 2, 0, 2, 0, 2, 2, 0, 2, 2, 0, 0, 2, 2, 2, 0, 0, 0, 0, 0, 0, 0,
+    2, 0, 0, 2, 0, 0, 0, 2, 0, 0, 1, 1, 0, 2, 2, 2, 0, 0, 2, 0, 0, 0, 2, 0, 0, 2, 0, 2, 0, 0, 2, 2,
+    0, 0, 0, 2, 0, 2, 2, 2, 0, 0, 0, 0, 2, 0, 1, 1, 0, 0, 0, 2, 2, 0, 2, 0, 0, 2, 0, 0, 0, 2, 2, 2,
+    0, 0, 0, 2, 2, 0, 0, 2, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 1, 1, 0, 0, 2, 2, 2, 0, 0, 2, 0, 0, 0, 0,
+    0, 2, 0, 2, 2, 2, 2, 0, 0, 0, 2, 0, 0, 2, 0, 2, 0, 2, 0, 2, 2, 0, 1, 1, 0, 0, 0, 2, 0, 0, 0, 0,
+    0, 0, 0, 2, 0, 0, 2, 2, 0, 2, 2, 0, 0, 2, 0, 2, 2, 0, 2, 0, 0, 0, 0, 2, 0, 0, 1, 1, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 6, 16, 92, 72, 80, 96, 26, 54, 61, 9,
+    5, 36, 81, 14, 76, 83, 59, 88, 74, 16, 69, 57, 76, 35, 51, 42, 88, 8, 89, 80, 10, 96, 1, 1, 18,
+    93, 46, 94, 5, 40, 63, 18, 90, 31, 7, 8, 46, 96, 13, 53, 21, 13, 66, 64, 67, 26, 11, 77, 23, 46,
+    8, 20, 97, 24, 26, 9, 11, 65, 36, 71, 13, 35, 18, 81, 66, 40, 88, 89, 56, 9, 24, 8, 78, 16, 44,
+    20, 57, 1, 69, 13, 71, 77, 37, 68, 31, 18, 32, 60, 37, 70, 8, 3, 46, 41, 18, 50, 78, 87, 4, 94,
+    91, 82, 96, 76, 15, 73, 47, 85, 6, 8, 92, 7, 46, 72, 68, 90, 22, 78, 6, 66, 1, 26, 98, 85, 80,
+    66, 95, 39, 62, 81, 52, 35, 98, 71, 58, 8, 55, 16, 93, 75, 77, 47, 36, 41, 91, 39, 20, 97, 13,
+    5, 31, 67, 91, 96, 10, 75, 7, 95, 59, 43, 90, 94, 25, 89, 21, 66, 98, 11, 2, 49, 67, 25, 96, 91,
+    72, 51, 41, 47, 1, 76, 30, 55, 20, 5, 97, 61, 73, 43, 79, 13, 16, 81, 1, 27, 11, 96, 47, 89, 48,
+    26, 25, 34, 37, 64, 13, 53, 69, 41, 30, 39, 93, 80, 34, 75, 41, 30, 42, 42, 49, 68, 49, 37, 40,
+    62, 93, 77, 86, 49, 72, 52, 5, 78, 31, 86, 86, 62, 60, 56, 17, 19, 80, 2, 39, 49, 50, 85, 63,
+    48, 61, 95, 82, 21, 18, 85, 16, 86, 45, 75, 28, 97, 33, 21, 56, 96, 40, 33, 95, 69, 53, 75, 47,
+    70, 51, 80, 92, 4, 54, 79, 59, 42, 15, 30, 86, 27, 86, 63, 64, 36, 27, 98, 49, 58, 78, 5, 16,
+    57, 61, 32, 14, 25, 51, 75, 96, 93, 25, 87, 20, 76, 32, 96, 96, 39, 84, 48, 62, 82, 11, 36, 1,
+    11, 44, 71, 86, 58, 4, 74, 35, 3, 31, 3, 27, 52, 78, 96, 10, 43, 16, 93, 93, 61, 23, 54, 90, 47,
+    81, 70, 81, 26, 89, 17, 63, 60, 48, 29, 77, 53, 80, 80, 12, 79, 80, 76, 37, 80, 79, 54, 17, 73,
+    68, 15, 40, 64, 81, 5, 62, 74, 27, 42, 72, 93, 2, 21, 46, 29, 76, 51, 61, 13, 19, 21, 96, 45,
+    38, 47, 87, 47, 67, 95, 82, 56, 51, 32, 1, 73, 59, 83, 65, 33, 92, 8, 94, 14, 45, 60, 20, 87,
+    82, 1, 29, 9, 15, 10, 76, 90, 27, 80, 30, 65, 9, 79, 2, 97, 41, 75, 8, 68, 23, 37, 19, 80, 22,
+    15, 52, 93, 79, 79, 23, 61, 37, 5, 88, 28, 5, 44, 31, 36, 20, 37, 71, 45, 21, 25, 16, 2, 79, 28,
+    67, 19, 47, 9, 19, 64, 46, 8, 88, 29, 75, 65, 22, 64, 32, 78, 20, 88, 48, 72, 90, 84, 50, 59,
+    63, 20, 86, 58, 50, 97, 14, 61, 10, 68, 45, 81, 43, 27, 95, 95, 80, 91, 68, 17, 83, 55, 49, 41,
+    9, 33, 51, 19, 60, 54, 24, 43, 68, 36, 60, 5, 20, 97, 14, 55, 70, 35, 27, 96, 80, 32, 3, 63, 52,
+    70, 31, 2, 58, 3, 70, 54, 35, 83, 87, 83, 50, 14, 97, 47, 38, 44, 71, 52, 3, 97, 83, 24, 36, 11,
+    45, 5, 87, 21, 80, 88, 98, 45, 42, 37, 96, 28, 42, 72, 47, 39, 58, 78, 23, 24, 50, 78, 1, 87,
+    81, 32, 49, 21, 60, 28, 33, 29, 5, 38, 36, 8, 59, 52, 66, 67, 15, 95, 87, 61, 67, 80, 54, 58,
+    36, 89, 72, 96, 78, 32, 58, 37, 39, 76, 43, 69, 20, 96, 26, 71, 98, 50, 36, 46, 18, 68, 24, 50,
+    43, 32, 95, 70, 18, 18, 66, 84, 18, 13, 44, 44, 6, 4, 42, 37, 31, 88, 18, 82, 29, 41, 88, 12,
+    96, 58, 61, 72, 72, 79, 80, 60, 48, 15, 26, 24, 29, 45, 7, 36, 2, 16, 31, 13, 60, 13, 84, 53, 4,
+    5, 94, 52, 39, 8, 14, 6, 30, 70, 75, 46, 13, 38, 57, 24, 24, 69, 51, 87, 96, 65, 57, 57, 14, 10,
+    27, 97, 98, 18, 4, 92, 47, 6, 17, 66, 93, 3, 82, 83, 56, 75, 82, 75, 92, 35, 68, 1, 43, 51, 24,
+    13, 57, 33, 87, 62, 92, 38, 61, 90, 1, 95, 45, 4, 70, 63, 34, 43, 67, 5, 91, 75, 23, 55, 27, 70,
+    52, 16, 78, 87, 46, 2, 56, 89, 88, 58, 23, 95, 31, 98, 96, 22, 11, 61, 29, 55, 77, 50, 55, 96,
+    64, 33, 14, 51, 25, 47, 48, 3, 15, 2, 18, 63, 12, 56, 47, 88, 74, 32, 87, 21, 74, 53, 37, 93,
+    21, 37, 9, 42, 16, 39, 57, 57, 59, 57, 96, 88, 17, 14, 5, 85, 18, 40, 54, 47, 80, 22, 35, 84,
+    10, 43, 91, 10, 82, 85, 52, 70, 69, 64, 44, 93, 77, 72, 80, 39, 86, 20, 44, 48, 24, 72, 810138,
+];
+
+// Where each compiled instruction starts and how many values it takes up
+const INSTRUCTIONS: &[(usize, usize)] = &[
+    (0, 4), (4, 4), (8, 3), (11, 1), (12, 2), (14, 4), (18, 4), (22, 4), (26, 4), (30, 4), (34, 3),
+    (37, 2), (39, 2), (41, 2), (43, 4), (47, 4), (51, 3), (54, 4), (58, 4), (62, 3), (65, 3),
+    (68, 1), (69, 2), (71, 2), (73, 2), (75, 2), (77, 4), (81, 3), (84, 4), (88, 3), (91, 3),
+    (94, 4), (98, 3), (101, 4), (105, 3), (108, 4), (112, 3), (115, 4), (119, 4), (123, 4),
+    (127, 4), (131, 4), (135, 3), (138, 4), (142, 4), (146, 4), (150, 4), (154, 4), (158, 3),
+    (161, 4), (165, 4), (169, 4), (173, 4), (177, 3), (180, 3), (183, 4), (187, 3), (190, 4),
+    (194, 4), (198, 4), (202, 3), (205, 4), (209, 4), (213, 4), (217, 4), (221, 4), (225, 3),
+    (228, 3), (231, 4), (235, 3), (238, 4), (242, 4), (246, 4), (250, 3), (253, 4), (257, 4),
+    (261, 3), (264, 4), (268, 4), (272, 4), (276, 3), (279, 3), (282, 4), (286, 3), (289, 4),
+    (293, 4), (297, 4), (301, 3), (304, 4), (308, 4), (312, 4), (316, 3), (319, 4), (323, 4),
+    (327, 4), (331, 4), (335, 3), (338, 4), (342, 4), (346, 4), (350, 4), (354, 4), (358, 4),
+    (362, 3), (365, 4), (369, 3), (372, 2), (374, 2), (376, 2), (378, 1), (393, 2), (395, 4),
+    (399, 4), (403, 4), (407, 4), (411, 3), (414, 4), (418, 4), (422, 4), (426, 3), (429, 4),
+    (433, 4), (437, 2), (439, 2), (441, 2), (443, 4), (447, 3), (450, 1), (451, 2), (453, 3),
+    (456, 2), (458, 4), (462, 4), (466, 4), (470, 4), (474, 3), (477, 4), (481, 4), (485, 4),
+    (489, 3), (492, 4), (496, 4), (500, 3), (503, 4), (507, 4), (511, 4), (515, 3), (518, 4),
+    (522, 3), (525, 4), (529, 4), (533, 4), (537, 3), (540, 4), (544, 2), (546, 3), (549, 2),
+    (551, 4), (555, 4), (559, 4), (563, 4), (567, 2), (569, 2), (571, 2), (573, 2), (575, 3),
+    (578, 2), (580, 4), (584, 4), (588, 4), (592, 4), (596, 2), (598, 3), (601, 2), (603, 4),
+    (607, 4), (611, 4), (615, 4), (619, 4), (623, 4), (627, 3), (630, 4), (634, 2), (636, 3),
+    (639, 4), (643, 4), (647, 4), (651, 4), (655, 4), (659, 4), (663, 4), (667, 4), (671, 4),
+    (675, 4),
+];
+
+pub fn run(computer: &mut Computer, io: &mut impl Io) -> Result<(), IntcodeError> {
+    if run_until_input(computer, io)? == State::NeedsInput {
+        return Err(IntcodeError::MissingInput {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+        });
+    }
+    Ok(())
+}
+
+pub fn run_until_input(computer: &mut Computer, io: &mut impl Io) -> Result<State, IntcodeError> {
+    if !computer.is_standard() {
+        return computer.run_until_input(io);
+    }
+    let mut code = Code::new(computer);
+
+    'dispatch: loop {
+        'compiled: {
+            if computer.has_big_values() {
+                break 'compiled;
+            }
+            match computer.current_position {
+                0 => {
+                    // 0: mul [380], [379], [385]
+                    if !code.valid[0] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(380);
+                    let b = computer.read_at(379);
+                    let destination = 385;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 4;
+                    continue 'dispatch;
+                }
+                4 => {
+                    // 4: eq [2367], #810138, [381]
+                    if !code.valid[1] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(2367);
+                    let b = 810138;
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 8: jt [381], #12
+                    computer.current_position = 8;
+                    if !code.valid[2] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) != 0 {
+                        computer.current_position = 12;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 11;
+                    continue 'dispatch;
+                }
+                11 => {
+                    // 11: hlt
+                    if !code.valid[3] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                12 => {
+                    // 12: arb #2368
+                    if !code.valid[4] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 2368) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 14: add #0, #0, [383]
+                    computer.current_position = 14;
+                    if !code.valid[5] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 0;
+                    let destination = 383;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 18;
+                    continue 'dispatch;
+                }
+                18 => {
+                    // 18: mul #0, #1, [382]
+                    if !code.valid[6] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 1;
+                    let destination = 382;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 22;
+                    continue 'dispatch;
+                }
+                22 => {
+                    // 22: add [382], #0, rb+1
+                    if !code.valid[7] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(382);
+                    let b = 0;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 26: add #0, [383], rb+2
+                    computer.current_position = 26;
+                    if !code.valid[8] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = computer.read_at(383);
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 30: mul #37, #1, rb+0
+                    computer.current_position = 30;
+                    if !code.valid[9] {
+                        break 'compiled;
+                    }
+                    let a = 37;
+                    let b = 1;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 34: jf #0, #578
+                    computer.current_position = 34;
+                    if !code.valid[10] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 578;
+                    continue 'dispatch;
+                }
+                37 => {
+                    // 37: out [382]
+                    if !code.valid[11] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(382));
+                    // 39: out [383]
+                    computer.current_position = 39;
+                    if !code.valid[12] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(383));
+                    // 41: out rb+1
+                    computer.current_position = 41;
+                    if !code.valid[13] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(relative(computer, 0, 1)?));
+                    // 43: add [382], #1, [382]
+                    computer.current_position = 43;
+                    if !code.valid[14] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(382);
+                    let b = 1;
+                    let destination = 382;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 47: lt [382], #36, [381]
+                    computer.current_position = 47;
+                    if !code.valid[15] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(382);
+                    let b = 36;
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 51: jt [381], #22
+                    computer.current_position = 51;
+                    if !code.valid[16] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) != 0 {
+                        computer.current_position = 22;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 54;
+                    continue 'dispatch;
+                }
+                54 => {
+                    // 54: add [383], #1, [383]
+                    if !code.valid[17] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(383);
+                    let b = 1;
+                    let destination = 383;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 58: lt [383], #24, [381]
+                    computer.current_position = 58;
+                    if !code.valid[18] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(383);
+                    let b = 24;
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 62: jt [381], #18
+                    computer.current_position = 62;
+                    if !code.valid[19] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) != 0 {
+                        computer.current_position = 18;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 65;
+                    continue 'dispatch;
+                }
+                65 => {
+                    // 65: jf [385], #69
+                    if !code.valid[20] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(385) == 0 {
+                        computer.current_position = 69;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 68;
+                    continue 'dispatch;
+                }
+                68 => {
+                    // 68: hlt
+                    if !code.valid[21] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                69 => {
+                    // 69: out #-1
+                    if !code.valid[22] {
+                        break 'compiled;
+                    }
+                    io.output(-1);
+                    // 71: out #0
+                    computer.current_position = 71;
+                    if !code.valid[23] {
+                        break 'compiled;
+                    }
+                    io.output(0);
+                    // 73: out [386]
+                    computer.current_position = 73;
+                    if !code.valid[24] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(386));
+                    computer.current_position = 75;
+                    continue 'dispatch;
+                }
+                75 => {
+                    // 75: in [384]
+                    if !code.valid[25] {
+                        break 'compiled;
+                    }
+                    let value = match io.input() {
+                        Some(value) => value,
+                        None => return Ok(State::NeedsInput),
+                    };
+                    let destination = 384;
+                    write(computer, &mut code, destination, value);
+                    // 77: lt [384], #0, [381]
+                    computer.current_position = 77;
+                    if !code.valid[26] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(384);
+                    let b = 0;
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 81: jt [381], #94
+                    computer.current_position = 81;
+                    if !code.valid[27] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) != 0 {
+                        computer.current_position = 94;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 84;
+                    continue 'dispatch;
+                }
+                84 => {
+                    // 84: lt #0, [384], [381]
+                    if !code.valid[28] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = computer.read_at(384);
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 88: jt [381], #108
+                    computer.current_position = 88;
+                    if !code.valid[29] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) != 0 {
+                        computer.current_position = 108;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 91;
+                    continue 'dispatch;
+                }
+                91 => {
+                    // 91: jt #1, #161
+                    if !code.valid[30] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 161;
+                    continue 'dispatch;
+                }
+                94 => {
+                    // 94: lt #1, [392], [381]
+                    if !code.valid[31] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = computer.read_at(392);
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 98: jf [381], #161
+                    computer.current_position = 98;
+                    if !code.valid[32] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) == 0 {
+                        computer.current_position = 161;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 101;
+                    continue 'dispatch;
+                }
+                101 => {
+                    // 101: mul #1, #-1, [384]
+                    if !code.valid[33] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = -1;
+                    let destination = 384;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 105: jf #0, #119
+                    computer.current_position = 105;
+                    if !code.valid[34] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 119;
+                    continue 'dispatch;
+                }
+                108 => {
+                    // 108: lt [392], #34, [381]
+                    if !code.valid[35] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(392);
+                    let b = 34;
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 112: jf [381], #161
+                    computer.current_position = 112;
+                    if !code.valid[36] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) == 0 {
+                        computer.current_position = 161;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 115;
+                    continue 'dispatch;
+                }
+                115 => {
+                    // 115: add #0, #1, [384]
+                    if !code.valid[37] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 1;
+                    let destination = 384;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 119;
+                    continue 'dispatch;
+                }
+                119 => {
+                    // 119: add #0, [392], rb+1
+                    if !code.valid[38] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = computer.read_at(392);
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 123: add #22, #0, rb+2
+                    computer.current_position = 123;
+                    if !code.valid[39] {
+                        break 'compiled;
+                    }
+                    let a = 22;
+                    let b = 0;
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 127: add #0, #0, rb+3
+                    computer.current_position = 127;
+                    if !code.valid[40] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 0;
+                    let destination = relative(computer, 2, 3)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 131: add #0, #138, rb+0
+                    computer.current_position = 131;
+                    if !code.valid[41] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 138;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 135: jt #1, #549
+                    computer.current_position = 135;
+                    if !code.valid[42] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 549;
+                    continue 'dispatch;
+                }
+                138 => {
+                    // 138: add [392], [384], [392]
+                    if !code.valid[43] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(392);
+                    let b = computer.read_at(384);
+                    let destination = 392;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 142: add [392], #0, rb+1
+                    computer.current_position = 142;
+                    if !code.valid[44] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(392);
+                    let b = 0;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 146: mul #22, #1, rb+2
+                    computer.current_position = 146;
+                    if !code.valid[45] {
+                        break 'compiled;
+                    }
+                    let a = 22;
+                    let b = 1;
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 150: add #3, #0, rb+3
+                    computer.current_position = 150;
+                    if !code.valid[46] {
+                        break 'compiled;
+                    }
+                    let a = 3;
+                    let b = 0;
+                    let destination = relative(computer, 2, 3)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 154: add #161, #0, rb+0
+                    computer.current_position = 154;
+                    if !code.valid[47] {
+                        break 'compiled;
+                    }
+                    let a = 161;
+                    let b = 0;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 158: jf #0, #549
+                    computer.current_position = 158;
+                    if !code.valid[48] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 549;
+                    continue 'dispatch;
+                }
+                161 => {
+                    // 161: add #0, #0, [384]
+                    if !code.valid[49] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 0;
+                    let destination = 384;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 165: add [388], [390], rb+1
+                    computer.current_position = 165;
+                    if !code.valid[50] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(388);
+                    let b = computer.read_at(390);
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 169: add [389], #0, rb+2
+                    computer.current_position = 169;
+                    if !code.valid[51] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(389);
+                    let b = 0;
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 173: mul #1, #180, rb+0
+                    computer.current_position = 173;
+                    if !code.valid[52] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 180;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 177: jf #0, #578
+                    computer.current_position = 177;
+                    if !code.valid[53] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 578;
+                    continue 'dispatch;
+                }
+                180 => {
+                    // 180: jf rb+1, #213
+                    if !code.valid[54] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(relative(computer, 0, 1)?) == 0 {
+                        computer.current_position = 213;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 183;
+                    continue 'dispatch;
+                }
+                183 => {
+                    // 183: eq rb+1, #2, [381]
+                    if !code.valid[55] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 1)?);
+                    let b = 2;
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 187: jf [381], #205
+                    computer.current_position = 187;
+                    if !code.valid[56] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) == 0 {
+                        computer.current_position = 205;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 190;
+                    continue 'dispatch;
+                }
+                190 => {
+                    // 190: add [388], [390], rb+1
+                    if !code.valid[57] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(388);
+                    let b = computer.read_at(390);
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 194: add #0, [389], rb+2
+                    computer.current_position = 194;
+                    if !code.valid[58] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = computer.read_at(389);
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 198: add #0, #205, rb+0
+                    computer.current_position = 198;
+                    if !code.valid[59] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 205;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 202: jf #0, #393
+                    computer.current_position = 202;
+                    if !code.valid[60] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 393;
+                    continue 'dispatch;
+                }
+                205 => {
+                    // 205: mul [390], #-1, [390]
+                    if !code.valid[61] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(390);
+                    let b = -1;
+                    let destination = 390;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 209: add #0, #1, [384]
+                    computer.current_position = 209;
+                    if !code.valid[62] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 1;
+                    let destination = 384;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 213;
+                    continue 'dispatch;
+                }
+                213 => {
+                    // 213: mul [388], #1, rb+1
+                    if !code.valid[63] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(388);
+                    let b = 1;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 217: add [389], [391], rb+2
+                    computer.current_position = 217;
+                    if !code.valid[64] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(389);
+                    let b = computer.read_at(391);
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 221: mul #228, #1, rb+0
+                    computer.current_position = 221;
+                    if !code.valid[65] {
+                        break 'compiled;
+                    }
+                    let a = 228;
+                    let b = 1;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 225: jf #0, #578
+                    computer.current_position = 225;
+                    if !code.valid[66] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 578;
+                    continue 'dispatch;
+                }
+                228 => {
+                    // 228: jf rb+1, #261
+                    if !code.valid[67] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(relative(computer, 0, 1)?) == 0 {
+                        computer.current_position = 261;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 231;
+                    continue 'dispatch;
+                }
+                231 => {
+                    // 231: eq rb+1, #2, [381]
+                    if !code.valid[68] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 1)?);
+                    let b = 2;
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 235: jf [381], #253
+                    computer.current_position = 235;
+                    if !code.valid[69] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) == 0 {
+                        computer.current_position = 253;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 238;
+                    continue 'dispatch;
+                }
+                238 => {
+                    // 238: mul [388], #1, rb+1
+                    if !code.valid[70] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(388);
+                    let b = 1;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 242: add [389], [391], rb+2
+                    computer.current_position = 242;
+                    if !code.valid[71] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(389);
+                    let b = computer.read_at(391);
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 246: add #0, #253, rb+0
+                    computer.current_position = 246;
+                    if !code.valid[72] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 253;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 250: jt #1, #393
+                    computer.current_position = 250;
+                    if !code.valid[73] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 393;
+                    continue 'dispatch;
+                }
+                253 => {
+                    // 253: mul [391], #-1, [391]
+                    if !code.valid[74] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(391);
+                    let b = -1;
+                    let destination = 391;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 257: mul #1, #1, [384]
+                    computer.current_position = 257;
+                    if !code.valid[75] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 1;
+                    let destination = 384;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 261;
+                    continue 'dispatch;
+                }
+                261 => {
+                    // 261: jt [384], #161
+                    if !code.valid[76] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(384) != 0 {
+                        computer.current_position = 161;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 264;
+                    continue 'dispatch;
+                }
+                264 => {
+                    // 264: add [388], [390], rb+1
+                    if !code.valid[77] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(388);
+                    let b = computer.read_at(390);
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 268: add [389], [391], rb+2
+                    computer.current_position = 268;
+                    if !code.valid[78] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(389);
+                    let b = computer.read_at(391);
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 272: mul #279, #1, rb+0
+                    computer.current_position = 272;
+                    if !code.valid[79] {
+                        break 'compiled;
+                    }
+                    let a = 279;
+                    let b = 1;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 276: jt #1, #578
+                    computer.current_position = 276;
+                    if !code.valid[80] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 578;
+                    continue 'dispatch;
+                }
+                279 => {
+                    // 279: jf rb+1, #316
+                    if !code.valid[81] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(relative(computer, 0, 1)?) == 0 {
+                        computer.current_position = 316;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 282;
+                    continue 'dispatch;
+                }
+                282 => {
+                    // 282: eq rb+1, #2, [381]
+                    if !code.valid[82] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 1)?);
+                    let b = 2;
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a == b));
+                    // 286: jf [381], #304
+                    computer.current_position = 286;
+                    if !code.valid[83] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) == 0 {
+                        computer.current_position = 304;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 289;
+                    continue 'dispatch;
+                }
+                289 => {
+                    // 289: add [388], [390], rb+1
+                    if !code.valid[84] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(388);
+                    let b = computer.read_at(390);
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 293: add [389], [391], rb+2
+                    computer.current_position = 293;
+                    if !code.valid[85] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(389);
+                    let b = computer.read_at(391);
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 297: add #0, #304, rb+0
+                    computer.current_position = 297;
+                    if !code.valid[86] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 304;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 301: jf #0, #393
+                    computer.current_position = 301;
+                    if !code.valid[87] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 393;
+                    continue 'dispatch;
+                }
+                304 => {
+                    // 304: mul [390], #-1, [390]
+                    if !code.valid[88] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(390);
+                    let b = -1;
+                    let destination = 390;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 308: mul [391], #-1, [391]
+                    computer.current_position = 308;
+                    if !code.valid[89] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(391);
+                    let b = -1;
+                    let destination = 391;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 312: add #1, #0, [384]
+                    computer.current_position = 312;
+                    if !code.valid[90] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 0;
+                    let destination = 384;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 316;
+                    continue 'dispatch;
+                }
+                316 => {
+                    // 316: jt [384], #161
+                    if !code.valid[91] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(384) != 0 {
+                        computer.current_position = 161;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 319;
+                    continue 'dispatch;
+                }
+                319 => {
+                    // 319: mul [388], #1, rb+1
+                    if !code.valid[92] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(388);
+                    let b = 1;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 323: mul #1, [389], rb+2
+                    computer.current_position = 323;
+                    if !code.valid[93] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = computer.read_at(389);
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 327: mul #1, #0, rb+3
+                    computer.current_position = 327;
+                    if !code.valid[94] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 0;
+                    let destination = relative(computer, 2, 3)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 331: mul #1, #338, rb+0
+                    computer.current_position = 331;
+                    if !code.valid[95] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 338;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 335: jt #1, #549
+                    computer.current_position = 335;
+                    if !code.valid[96] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 549;
+                    continue 'dispatch;
+                }
+                338 => {
+                    // 338: add [388], [390], [388]
+                    if !code.valid[97] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(388);
+                    let b = computer.read_at(390);
+                    let destination = 388;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 342: add [389], [391], [389]
+                    computer.current_position = 342;
+                    if !code.valid[98] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(389);
+                    let b = computer.read_at(391);
+                    let destination = 389;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 346: add [388], #0, rb+1
+                    computer.current_position = 346;
+                    if !code.valid[99] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(388);
+                    let b = 0;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 350: mul [389], #1, rb+2
+                    computer.current_position = 350;
+                    if !code.valid[100] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(389);
+                    let b = 1;
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 354: add #4, #0, rb+3
+                    computer.current_position = 354;
+                    if !code.valid[101] {
+                        break 'compiled;
+                    }
+                    let a = 4;
+                    let b = 0;
+                    let destination = relative(computer, 2, 3)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 358: add #365, #0, rb+0
+                    computer.current_position = 358;
+                    if !code.valid[102] {
+                        break 'compiled;
+                    }
+                    let a = 365;
+                    let b = 0;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 362: jt #1, #549
+                    computer.current_position = 362;
+                    if !code.valid[103] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 549;
+                    continue 'dispatch;
+                }
+                365 => {
+                    // 365: lt [389], #23, [381]
+                    if !code.valid[104] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(389);
+                    let b = 23;
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 369: jt [381], #75
+                    computer.current_position = 369;
+                    if !code.valid[105] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) != 0 {
+                        computer.current_position = 75;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 372;
+                    continue 'dispatch;
+                }
+                372 => {
+                    // 372: out #-1
+                    if !code.valid[106] {
+                        break 'compiled;
+                    }
+                    io.output(-1);
+                    // 374: out #0
+                    computer.current_position = 374;
+                    if !code.valid[107] {
+                        break 'compiled;
+                    }
+                    io.output(0);
+                    // 376: out #0
+                    computer.current_position = 376;
+                    if !code.valid[108] {
+                        break 'compiled;
+                    }
+                    io.output(0);
+                    // 378: hlt
+                    computer.current_position = 378;
+                    if !code.valid[109] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                393 => {
+                    // 393: arb #3
+                    if !code.valid[110] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 395: mul rb-2, #1, rb+1
+                    computer.current_position = 395;
+                    if !code.valid[111] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = 1;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 399: mul #1, rb-1, rb+2
+                    computer.current_position = 399;
+                    if !code.valid[112] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = computer.read_at(relative(computer, 1, -1)?);
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 403: add #0, #0, rb+3
+                    computer.current_position = 403;
+                    if !code.valid[113] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 0;
+                    let destination = relative(computer, 2, 3)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 407: mul #1, #414, rb+0
+                    computer.current_position = 407;
+                    if !code.valid[114] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 414;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 411: jt #1, #549
+                    computer.current_position = 411;
+                    if !code.valid[115] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 549;
+                    continue 'dispatch;
+                }
+                414 => {
+                    // 414: add rb-2, #0, rb+1
+                    if !code.valid[116] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = 0;
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 418: add rb-1, #0, rb+2
+                    computer.current_position = 418;
+                    if !code.valid[117] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -1)?);
+                    let b = 0;
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 422: add #429, #0, rb+0
+                    computer.current_position = 422;
+                    if !code.valid[118] {
+                        break 'compiled;
+                    }
+                    let a = 429;
+                    let b = 0;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 426: jf #0, #601
+                    computer.current_position = 426;
+                    if !code.valid[119] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 601;
+                    continue 'dispatch;
+                }
+                429 => {
+                    // 429: add #0, rb+1, [435]
+                    if !code.valid[120] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = computer.read_at(relative(computer, 1, 1)?);
+                    let destination = 435;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 433: add [386], [0], [386]
+                    computer.current_position = 433;
+                    if !code.valid[121] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(386);
+                    let b = computer.read_at(0);
+                    let destination = 386;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 437;
+                    continue 'dispatch;
+                }
+                437 => {
+                    // 437: out #-1
+                    if !code.valid[122] {
+                        break 'compiled;
+                    }
+                    io.output(-1);
+                    computer.current_position = 439;
+                    continue 'dispatch;
+                }
+                439 => {
+                    // 439: out #0
+                    if !code.valid[123] {
+                        break 'compiled;
+                    }
+                    io.output(0);
+                    // 441: out [386]
+                    computer.current_position = 441;
+                    if !code.valid[124] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(386));
+                    // 443: add [387], #-1, [387]
+                    computer.current_position = 443;
+                    if !code.valid[125] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(387);
+                    let b = -1;
+                    let destination = 387;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 447: jt [387], #451
+                    computer.current_position = 447;
+                    if !code.valid[126] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(387) != 0 {
+                        computer.current_position = 451;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 450;
+                    continue 'dispatch;
+                }
+                450 => {
+                    // 450: hlt
+                    if !code.valid[127] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                451 => {
+                    // 451: arb #-3
+                    if !code.valid[128] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 453: jf #0, rb+0
+                    computer.current_position = 453;
+                    if !code.valid[129] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, 0)?))?;
+                    continue 'dispatch;
+                }
+                456 => {
+                    // 456: arb #8
+                    if !code.valid[130] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 8) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 458: mul rb-7, rb-6, rb-3
+                    computer.current_position = 458;
+                    if !code.valid[131] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -7)?);
+                    let b = computer.read_at(relative(computer, 1, -6)?);
+                    let destination = relative(computer, 2, -3)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 462: add rb-3, rb-5, rb-3
+                    computer.current_position = 462;
+                    if !code.valid[132] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -5)?);
+                    let destination = relative(computer, 2, -3)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 466: mul rb-4, #64, rb-2
+                    computer.current_position = 466;
+                    if !code.valid[133] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -4)?);
+                    let b = 64;
+                    let destination = relative(computer, 2, -2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 470: lt rb-3, rb-2, [381]
+                    computer.current_position = 470;
+                    if !code.valid[134] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -2)?);
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 474: jt [381], #492
+                    computer.current_position = 474;
+                    if !code.valid[135] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) != 0 {
+                        computer.current_position = 492;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 477;
+                    continue 'dispatch;
+                }
+                477 => {
+                    // 477: mul rb-2, #-1, rb-1
+                    if !code.valid[136] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = -1;
+                    let destination = relative(computer, 2, -1)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 481;
+                    continue 'dispatch;
+                }
+                481 => {
+                    // 481: add rb-3, rb-1, rb-3
+                    if !code.valid[137] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -1)?);
+                    let destination = relative(computer, 2, -3)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 485: lt rb-3, rb-2, [381]
+                    computer.current_position = 485;
+                    if !code.valid[138] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -2)?);
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 489: jf [381], #481
+                    computer.current_position = 489;
+                    if !code.valid[139] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) == 0 {
+                        computer.current_position = 481;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 492;
+                    continue 'dispatch;
+                }
+                492 => {
+                    // 492: mul rb-4, #8, rb-2
+                    if !code.valid[140] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -4)?);
+                    let b = 8;
+                    let destination = relative(computer, 2, -2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 496: lt rb-3, rb-2, [381]
+                    computer.current_position = 496;
+                    if !code.valid[141] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -2)?);
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 500: jt [381], #518
+                    computer.current_position = 500;
+                    if !code.valid[142] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) != 0 {
+                        computer.current_position = 518;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 503;
+                    continue 'dispatch;
+                }
+                503 => {
+                    // 503: mul rb-2, #-1, rb-1
+                    if !code.valid[143] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = -1;
+                    let destination = relative(computer, 2, -1)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 507;
+                    continue 'dispatch;
+                }
+                507 => {
+                    // 507: add rb-3, rb-1, rb-3
+                    if !code.valid[144] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -1)?);
+                    let destination = relative(computer, 2, -3)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 511: lt rb-3, rb-2, [381]
+                    computer.current_position = 511;
+                    if !code.valid[145] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -2)?);
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 515: jf [381], #507
+                    computer.current_position = 515;
+                    if !code.valid[146] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) == 0 {
+                        computer.current_position = 507;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 518;
+                    continue 'dispatch;
+                }
+                518 => {
+                    // 518: lt rb-3, rb-4, [381]
+                    if !code.valid[147] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -4)?);
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 522: jt [381], #540
+                    computer.current_position = 522;
+                    if !code.valid[148] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) != 0 {
+                        computer.current_position = 540;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 525;
+                    continue 'dispatch;
+                }
+                525 => {
+                    // 525: mul rb-4, #-1, rb-1
+                    if !code.valid[149] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -4)?);
+                    let b = -1;
+                    let destination = relative(computer, 2, -1)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 529;
+                    continue 'dispatch;
+                }
+                529 => {
+                    // 529: add rb-3, rb-1, rb-3
+                    if !code.valid[150] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -1)?);
+                    let destination = relative(computer, 2, -3)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 533: lt rb-3, rb-4, [381]
+                    computer.current_position = 533;
+                    if !code.valid[151] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(relative(computer, 1, -4)?);
+                    let destination = 381;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 537: jf [381], #529
+                    computer.current_position = 537;
+                    if !code.valid[152] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(381) == 0 {
+                        computer.current_position = 529;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 540;
+                    continue 'dispatch;
+                }
+                540 => {
+                    // 540: add rb-3, #0, rb-7
+                    if !code.valid[153] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = 0;
+                    let destination = relative(computer, 2, -7)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 544: arb #-8
+                    computer.current_position = 544;
+                    if !code.valid[154] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -8) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 546: jf #0, rb+0
+                    computer.current_position = 546;
+                    if !code.valid[155] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, 0)?))?;
+                    continue 'dispatch;
+                }
+                549 => {
+                    // 549: arb #4
+                    if !code.valid[156] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 4) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 551: mul rb-2, #36, [566]
+                    computer.current_position = 551;
+                    if !code.valid[157] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = 36;
+                    let destination = 566;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 555: add rb-3, [566], [566]
+                    computer.current_position = 555;
+                    if !code.valid[158] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -3)?);
+                    let b = computer.read_at(566);
+                    let destination = 566;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 559: add #639, [566], [566]
+                    computer.current_position = 559;
+                    if !code.valid[159] {
+                        break 'compiled;
+                    }
+                    let a = 639;
+                    let b = computer.read_at(566);
+                    let destination = 566;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 563: mul rb-1, #1, [0]
+                    computer.current_position = 563;
+                    if !code.valid[160] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -1)?);
+                    let b = 1;
+                    let destination = 0;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 567;
+                    continue 'dispatch;
+                }
+                567 => {
+                    // 567: out rb-3
+                    if !code.valid[161] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(relative(computer, 0, -3)?));
+                    // 569: out rb-2
+                    computer.current_position = 569;
+                    if !code.valid[162] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(relative(computer, 0, -2)?));
+                    // 571: out rb-1
+                    computer.current_position = 571;
+                    if !code.valid[163] {
+                        break 'compiled;
+                    }
+                    io.output(computer.read_at(relative(computer, 0, -1)?));
+                    // 573: arb #-4
+                    computer.current_position = 573;
+                    if !code.valid[164] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -4) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 575: jt #1, rb+0
+                    computer.current_position = 575;
+                    if !code.valid[165] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, 0)?))?;
+                    continue 'dispatch;
+                }
+                578 => {
+                    // 578: arb #3
+                    if !code.valid[166] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 580: mul rb-1, #36, [594]
+                    computer.current_position = 580;
+                    if !code.valid[167] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -1)?);
+                    let b = 36;
+                    let destination = 594;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 584: add rb-2, [594], [594]
+                    computer.current_position = 584;
+                    if !code.valid[168] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, -2)?);
+                    let b = computer.read_at(594);
+                    let destination = 594;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 588: add #639, [594], [594]
+                    computer.current_position = 588;
+                    if !code.valid[169] {
+                        break 'compiled;
+                    }
+                    let a = 639;
+                    let b = computer.read_at(594);
+                    let destination = 594;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 592: mul #1, [0], rb-2
+                    computer.current_position = 592;
+                    if !code.valid[170] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = computer.read_at(0);
+                    let destination = relative(computer, 2, -2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 596;
+                    continue 'dispatch;
+                }
+                596 => {
+                    // 596: arb #-3
+                    if !code.valid[171] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 598: jt #1, rb+0
+                    computer.current_position = 598;
+                    if !code.valid[172] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, 0)?))?;
+                    continue 'dispatch;
+                }
+                601 => {
+                    // 601: arb #3
+                    if !code.valid[173] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, 3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 603: mul #24, rb-2, rb+1
+                    computer.current_position = 603;
+                    if !code.valid[174] {
+                        break 'compiled;
+                    }
+                    let a = 24;
+                    let b = computer.read_at(relative(computer, 1, -2)?);
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 607: add rb+1, rb-1, rb+1
+                    computer.current_position = 607;
+                    if !code.valid[175] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 1)?);
+                    let b = computer.read_at(relative(computer, 1, -1)?);
+                    let destination = relative(computer, 2, 1)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 611: mul #1, #439, rb+2
+                    computer.current_position = 611;
+                    if !code.valid[176] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 439;
+                    let destination = relative(computer, 2, 2)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 615: mul #1, #233, rb+3
+                    computer.current_position = 615;
+                    if !code.valid[177] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 233;
+                    let destination = relative(computer, 2, 3)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 619: mul #1, #864, rb+4
+                    computer.current_position = 619;
+                    if !code.valid[178] {
+                        break 'compiled;
+                    }
+                    let a = 1;
+                    let b = 864;
+                    let destination = relative(computer, 2, 4)?;
+                    let value = match i64::checked_mul(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 623: add #0, #630, rb+0
+                    computer.current_position = 623;
+                    if !code.valid[179] {
+                        break 'compiled;
+                    }
+                    let a = 0;
+                    let b = 630;
+                    let destination = relative(computer, 2, 0)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 627: jf #0, #456
+                    computer.current_position = 627;
+                    if !code.valid[180] {
+                        break 'compiled;
+                    }
+                    computer.current_position = 456;
+                    continue 'dispatch;
+                }
+                630 => {
+                    // 630: add rb+1, #1503, rb-2
+                    if !code.valid[181] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(relative(computer, 0, 1)?);
+                    let b = 1503;
+                    let destination = relative(computer, 2, -2)?;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 634: arb #-3
+                    computer.current_position = 634;
+                    if !code.valid[182] {
+                        break 'compiled;
+                    }
+                    computer.relative_base = match i64::checked_add(computer.relative_base as i64, -3) {
+                        Some(base) => base as usize,
+                        None => break 'compiled,
+                    };
+                    // 636: jt #1, rb+0
+                    computer.current_position = 636;
+                    if !code.valid[183] {
+                        break 'compiled;
+                    }
+                    computer.current_position = address(computer, 1, computer.read_at(relative(computer, 1, 0)?))?;
+                    continue 'dispatch;
+                }
+                639 => {
+                    // 639: add [1], [1], [1]
+                    if !code.valid[184] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(1);
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 643: add [1], [1], [1]
+                    computer.current_position = 643;
+                    if !code.valid[185] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(1);
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 647: add [1], [1], [1]
+                    computer.current_position = 647;
+                    if !code.valid[186] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(1);
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 651: add [1], [1], [1]
+                    computer.current_position = 651;
+                    if !code.valid[187] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(1);
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 655: add [1], [1], [1]
+                    computer.current_position = 655;
+                    if !code.valid[188] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(1);
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 659: add [1], [1], [1]
+                    computer.current_position = 659;
+                    if !code.valid[189] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(1);
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 663: add [1], [1], [1]
+                    computer.current_position = 663;
+                    if !code.valid[190] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(1);
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 667: add [1], [1], [1]
+                    computer.current_position = 667;
+                    if !code.valid[191] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(1);
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 671: add [1], [1], [1]
+                    computer.current_position = 671;
+                    if !code.valid[192] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = computer.read_at(1);
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 675: add [0], [0], [0]
+                    computer.current_position = 675;
+                    if !code.valid[193] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(0);
+                    let b = computer.read_at(0);
+                    let destination = 0;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    computer.current_position = 679;
+                    continue 'dispatch;
+                }
+                _ => break 'compiled,
+            }
+        }
+
+        let state = computer.step(io)?;
+        if let Some(address) = computer.last_write() {
+            code.written(computer, address);
+        }
+        match state {
+            State::Running | State::Output(_) => {}
+            other => return Ok(other),
+        }
+    }
+}
+
+// Which compiled instructions are still what's in memory
+struct Code {
+    owners: Vec<Option<usize>>,
+    valid: Vec<bool>,
+}
+
+impl Code {
+    fn new(computer: &Computer) -> Code {
+        let mut owners = vec![None; PROGRAM.len()];
+        for (i, &(start, length)) in INSTRUCTIONS.iter().enumerate() {
+            for owner in &mut owners[start..start + length] {
+                *owner = Some(i);
+            }
+        }
+
+        let valid = INSTRUCTIONS
+            .iter()
+            .map(|&instruction| unchanged(computer, instruction))
+            .collect();
+
+        Code { owners, valid }
+    }
+
+    fn written(&mut self, computer: &Computer, address: usize) {
+        if let Some(&Some(i)) = self.owners.get(address) {
+            self.valid[i] = unchanged(computer, INSTRUCTIONS[i]);
+        }
+    }
+}
+
+fn unchanged(computer: &Computer, (start, length): (usize, usize)) -> bool {
+    (start..start + length).all(|address| computer.read_at(address) == PROGRAM[address])
+}
+
+fn write(computer: &mut Computer, code: &mut Code, address: usize, value: i64) {
+    computer.write_at(address, value);
+    code.written(computer, address);
+}
+
+fn relative(computer: &Computer, parameter: usize, offset: i64) -> Result<usize, IntcodeError> {
+    match i64::checked_add(computer.relative_base as i64, offset) {
+        Some(value) => address(computer, parameter, value),
+        None => Err(IntcodeError::AddressOverflow {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+            parameter,
+        }),
+    }
+}
+
+fn address(computer: &Computer, parameter: usize, address: i64) -> Result<usize, IntcodeError> {
+    if address < 0 {
+        return Err(IntcodeError::NegativeAddress {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+            parameter,
+            address,
+        });
+    }
+    Ok(address as usize)
+}
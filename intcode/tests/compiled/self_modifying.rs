@@ -0,0 +1,146 @@
+// Compiled from a 16-value Intcode program by `intcode compile`. Don't edit it by hand;
+// compile the program again instead.
+//
+// Each instruction that was found in the program is Rust code here. Any that weren't, or that the
+// program has written over since, are run by the interpreter. So is any add or multiply that
+// overflows, and everything once memory has a value too big for an i64, so the computer's
+// arithmetic decides what happens. Nothing is recorded in the computer's history. A computer that
+// isn't `Computer::is_standard`, with opcodes of its own, an earlier spec level or limits, is run
+// by the interpreter the whole way.
+
+use intcode::{Computer, IntcodeError, Io, State};
+
+const PROGRAM: &[i64] = &[
+    104, 0, 1001, 1, 1, 1, 1007, 1, 3, 15, 1005, 15, 0, 99, 0, 0,
+];
+
+// Where each compiled instruction starts and how many values it takes up
+const INSTRUCTIONS: &[(usize, usize)] = &[
+    (0, 2), (2, 4), (6, 4), (10, 3), (13, 1),
+];
+
+pub fn run(computer: &mut Computer, io: &mut impl Io) -> Result<(), IntcodeError> {
+    if run_until_input(computer, io)? == State::NeedsInput {
+        return Err(IntcodeError::MissingInput {
+            position: computer.current_position,
+            opcode: computer.read_at(computer.current_position),
+        });
+    }
+    Ok(())
+}
+
+pub fn run_until_input(computer: &mut Computer, io: &mut impl Io) -> Result<State, IntcodeError> {
+    if !computer.is_standard() {
+        return computer.run_until_input(io);
+    }
+    let mut code = Code::new(computer);
+
+    'dispatch: loop {
+        'compiled: {
+            if computer.has_big_values() {
+                break 'compiled;
+            }
+            match computer.current_position {
+                0 => {
+                    // 0: out #0
+                    if !code.valid[0] {
+                        break 'compiled;
+                    }
+                    io.output(0);
+                    computer.current_position = 2;
+                    continue 'dispatch;
+                }
+                2 => {
+                    // 2: add [1], #1, [1]
+                    if !code.valid[1] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = 1;
+                    let destination = 1;
+                    let value = match i64::checked_add(a, b) {
+                        Some(value) => value,
+                        None => break 'compiled,
+                    };
+                    write(computer, &mut code, destination, value);
+                    // 6: lt [1], #3, [15]
+                    computer.current_position = 6;
+                    if !code.valid[2] {
+                        break 'compiled;
+                    }
+                    let a = computer.read_at(1);
+                    let b = 3;
+                    let destination = 15;
+                    write(computer, &mut code, destination, i64::from(a < b));
+                    // 10: jt [15], #0
+                    computer.current_position = 10;
+                    if !code.valid[3] {
+                        break 'compiled;
+                    }
+                    if computer.read_at(15) != 0 {
+                        computer.current_position = 0;
+                        continue 'dispatch;
+                    }
+                    computer.current_position = 13;
+                    continue 'dispatch;
+                }
+                13 => {
+                    // 13: hlt
+                    if !code.valid[4] {
+                        break 'compiled;
+                    }
+                    return Ok(State::Halted);
+                }
+                _ => break 'compiled,
+            }
+        }
+
+        let state = computer.step(io)?;
+        if let Some(address) = computer.last_write() {
+            code.written(computer, address);
+        }
+        match state {
+            State::Running | State::Output(_) => {}
+            other => return Ok(other),
+        }
+    }
+}
+
+// Which compiled instructions are still what's in memory
+struct Code {
+    owners: Vec<Option<usize>>,
+    valid: Vec<bool>,
+}
+
+impl Code {
+    fn new(computer: &Computer) -> Code {
+        let mut owners = vec![None; PROGRAM.len()];
+        for (i, &(start, length)) in INSTRUCTIONS.iter().enumerate() {
+            for owner in &mut owners[start..start + length] {
+                *owner = Some(i);
+            }
+        }
+
+        let valid = INSTRUCTIONS
+            .iter()
+            .map(|&instruction| unchanged(computer, instruction))
+            .collect();
+
+        Code { owners, valid }
+    }
+
+    fn written(&mut self, computer: &Computer, address: usize) {
+        if let Some(&Some(i)) = self.owners.get(address) {
+            self.valid[i] = unchanged(computer, INSTRUCTIONS[i]);
+        }
+    }
+}
+
+fn unchanged(computer: &Computer, (start, length): (usize, usize)) -> bool {
+    (start..start + length).all(|address| computer.read_at(address) == PROGRAM[address])
+}
+
+fn write(computer: &mut Computer, code: &mut Code, address: usize, value: i64) {
+    computer.write_at(address, value);
+    code.written(computer, address);
+}
@@ -0,0 +1,468 @@
+// Generates random, well-formed programs and runs each one every way there is to run it: a plain
+// reference VM in this file, the `Computer` stepped, run, traced, with history, stopped halfway
+// and restored from a snapshot, with each kind of arithmetic, and symbolically. They all have to
+// end up with the same memory, output and error. Everything is seeded, so a failure names the
+// seed and the program, and happens again the next time.
+use intcode::snapshot::Snapshot;
+use intcode::symbolic::End;
+use intcode::trace::Profile;
+use intcode::{Arithmetic, Computer, IntcodeError, Limits, State, Symbolic};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+
+const PROGRAMS: u64 = 500;
+
+// Where the generated code leaves its data. The relative base points at `DATA` the whole time,
+// and loop counters get cells of their own past it, so nothing but a loop touches them.
+const DATA: usize = 400;
+const DATA_CELLS: usize = 16;
+const COUNTERS: usize = DATA + DATA_CELLS;
+const MAX_LOOP_DEPTH: usize = 2;
+const MEMORY: usize = COUNTERS + MAX_LOOP_DEPTH;
+
+// xorshift64*, so the programs are the same on every machine without a dependency
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn between(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next() % (high - low + 1) as u64) as i64
+    }
+}
+
+struct Generator {
+    rng: Rng,
+    code: Vec<i64>,
+    inputs: usize,
+}
+
+impl Generator {
+    fn program(seed: u64) -> (Vec<i64>, Vec<i64>) {
+        let mut generator = Generator {
+            rng: Rng::new(seed),
+            code: vec![109, DATA as i64],
+            inputs: 0,
+        };
+        generator.block(0, false);
+        generator.code.push(99);
+        assert!(
+            generator.code.len() <= DATA,
+            "seed {} made too much code",
+            seed
+        );
+
+        let mut program = generator.code.clone();
+        program.resize(MEMORY, 0);
+        for cell in &mut program[DATA..COUNTERS] {
+            *cell = generator.rng.between(-100, 100);
+        }
+        let input = (0..generator.inputs)
+            .map(|_| generator.rng.between(-1000, 1000))
+            .collect();
+        (program, input)
+    }
+
+    fn block(&mut self, depth: usize, in_loop: bool) {
+        for _ in 0..self.rng.between(1, 8 - 2 * depth as i64) {
+            match self.rng.below(10) {
+                0..=3 => {
+                    let opcode = [1, 2, 7, 8][self.rng.below(4)];
+                    self.instruction(opcode, 2, true);
+                }
+                4 => self.instruction(4, 1, false),
+                // Inside a loop it'd read more input than anyone can tell from the code
+                5 if !in_loop => {
+                    self.instruction(3, 0, true);
+                    self.inputs += 1;
+                }
+                5 | 6 if depth < MAX_LOOP_DEPTH => self.skip(depth, in_loop),
+                7 if depth < MAX_LOOP_DEPTH => self.repeat(depth),
+                _ => self.instruction(1, 2, true),
+            }
+        }
+    }
+
+    // An instruction that reads `reads` parameters and maybe writes one, all of them data.
+    fn instruction(&mut self, opcode: i64, reads: usize, writes: bool) {
+        let start = self.code.len();
+        self.code.push(opcode);
+        let mut scale = 100;
+        for _ in 0..reads {
+            let mode = self.rng.below(3) as i64;
+            let value = match mode {
+                0 => (DATA + self.rng.below(DATA_CELLS)) as i64,
+                // Now and then one big enough that multiplying overflows
+                1 if self.rng.below(4) == 0 => self.rng.between(-(1 << 61), 1 << 61),
+                1 => self.rng.between(-50, 50),
+                _ => self.rng.below(DATA_CELLS) as i64,
+            };
+            self.code[start] += mode * scale;
+            self.code.push(value);
+            scale *= 10;
+        }
+        if writes {
+            if self.rng.below(2) == 0 {
+                self.code.push((DATA + self.rng.below(DATA_CELLS)) as i64);
+            } else {
+                self.code[start] += 2 * scale;
+                self.code.push(self.rng.below(DATA_CELLS) as i64);
+            }
+        }
+    }
+
+    // Jumps over a block, or not, depending on a data cell.
+    fn skip(&mut self, depth: usize, in_loop: bool) {
+        let opcode = [5, 6][self.rng.below(2)];
+        self.instruction(opcode, 1, false);
+        let start = self.code.len() - 2;
+        self.code[start] += 1000;
+        let target = self.code.len();
+        self.code.push(0);
+
+        self.block(depth + 1, in_loop);
+        self.code[target] = self.code.len() as i64;
+    }
+
+    // Runs a block 1 to 4 times, counting down in the counter for this depth.
+    fn repeat(&mut self, depth: usize) {
+        let counter = (COUNTERS + depth) as i64;
+        self.code
+            .extend_from_slice(&[1101, 0, self.rng.between(1, 4), counter]);
+        let start = self.code.len() as i64;
+        self.block(depth + 1, true);
+        self.code.extend_from_slice(&[1001, counter, -1, counter]);
+        self.code.extend_from_slice(&[1005, counter, start]);
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Outcome {
+    memory: Vec<i64>,
+    output: Vec<i64>,
+    // What kind of error there was, and where
+    error: Option<(String, usize)>,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  output: {:?}", self.output)?;
+        writeln!(f, "  error: {:?}", self.error)?;
+        write!(f, "  data: {:?}", &self.memory[DATA..])
+    }
+}
+
+fn error(e: &IntcodeError) -> (String, usize) {
+    let debug = format!("{:?}", e);
+    let kind = debug.split(|c: char| !c.is_alphanumeric()).next().unwrap();
+    (kind.to_string(), e.position())
+}
+
+fn outcome(computer: &Computer, output: Vec<i64>, result: Result<(), IntcodeError>) -> Outcome {
+    Outcome {
+        memory: (0..MEMORY)
+            .map(|address| computer.read_at(address))
+            .collect(),
+        output,
+        error: result.err().map(|e| error(&e)),
+    }
+}
+
+// The VM the way day 9 describes it, written as plainly as possible, with no code in common with
+// the `Computer`. Returns how many instructions it ran, too.
+fn reference(program: &[i64], input: &[i64]) -> (Outcome, u64) {
+    let mut memory: HashMap<usize, i64> = program.iter().copied().enumerate().collect();
+    let mut input = input.iter().copied();
+    let mut output = vec![];
+    let (mut position, mut relative_base) = (0_i64, 0_i64);
+    let mut steps = 0;
+
+    let error = loop {
+        let instruction = memory[&(position as usize)];
+        let opcode = instruction % 100;
+        let address = |memory: &HashMap<usize, i64>, parameter: i64| {
+            let mode = instruction / 10_i64.pow(parameter as u32 + 2) % 10;
+            let value = memory[&((position + parameter + 1) as usize)];
+            match mode {
+                0 => value as usize,
+                1 => (position + parameter + 1) as usize,
+                _ => (relative_base + value) as usize,
+            }
+        };
+        let read = |memory: &HashMap<usize, i64>, parameter: i64| {
+            *memory.get(&address(memory, parameter)).unwrap_or(&0)
+        };
+
+        steps += 1;
+        match opcode {
+            1 | 2 | 7 | 8 => {
+                let (a, b) = (read(&memory, 0), read(&memory, 1));
+                let value = match opcode {
+                    1 => a.checked_add(b),
+                    2 => a.checked_mul(b),
+                    7 => Some((a < b) as i64),
+                    _ => Some((a == b) as i64),
+                };
+                let value = match value {
+                    Some(value) => value,
+                    None => break Some(("Overflow".to_string(), position as usize)),
+                };
+                let to = address(&memory, 2);
+                memory.insert(to, value);
+                position += 4;
+            }
+            3 => {
+                let to = address(&memory, 0);
+                memory.insert(to, input.next().expect("generated too few inputs"));
+                position += 2;
+            }
+            4 => {
+                output.push(read(&memory, 0));
+                position += 2;
+            }
+            5 | 6 => {
+                if (read(&memory, 0) != 0) == (opcode == 5) {
+                    position = read(&memory, 1);
+                } else {
+                    position += 3;
+                }
+            }
+            9 => {
+                relative_base += read(&memory, 0);
+                position += 2;
+            }
+            99 => break None,
+            _ => panic!("generated opcode {} at {}", opcode, position),
+        }
+    };
+
+    let memory = (0..MEMORY)
+        .map(|address| *memory.get(&address).unwrap_or(&0))
+        .collect();
+    (
+        Outcome {
+            memory,
+            output,
+            error,
+        },
+        steps,
+    )
+}
+
+fn run(program: &[i64], input: &[i64]) -> Outcome {
+    let mut computer = Computer::new(program.to_vec());
+    let mut output = vec![];
+    let result = computer.run(&mut (VecDeque::from(input.to_vec()), &mut output));
+    outcome(&computer, output, result)
+}
+
+fn run_with(arithmetic: Arithmetic) -> impl Fn(&[i64], &[i64]) -> Outcome {
+    move |program, input| {
+        let mut computer = Computer::with_arithmetic(program.to_vec(), arithmetic);
+        let mut output = vec![];
+        let result = computer.run(&mut (VecDeque::from(input.to_vec()), &mut output));
+        outcome(&computer, output, result)
+    }
+}
+
+// One step at a time with history on. If it halts, every step is undone afterwards, which has to
+// get back to the program it started with.
+fn step_with_history(program: &[i64], input: &[i64]) -> Outcome {
+    let mut computer = Computer::new(program.to_vec());
+    computer.record_history();
+    let mut io = (VecDeque::from(input.to_vec()), vec![]);
+    let result = loop {
+        match computer.step(&mut io) {
+            Ok(State::Halted) => break Ok(()),
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+    let halted = result.is_ok();
+    let outcome = outcome(&computer, io.1, result);
+
+    if halted {
+        while computer.step_back().is_some() {}
+        for (address, &value) in program.iter().enumerate() {
+            assert_eq!(
+                computer.read_at(address),
+                value,
+                "stepping back didn't undo the write to {}",
+                address
+            );
+        }
+    }
+    outcome
+}
+
+fn traced(program: &[i64], input: &[i64]) -> Outcome {
+    let mut computer = Computer::new(program.to_vec());
+    let mut output = vec![];
+    let mut profile = Profile::new();
+    let result = computer.run_traced(
+        &mut (VecDeque::from(input.to_vec()), &mut output),
+        &mut profile,
+    );
+    let (expected, steps) = reference(program, input);
+    if expected.error.is_none() {
+        assert_eq!(profile.steps, steps, "the profile counted the wrong steps");
+    }
+    outcome(&computer, output, result)
+}
+
+// Stops halfway, saves a snapshot to bytes, and carries on from a new `Computer` made from it.
+fn snapshot_halfway(program: &[i64], input: &[i64]) -> Outcome {
+    let (_, steps) = reference(program, input);
+    let mut computer = Computer::new(program.to_vec());
+    computer.set_limits(Limits {
+        max_steps: Some(steps / 2),
+        ..Limits::default()
+    });
+    let mut io = (VecDeque::from(input.to_vec()), vec![]);
+    match computer.run(&mut io) {
+        Err(IntcodeError::StepLimit { .. }) => {}
+        result => return outcome(&computer, io.1, result),
+    }
+
+    let mut bytes = vec![];
+    computer.snapshot().write_to(&mut bytes).unwrap();
+    let mut computer = Computer::restore(&Snapshot::read_from(&bytes[..]).unwrap());
+    let result = computer.run(&mut io);
+    outcome(&computer, io.1, result)
+}
+
+// Runs the program with the input as variables, then picks the path the input takes.
+fn symbolic(program: &[i64], input: &[i64]) -> Option<Outcome> {
+    let values: BTreeMap<String, i64> = input
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| (format!("input{}", i), value))
+        .collect();
+    let values: BTreeMap<&str, i64> = values.iter().map(|(k, &v)| (k.as_str(), v)).collect();
+
+    let paths = Symbolic::new(program).max_paths(1024).run();
+    let path = paths.iter().find(|path| {
+        path.constraints.iter().all(|constraint| {
+            constraint
+                .expr
+                .evaluate(&values)
+                .is_some_and(|value| (value != 0) == constraint.nonzero)
+        })
+    })?;
+    if path.end != End::Halted {
+        return None;
+    }
+
+    let evaluate = |expr: &intcode::symbolic::Expr| expr.evaluate(&values);
+    Some(Outcome {
+        memory: (0..MEMORY)
+            .map(|address| evaluate(&path.read(address)))
+            .collect::<Option<_>>()?,
+        output: path.outputs.iter().map(evaluate).collect::<Option<_>>()?,
+        error: None,
+    })
+}
+
+fn program_text(program: &[i64]) -> String {
+    let end = program[..DATA]
+        .iter()
+        .rposition(|&value| value != 0)
+        .unwrap()
+        + 1;
+    let strings: Vec<String> = program[..end].iter().map(|v| v.to_string()).collect();
+    strings.join(",")
+}
+
+fn check(
+    seed: u64,
+    name: &str,
+    program: &[i64],
+    input: &[i64],
+    expected: &Outcome,
+    actual: &Outcome,
+) {
+    assert!(
+        actual == expected,
+        "{} diverged from the reference on seed {}\nprogram: {}\ninput: {:?}\nreference:\n{}\n{}:\n{}",
+        name,
+        seed,
+        program_text(program),
+        input,
+        expected,
+        name,
+        actual
+    );
+}
+
+#[test]
+fn every_way_of_running_a_program_agrees() {
+    type Run = Box<dyn Fn(&[i64], &[i64]) -> Outcome>;
+    let always: Vec<(&str, Run)> = vec![
+        ("run", Box::new(run)),
+        ("step with history", Box::new(step_with_history)),
+        ("run traced", Box::new(traced)),
+        ("snapshot halfway", Box::new(snapshot_halfway)),
+    ];
+    // These only agree with the reference when nothing overflows
+    let without_overflow: Vec<(&str, Run)> = vec![
+        ("wrapping", Box::new(run_with(Arithmetic::Wrapping))),
+        ("big integers", Box::new(run_with(Arithmetic::BigInt))),
+    ];
+
+    let mut overflowed = 0;
+    let mut followed_symbolically = 0;
+    for seed in 0..PROGRAMS {
+        let (program, input) = Generator::program(seed);
+        let (expected, _) = reference(&program, &input);
+
+        for (name, run) in &always {
+            check(
+                seed,
+                name,
+                &program,
+                &input,
+                &expected,
+                &run(&program, &input),
+            );
+        }
+        if expected.error.is_some() {
+            overflowed += 1;
+            continue;
+        }
+        for (name, run) in &without_overflow {
+            check(
+                seed,
+                name,
+                &program,
+                &input,
+                &expected,
+                &run(&program, &input),
+            );
+        }
+        if let Some(actual) = symbolic(&program, &input) {
+            check(seed, "symbolic", &program, &input, &expected, &actual);
+            followed_symbolically += 1;
+        }
+    }
+
+    // Make sure the generator is still making programs worth testing
+    assert!(overflowed > 0, "no program overflowed");
+    assert!(
+        followed_symbolically > PROGRAMS / 2,
+        "only {} programs could be followed symbolically",
+        followed_symbolically
+    );
+}
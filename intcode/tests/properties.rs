@@ -0,0 +1,281 @@
+// What every instruction has to do with any values, in any modes, wherever it and its operands
+// are in memory. The unit tests in `lib.rs` have the puzzles' examples.
+use intcode::{
+    assemble, disassemble, instruction, Computer, IntcodeError, Mode, State, UnexpectedMode,
+};
+use proptest::prelude::*;
+
+// Operands are kept in memory well past the instruction, each in a range of its own, and written
+// results go past those.
+const OPERANDS: usize = 200;
+const RESULTS: usize = 300;
+
+#[derive(Debug, Clone, Copy)]
+struct Operand {
+    mode: Mode,
+    value: i64,
+    // Where the value is, unless it's in immediate mode; how far into this operand's range
+    offset: usize,
+}
+
+fn mode() -> impl Strategy<Value = Mode> {
+    prop_oneof![
+        Just(Mode::Position),
+        Just(Mode::Immediate),
+        Just(Mode::Relative),
+    ]
+}
+
+fn write_mode() -> impl Strategy<Value = Mode> {
+    prop_oneof![Just(Mode::Position), Just(Mode::Relative)]
+}
+
+fn operand(values: impl Strategy<Value = i64>) -> impl Strategy<Value = Operand> {
+    (mode(), values, 0..10_usize).prop_map(|(mode, value, offset)| Operand {
+        mode,
+        value,
+        offset,
+    })
+}
+
+fn encode(opcode: i64, modes: &[Mode]) -> i64 {
+    modes
+        .iter()
+        .rev()
+        .fold(0, |modes, &mode| modes * 10 + mode as i64)
+        * 100
+        + opcode
+}
+
+// Where in memory an instruction is, and the relative base it runs with.
+#[derive(Debug, Clone, Copy)]
+struct Place {
+    start: usize,
+    relative_base: usize,
+}
+
+fn place() -> impl Strategy<Value = Place> {
+    (0..50_usize, 0..150_usize).prop_map(|(start, relative_base)| Place {
+        start,
+        relative_base,
+    })
+}
+
+// The parameter to use so `mode` points at `address`.
+fn parameter_for(mode: Mode, address: usize, place: Place) -> i64 {
+    match mode {
+        Mode::Position => address as i64,
+        Mode::Immediate => panic!("immediate mode doesn't point anywhere"),
+        Mode::Relative => address as i64 - place.relative_base as i64,
+    }
+}
+
+// A computer about to run `opcode` with `reads` and then maybe a written parameter.
+fn set_up(opcode: i64, reads: &[Operand], write: Option<(Mode, usize)>, place: Place) -> Computer {
+    let mut modes: Vec<Mode> = reads.iter().map(|operand| operand.mode).collect();
+    modes.extend(write.map(|(mode, _)| mode));
+
+    let mut computer = Computer::new(vec![]);
+    computer.current_position = place.start;
+    computer.relative_base = place.relative_base;
+    computer.write_at(place.start, encode(opcode, &modes));
+
+    for (i, operand) in reads.iter().enumerate() {
+        let parameter = match operand.mode {
+            Mode::Immediate => operand.value,
+            mode => {
+                let address = OPERANDS + 10 * i + operand.offset;
+                computer.write_at(address, operand.value);
+                parameter_for(mode, address, place)
+            }
+        };
+        computer.write_at(place.start + i + 1, parameter);
+    }
+    if let Some((mode, address)) = write {
+        let parameter = parameter_for(mode, address, place);
+        computer.write_at(place.start + reads.len() + 1, parameter);
+    }
+    computer
+}
+
+fn step(computer: &mut Computer) -> Result<State, IntcodeError> {
+    computer.step(&mut (vec![].into_iter(), vec![]))
+}
+
+proptest! {
+    #[test]
+    fn add_and_multiply_write_to_the_right_address(
+        opcode in prop_oneof![Just(1_i64), Just(2)],
+        a in operand(any::<i64>()),
+        b in operand(any::<i64>()),
+        write_mode in write_mode(),
+        result in RESULTS..RESULTS + 100,
+        place in place(),
+    ) {
+        let mut computer = set_up(opcode, &[a, b], Some((write_mode, result)), place);
+        let full_opcode = computer.read_at(place.start);
+        let expected = if opcode == 1 {
+            a.value.checked_add(b.value)
+        } else {
+            a.value.checked_mul(b.value)
+        };
+
+        match expected {
+            Some(expected) => {
+                prop_assert_eq!(step(&mut computer), Ok(State::Running));
+                prop_assert_eq!(computer.read_at(result), expected);
+                prop_assert_eq!(computer.current_position, place.start + 4);
+            }
+            None => {
+                prop_assert_eq!(
+                    step(&mut computer),
+                    Err(IntcodeError::Overflow { position: place.start, opcode: full_opcode })
+                );
+                prop_assert_eq!(computer.read_at(result), 0);
+                prop_assert_eq!(computer.current_position, place.start);
+            }
+        }
+    }
+
+    #[test]
+    fn comparisons_write_one_or_zero(
+        opcode in prop_oneof![Just(7_i64), Just(8)],
+        a in operand(-3..3_i64),
+        b in operand(-3..3_i64),
+        write_mode in write_mode(),
+        result in RESULTS..RESULTS + 100,
+        place in place(),
+    ) {
+        let mut computer = set_up(opcode, &[a, b], Some((write_mode, result)), place);
+        computer.write_at(result, 42);
+        let expected = if opcode == 7 { a.value < b.value } else { a.value == b.value };
+
+        prop_assert_eq!(step(&mut computer), Ok(State::Running));
+        prop_assert_eq!(computer.read_at(result), expected as i64);
+        prop_assert_eq!(computer.current_position, place.start + 4);
+    }
+
+    #[test]
+    fn input_is_written_to_the_right_address(
+        value in any::<i64>(),
+        write_mode in write_mode(),
+        result in RESULTS..RESULTS + 100,
+        place in place(),
+    ) {
+        let mut computer = set_up(3, &[], Some((write_mode, result)), place);
+
+        prop_assert_eq!(
+            computer.step(&mut (vec![value].into_iter(), vec![])),
+            Ok(State::Running)
+        );
+        prop_assert_eq!(computer.read_at(result), value);
+        prop_assert_eq!(computer.current_position, place.start + 2);
+    }
+
+    #[test]
+    fn output_and_relative_base_adjustments_read_any_mode(
+        value in operand(-1000..1000_i64),
+        place in place(),
+    ) {
+        let mut computer = set_up(4, &[value], None, place);
+        prop_assert_eq!(step(&mut computer), Ok(State::Output(value.value)));
+        prop_assert_eq!(computer.current_position, place.start + 2);
+
+        // A negative relative base wraps around, and comes back out when it's added to a
+        // relative parameter
+        let mut computer = set_up(9, &[value], None, place);
+        prop_assert_eq!(step(&mut computer), Ok(State::Running));
+        prop_assert_eq!(
+            computer.relative_base as i64,
+            place.relative_base as i64 + value.value
+        );
+        prop_assert_eq!(computer.current_position, place.start + 2);
+    }
+
+    #[test]
+    fn jumps_go_where_they_say(
+        opcode in prop_oneof![Just(5_i64), Just(6)],
+        test in operand(-2..2_i64),
+        target in operand(0..1000_i64),
+        place in place(),
+    ) {
+        let mut computer = set_up(opcode, &[test, target], None, place);
+        let jumps = (test.value != 0) == (opcode == 5);
+
+        prop_assert_eq!(step(&mut computer), Ok(State::Running));
+        let expected = if jumps { target.value as usize } else { place.start + 3 };
+        prop_assert_eq!(computer.current_position, expected);
+    }
+
+    #[test]
+    fn jumps_never_read_past_their_operands(
+        opcode in prop_oneof![Just(5_i64), Just(6)],
+        test in operand(-2..2_i64),
+        target in operand(0..1000_i64),
+        after in prop::collection::vec(any::<i64>(), 3),
+        place in place(),
+    ) {
+        let mut plain = set_up(opcode, &[test, target], None, place);
+        let mut cluttered = plain.clone();
+        for (i, &value) in after.iter().enumerate() {
+            cluttered.write_at(place.start + 3 + i, value);
+        }
+
+        prop_assert_eq!(step(&mut cluttered), step(&mut plain));
+        prop_assert_eq!(cluttered.current_position, plain.current_position);
+        prop_assert_eq!(cluttered.relative_base, plain.relative_base);
+        prop_assert_eq!(cluttered.last_write(), plain.last_write());
+    }
+
+    #[test]
+    fn modes_round_trip_through_encoding(
+        opcode in 0..100_i64,
+        modes in prop::collection::vec(mode(), 0..=3),
+    ) {
+        let decoded = instruction(encode(opcode, &modes)).unwrap();
+
+        prop_assert_eq!(decoded.opcode, opcode);
+        for (i, &mode) in modes.iter().enumerate() {
+            prop_assert_eq!(decoded.mode(i), mode);
+        }
+        // Position modes at the end are leading zeros, so they aren't there to count
+        let counted = modes.iter().rposition(|&mode| mode != Mode::Position).map_or(0, |i| i + 1);
+        prop_assert_eq!(decoded.mode_count(), counted);
+    }
+
+    #[test]
+    fn the_first_bad_mode_digit_is_reported(
+        opcode in 0..100_i64,
+        digits in prop::collection::vec(0..10_i64, 1..=4),
+    ) {
+        let full_opcode = digits.iter().rev().fold(0, |modes, digit| modes * 10 + digit) * 100 + opcode;
+        let bad = digits.iter().position(|&digit| digit > 2);
+
+        match bad {
+            Some(parameter) => prop_assert_eq!(
+                instruction(full_opcode),
+                Err(UnexpectedMode { parameter, mode: digits[parameter] })
+            ),
+            None => prop_assert!(instruction(full_opcode).is_ok()),
+        }
+    }
+
+    #[test]
+    fn the_assembler_encodes_what_the_disassembler_decodes(
+        opcode in prop_oneof![1..=9_i64, Just(99)],
+        modes in prop::collection::vec(mode(), 3),
+        parameters in prop::collection::vec(0..1000_i64, 3),
+    ) {
+        let parameter_count = intcode::disassemble::mnemonic(opcode).unwrap().1;
+        let mut modes = modes[..parameter_count].to_vec();
+        // Written parameters can't be immediate, and there's nothing to assemble if they are
+        if matches!(opcode, 1 | 2 | 3 | 7 | 8) && modes.last() == Some(&Mode::Immediate) {
+            *modes.last_mut().unwrap() = Mode::Position;
+        }
+        let mut program = vec![encode(opcode, &modes)];
+        program.extend_from_slice(&parameters[..parameter_count]);
+
+        let listing: Vec<String> = disassemble(&program).iter().map(|line| line.to_string()).collect();
+        prop_assert_eq!(assemble(&listing.join("\n")), Ok(program));
+    }
+}
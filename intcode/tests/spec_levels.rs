@@ -0,0 +1,74 @@
+// The puzzles' own programs hold to the spec for the day they came out.
+use intcode::{parse_program, Computer, IntcodeError, Mode, SpecLevel, Violation};
+use std::fs;
+
+fn program(day: &str) -> Vec<i64> {
+    parse_program(&fs::read_to_string(format!("../aoc-{}-02/input", day)).unwrap()).unwrap()
+}
+
+fn run(level: SpecLevel, program: Vec<i64>, input: Vec<i64>) -> Result<Vec<i64>, IntcodeError> {
+    let mut output = vec![];
+    Computer::with_spec_level(program, level).run(&mut (input.into_iter(), &mut output))?;
+    Ok(output)
+}
+
+#[test]
+fn day_2_is_day_2() {
+    let mut program = program("02");
+    program[1] = 12;
+    program[2] = 2;
+
+    let mut strict = Computer::with_spec_level(program.clone(), SpecLevel::Day2);
+    strict.run(&mut (vec![].into_iter(), vec![])).unwrap();
+    let mut computer = Computer::new(program);
+    computer.run(&mut (vec![].into_iter(), vec![])).unwrap();
+
+    assert_eq!(strict.read_at(0), computer.read_at(0));
+}
+
+#[test]
+fn day_5_is_day_5() {
+    assert_eq!(
+        run(SpecLevel::Day5, program("05"), vec![5]),
+        run(SpecLevel::Day9, program("05"), vec![5])
+    );
+    assert_eq!(
+        run(SpecLevel::Day2, program("05"), vec![5]),
+        Err(IntcodeError::UnknownOpcode {
+            position: 0,
+            opcode: 3,
+        })
+    );
+}
+
+#[test]
+fn day_9_is_day_9() {
+    assert!(run(SpecLevel::Day9, program("09"), vec![1]).is_ok());
+    assert!(run(SpecLevel::Day5, program("09"), vec![1]).is_err());
+}
+
+#[test]
+fn modes_from_later_days_break_the_spec() {
+    // Day 5's program, reading its input in relative mode
+    let mut program = program("05");
+    program[0] = 203;
+
+    let error = run(SpecLevel::Day5, program, vec![1]).unwrap_err();
+    assert_eq!(
+        error,
+        IntcodeError::SpecViolation {
+            position: 0,
+            opcode: 203,
+            parameter: 0,
+            level: SpecLevel::Day5,
+            violation: Violation::Mode {
+                mode: Mode::Relative
+            },
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "Parameter 0 breaks the Day5 spec: it doesn't have Relative mode \
+         (instruction 203 at position 0)"
+    );
+}
@@ -0,0 +1,34 @@
+// Day 2's answer is a linear function of the noun and verb, so it can be solved for directly.
+use intcode::symbolic::End;
+use intcode::{parse_program, Computer, Symbolic};
+use std::fs;
+
+#[test]
+fn solve_day_2() {
+    let program = parse_program(&fs::read_to_string("../aoc-02-02/input").unwrap()).unwrap();
+    let paths = Symbolic::new(&program)
+        .variable(1, "noun")
+        .variable(2, "verb")
+        .run();
+
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].end, End::Halted);
+
+    let result = paths[0].read(0);
+    let linear = result.linear(&["noun", "verb"]).unwrap();
+    assert_eq!(
+        result.to_string(),
+        format!(
+            "{}*noun + verb + {}",
+            linear.coefficients[0], linear.constant
+        )
+    );
+
+    let values = linear.solve(&[0..=99, 0..=99], 19690720).unwrap();
+    let mut patched = program;
+    patched[1] = values[0];
+    patched[2] = values[1];
+    let mut computer = Computer::new(patched);
+    computer.run(&mut (vec![].into_iter(), vec![])).unwrap();
+    assert_eq!(computer.read_at(0), 19690720);
+}